@@ -1,6 +1,8 @@
 //!Simple, easy to understand and barebones math library for game and graphics development.
 
-/// Square floating point matrices of orders 2-4
+/// Square floating point matrices of orders 2-4,
+/// non-square matrices with 2-4 rows and columns,
+/// column major 4x4 matrices and symmetric 3x3 and 4x4 matrices.
 pub mod mat;
 
 /// 2D, 3D and 4D floating point vectors.
@@ -0,0 +1,1237 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+use crate::mat::colmat4f32::ColMat4f32;
+use crate::mat::convention::{Handedness, ProjectionConvention};
+use crate::mat::mat3f64::Mat3f64;
+use crate::mat::mat4f64::{Mat4f64, Mat4f64Cholesky, Mat4f64Decomposition, Mat4f64Ldlt, Mat4f64Lu};
+use crate::vec::vec3f64::Vec3f64;
+use crate::vec::vec4f64::Vec4f64;
+
+/// A 4x4 floating point matrix stored in column major order.
+/// It has the same layout as a [[f64; 4]; 4] of columns, which is what graphics APIs
+/// expect, so it can be uploaded without transposing (see as_slice()).
+/// Unlike Mat4f64 it does not implement Index, because m[i][j] would select column i
+/// and row j here but row i and column j there. Use col(), row() or the cols field.
+/// It has the same methods as Mat4f64, convert between the two with from_mat4() and to_mat4().
+/// Most methods work on the columns directly, or on the transpose by reinterpreting the
+/// columns as rows where the operation commutes with transposing (inverses, determinants,
+/// matrix functions). The projection, view, shear, reflection and shadow constructors
+/// build a Mat4f64 and transpose it, and tile(), with_oblique_near_plane(), decompose(),
+/// lu(), solve(), qr(), orthonormalized() and null_space() also transpose self first.
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bitcode", derive(bitcode::Encode, bitcode::Decode))]
+pub struct ColMat4f64 {
+    pub cols: [[f64; 4]; 4],
+}
+
+impl ColMat4f64 {
+    /// Creates a new matrix with user defined elements.
+    /// The user defined elements are in column major order.
+    pub const fn new(cols: [[f64; 4]; 4]) -> Self {
+        Self { cols }
+    }
+
+    /// Creates a new matrix with user defined elements.
+    /// The user defined elements are in row major order.
+    pub const fn new_from_rows(rows: [[f64; 4]; 4]) -> Self {
+        Self::new(rows).transposed()
+    }
+
+    /// Creates a new matrix with all elements equal to 0.0.
+    pub const fn zero() -> Self {
+        Self::new(Mat4f64::zero().rows)
+    }
+
+    /// Creates a new identity matrix.
+    pub const fn identity() -> Self {
+        Self::new(Mat4f64::identity().rows)
+    }
+
+    /// Creates a new column major matrix with the same elements as m.
+    pub const fn from_mat4(m: Mat4f64) -> Self {
+        Self::new(m.transposed().rows)
+    }
+
+    /// Returns a row major matrix with the same elements as self.
+    pub const fn to_mat4(&self) -> Mat4f64 {
+        self.as_transposed_mat4().transposed()
+    }
+
+    /// Reinterprets the columns of self as the rows of a Mat4f64, which is the transpose
+    /// of self. Used to implement operations without copying any elements.
+    const fn as_transposed_mat4(&self) -> Mat4f64 {
+        Mat4f64::new(self.cols)
+    }
+
+    /// Returns self but transposed
+    /// (Rows are now columns and columns are now rows).
+    pub const fn transposed(&self) -> Self {
+        Self::new(self.as_transposed_mat4().transposed().rows)
+    }
+
+    /// Transposes self
+    /// (Rows are now columns and columns are now rows).
+    pub const fn transpose(&mut self) {
+        *self = self.transposed()
+    }
+
+    /// Returns the matrix rows as arrays in row major order.
+    pub const fn as_row_major(&self) -> [[f64; 4]; 4] {
+        self.transposed().cols
+    }
+
+    /// Returns the matrix columns as arrays in column major order.
+    pub const fn as_col_major(&self) -> [[f64; 4]; 4] {
+        self.cols
+    }
+
+    /// Returns the elements of self in column major order without copying them,
+    /// ready to be uploaded to the GPU.
+    pub fn as_slice(&self) -> &[f64] {
+        self.cols.as_flattened()
+    }
+
+    /// Creates a new matrix from a flat array of elements in column major order.
+    pub const fn from_col_major_flat(elements: [f64; 16]) -> Self {
+        Self::new(Mat4f64::from_row_major_flat(elements).rows)
+    }
+
+    /// Returns the elements of self as a flat array in column major order.
+    pub const fn as_col_major_flat(&self) -> [f64; 16] {
+        self.as_transposed_mat4().as_row_major_flat()
+    }
+
+    /// Creates a new matrix from a flat array of elements in row major order.
+    pub const fn from_row_major_flat(elements: [f64; 16]) -> Self {
+        Self::from_mat4(Mat4f64::from_row_major_flat(elements))
+    }
+
+    /// Returns the elements of self as a flat array in row major order.
+    pub const fn as_row_major_flat(&self) -> [f64; 16] {
+        self.to_mat4().as_row_major_flat()
+    }
+
+    /// Creates a new matrix from row vectors.
+    pub const fn from_rows(rows: [Vec4f64; 4]) -> Self {
+        Self::from_mat4(Mat4f64::from_rows(rows))
+    }
+
+    /// Creates a new matrix from column vectors.
+    pub const fn from_cols(cols: [Vec4f64; 4]) -> Self {
+        Self::new([
+            cols[0].coords,
+            cols[1].coords,
+            cols[2].coords,
+            cols[3].coords,
+        ])
+    }
+
+    /// Creates a new diagonal matrix with the components of diagonal
+    /// on the main diagonal and all other elements equal to 0.0.
+    pub const fn from_diagonal(diagonal: Vec4f64) -> Self {
+        Self::new(Mat4f64::from_diagonal(diagonal).rows)
+    }
+
+    /// Creates the outer product of a and b (a * b^T).
+    pub const fn outer_product(a: Vec4f64, b: Vec4f64) -> Self {
+        // (a * b^T)^T = b * a^T
+        Self::new(Mat4f64::outer_product(b, a).rows)
+    }
+
+    /// Returns the row at index as a vector.
+    pub const fn row(&self, index: usize) -> Vec4f64 {
+        self.as_transposed_mat4().col(index)
+    }
+
+    /// Returns the column at index as a vector.
+    pub const fn col(&self, index: usize) -> Vec4f64 {
+        Vec4f64::new(self.cols[index])
+    }
+
+    /// Replaces the row at index with row.
+    pub const fn set_row(&mut self, index: usize, row: Vec4f64) {
+        self.cols[0][index] = row.coords[0];
+        self.cols[1][index] = row.coords[1];
+        self.cols[2][index] = row.coords[2];
+        self.cols[3][index] = row.coords[3];
+    }
+
+    /// Replaces the column at index with col.
+    pub const fn set_col(&mut self, index: usize, col: Vec4f64) {
+        self.cols[index] = col.coords;
+    }
+
+    /// Returns the elements on the main diagonal as a vector.
+    pub const fn diagonal(&self) -> Vec4f64 {
+        self.as_transposed_mat4().diagonal()
+    }
+
+    /// Returns the trace of self (the sum of the main diagonal).
+    pub const fn trace(&self) -> f64 {
+        self.as_transposed_mat4().trace()
+    }
+
+    /// The Frobenius norm of self
+    /// (the square root of the sum of all squared elements).
+    pub fn frobenius_norm(&self) -> f64 {
+        self.frobenius_norm_squared().sqrt()
+    }
+
+    /// The Frobenius norm of self, but squared.
+    /// This is faster to compute than frobenius_norm() and useful in some situations.
+    pub const fn frobenius_norm_squared(&self) -> f64 {
+        self.as_transposed_mat4().frobenius_norm_squared()
+    }
+
+    /// Creates a new translation matrix for homogeneous coordinates.
+    pub const fn from_translation(translation: Vec3f64) -> Self {
+        let t = &translation.coords;
+        Self::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [t[0], t[1], t[2], 1.0],
+        ])
+    }
+
+    /// Creates a new scaling matrix for homogeneous coordinates.
+    pub const fn from_scale(scale: Vec3f64) -> Self {
+        Self::new(Mat4f64::from_scale(scale).rows)
+    }
+
+    /// Creates a new matrix rotating around the x axis for homogeneous coordinates,
+    /// see Mat4f64::from_rotation_x().
+    pub fn from_rotation_x(angle: f64) -> Self {
+        // The transpose of a rotation is the rotation by the negated angle
+        Self::new(Mat4f64::from_rotation_x(-angle).rows)
+    }
+
+    /// Creates a new matrix rotating around the y axis for homogeneous coordinates,
+    /// see Mat4f64::from_rotation_y().
+    pub fn from_rotation_y(angle: f64) -> Self {
+        Self::new(Mat4f64::from_rotation_y(-angle).rows)
+    }
+
+    /// Creates a new matrix rotating around the z axis for homogeneous coordinates,
+    /// see Mat4f64::from_rotation_z().
+    pub fn from_rotation_z(angle: f64) -> Self {
+        Self::new(Mat4f64::from_rotation_z(-angle).rows)
+    }
+
+    /// Creates a new matrix rotating around an arbitrary axis for homogeneous coordinates,
+    /// see Mat4f64::from_axis_angle().
+    pub fn from_axis_angle(axis: Vec3f64, angle: f64) -> Self {
+        Self::new(Mat4f64::from_axis_angle(axis, -angle).rows)
+    }
+
+    /// Creates a new shear matrix for homogeneous coordinates, see Mat4f64::from_shear().
+    pub const fn from_shear(
+        x_by_y: f64,
+        x_by_z: f64,
+        y_by_x: f64,
+        y_by_z: f64,
+        z_by_x: f64,
+        z_by_y: f64,
+    ) -> Self {
+        Self::from_mat4(Mat4f64::from_shear(
+            x_by_y, x_by_z, y_by_x, y_by_z, z_by_x, z_by_y,
+        ))
+    }
+
+    /// Creates a new matrix reflecting across a plane for homogeneous coordinates,
+    /// see Mat4f64::from_reflection().
+    pub const fn from_reflection(plane: Vec4f64) -> Self {
+        Self::from_mat4(Mat4f64::from_reflection(plane))
+    }
+
+    /// Creates a new matrix projecting orthogonally onto a plane for homogeneous coordinates,
+    /// see Mat4f64::from_plane_projection().
+    pub const fn from_plane_projection(plane: Vec4f64) -> Self {
+        Self::from_mat4(Mat4f64::from_plane_projection(plane))
+    }
+
+    /// Creates a new matrix projecting points onto a plane as seen from a point light,
+    /// see Mat4f64::from_point_shadow().
+    pub const fn from_point_shadow(plane: Vec4f64, light_position: Vec3f64) -> Self {
+        Self::from_mat4(Mat4f64::from_point_shadow(plane, light_position))
+    }
+
+    /// Creates a new matrix projecting points onto a plane along the direction of a
+    /// directional light, see Mat4f64::from_directional_shadow().
+    pub const fn from_directional_shadow(plane: Vec4f64, light_direction: Vec3f64) -> Self {
+        Self::from_mat4(Mat4f64::from_directional_shadow(plane, light_direction))
+    }
+
+    /// Returns self followed by a translation (translation * self).
+    pub const fn translate(&self, translation: Vec3f64) -> Self {
+        Self::from_translation(translation).product(*self)
+    }
+
+    /// Returns self followed by a scaling (scale * self).
+    pub const fn scale(&self, scale: Vec3f64) -> Self {
+        Self::from_scale(scale).product(*self)
+    }
+
+    /// Returns self followed by a rotation around the x axis (rotation * self).
+    pub fn rotate_x(&self, angle: f64) -> Self {
+        Self::from_rotation_x(angle) * *self
+    }
+
+    /// Returns self followed by a rotation around the y axis (rotation * self).
+    pub fn rotate_y(&self, angle: f64) -> Self {
+        Self::from_rotation_y(angle) * *self
+    }
+
+    /// Returns self followed by a rotation around the z axis (rotation * self).
+    pub fn rotate_z(&self, angle: f64) -> Self {
+        Self::from_rotation_z(angle) * *self
+    }
+
+    /// Returns self followed by a rotation around an arbitrary unit axis (rotation * self).
+    pub fn rotate(&self, axis: Vec3f64, angle: f64) -> Self {
+        Self::from_axis_angle(axis, angle) * *self
+    }
+
+    /// Returns self followed by a shear (shear * self), see Mat4f64::from_shear().
+    pub const fn shear(
+        &self,
+        x_by_y: f64,
+        x_by_z: f64,
+        y_by_x: f64,
+        y_by_z: f64,
+        z_by_x: f64,
+        z_by_y: f64,
+    ) -> Self {
+        Self::from_shear(x_by_y, x_by_z, y_by_x, y_by_z, z_by_x, z_by_y).product(*self)
+    }
+
+    /// Creates a new perspective projection matrix from a vertical field of view
+    /// (in radians) and an aspect ratio (width / height), see Mat4f64::perspective().
+    pub fn perspective(
+        fov_y: f64,
+        aspect: f64,
+        near: f64,
+        far: f64,
+        convention: ProjectionConvention,
+    ) -> Self {
+        Self::from_mat4(Mat4f64::perspective(fov_y, aspect, near, far, convention))
+    }
+
+    /// Creates a new off-axis perspective projection matrix from the tangents of the
+    /// angles between the viewing direction and the four sides of the frustum,
+    /// see Mat4f64::perspective_off_axis().
+    pub const fn perspective_off_axis(
+        tan_left: f64,
+        tan_right: f64,
+        tan_down: f64,
+        tan_up: f64,
+        near: f64,
+        far: f64,
+        convention: ProjectionConvention,
+    ) -> Self {
+        Self::from_mat4(Mat4f64::perspective_off_axis(
+            tan_left, tan_right, tan_down, tan_up, near, far, convention,
+        ))
+    }
+
+    /// Creates a new perspective projection matrix from the bounds of the near plane,
+    /// see Mat4f64::frustum().
+    pub const fn frustum(
+        left: f64,
+        right: f64,
+        bottom: f64,
+        top: f64,
+        near: f64,
+        far: f64,
+        convention: ProjectionConvention,
+    ) -> Self {
+        Self::from_mat4(Mat4f64::frustum(
+            left, right, bottom, top, near, far, convention,
+        ))
+    }
+
+    /// Creates a new orthographic projection matrix from the bounds of the view volume,
+    /// see Mat4f64::orthographic().
+    pub const fn orthographic(
+        left: f64,
+        right: f64,
+        bottom: f64,
+        top: f64,
+        near: f64,
+        far: f64,
+        convention: ProjectionConvention,
+    ) -> Self {
+        Self::from_mat4(Mat4f64::orthographic(
+            left, right, bottom, top, near, far, convention,
+        ))
+    }
+
+    /// Returns the projection matrix for one tile of self split into a grid of
+    /// columns x rows tiles, see Mat4f64::tile().
+    pub const fn tile(&self, columns: usize, rows: usize, column: usize, row: usize) -> Self {
+        Self::from_mat4(self.to_mat4().tile(columns, rows, column, row))
+    }
+
+    /// Returns the projection matrix self with its near plane replaced by an arbitrary
+    /// clip plane, see Mat4f64::with_oblique_near_plane().
+    pub fn with_oblique_near_plane(
+        &self,
+        plane: Vec4f64,
+        convention: ProjectionConvention,
+    ) -> Self {
+        Self::from_mat4(self.to_mat4().with_oblique_near_plane(plane, convention))
+    }
+
+    /// Creates a new view matrix for a camera at eye looking at target,
+    /// see Mat4f64::look_at().
+    pub fn look_at(eye: Vec3f64, target: Vec3f64, up: Vec3f64, handedness: Handedness) -> Self {
+        Self::from_mat4(Mat4f64::look_at(eye, target, up, handedness))
+    }
+
+    /// Creates a new view matrix for a camera at eye looking in direction dir,
+    /// see Mat4f64::look_to().
+    pub fn look_to(eye: Vec3f64, dir: Vec3f64, up: Vec3f64, handedness: Handedness) -> Self {
+        Self::from_mat4(Mat4f64::look_to(eye, dir, up, handedness))
+    }
+
+    /// Returns the camera position of a view matrix, see Mat4f64::camera_position().
+    pub const fn camera_position(&self) -> Vec3f64 {
+        let c = &self.cols;
+        Vec3f64::new([
+            -(c[0][0] * c[3][0] + c[0][1] * c[3][1] + c[0][2] * c[3][2]),
+            -(c[1][0] * c[3][0] + c[1][1] * c[3][1] + c[1][2] * c[3][2]),
+            -(c[2][0] * c[3][0] + c[2][1] * c[3][1] + c[2][2] * c[3][2]),
+        ])
+    }
+
+    /// Returns the world space direction the camera of a view matrix looks at,
+    /// see Mat4f64::camera_forward().
+    pub const fn camera_forward(&self, handedness: Handedness) -> Vec3f64 {
+        let c = &self.cols;
+        match handedness {
+            Handedness::Right => Vec3f64::new([-c[0][2], -c[1][2], -c[2][2]]),
+            Handedness::Left => Vec3f64::new([c[0][2], c[1][2], c[2][2]]),
+        }
+    }
+
+    /// Returns the world space right direction of the camera of a view matrix,
+    /// see Mat4f64::camera_right().
+    pub const fn camera_right(&self) -> Vec3f64 {
+        let c = &self.cols;
+        Vec3f64::new([c[0][0], c[1][0], c[2][0]])
+    }
+
+    /// Returns the world space up direction of the camera of a view matrix,
+    /// see Mat4f64::camera_up().
+    pub const fn camera_up(&self) -> Vec3f64 {
+        let c = &self.cols;
+        Vec3f64::new([c[0][1], c[1][1], c[2][1]])
+    }
+
+    /// Creates a new transformation matrix that scales, then rotates, then translates
+    /// (translation * rotation * scale). Rotation has to be a rotation matrix.
+    pub const fn from_trs(translation: Vec3f64, rotation: Mat3f64, scale: Vec3f64) -> Self {
+        let t = &translation.coords;
+        let r = &rotation.rows;
+        let s = &scale.coords;
+        Self::new([
+            [r[0][0] * s[0], r[1][0] * s[0], r[2][0] * s[0], 0.0],
+            [r[0][1] * s[1], r[1][1] * s[1], r[2][1] * s[1], 0.0],
+            [r[0][2] * s[2], r[1][2] * s[2], r[2][2] * s[2], 0.0],
+            [t[0], t[1], t[2], 1.0],
+        ])
+    }
+
+    /// Transforms a point (w = 1) by self and returns the x, y and z coordinates.
+    /// The resulting w coordinate is ignored, which is correct for affine
+    /// transformations. Use project_point3() for projective transformations.
+    pub const fn transform_point3(&self, point: Vec3f64) -> Vec3f64 {
+        let p = &point.coords;
+        let v = self.product_vec(Vec4f64::new([p[0], p[1], p[2], 1.0]));
+        Vec3f64::new([v.coords[0], v.coords[1], v.coords[2]])
+    }
+
+    /// Transforms a direction (w = 0) by self and returns the x, y and z coordinates.
+    /// Directions are not affected by the translation of self.
+    pub const fn transform_vector3(&self, vector: Vec3f64) -> Vec3f64 {
+        let d = &vector.coords;
+        let v = self.product_vec(Vec4f64::new([d[0], d[1], d[2], 0.0]));
+        Vec3f64::new([v.coords[0], v.coords[1], v.coords[2]])
+    }
+
+    /// Transforms a point (w = 1) by self and divides the result by its w coordinate
+    /// (perspective divide). Use this with projection matrices.
+    pub const fn project_point3(&self, point: Vec3f64) -> Vec3f64 {
+        let p = &point.coords;
+        let v = self.product_vec(Vec4f64::new([p[0], p[1], p[2], 1.0]));
+        Vec3f64::new([v.coords[0], v.coords[1], v.coords[2]]).divided(v.coords[3])
+    }
+
+    /// Transforms a plane by self, using the inverse transpose of self,
+    /// see Mat4f64::transform_plane().
+    pub const fn transform_plane(&self, plane: Vec4f64) -> Vec4f64 {
+        // (self^-1)^T = (self^T)^-1
+        self.as_transposed_mat4().inverse().product_vec(plane)
+    }
+
+    /// Returns self converted to single precision.
+    pub const fn to_f32(&self) -> ColMat4f32 {
+        ColMat4f32::new(self.as_transposed_mat4().to_f32().rows)
+    }
+
+    /// Creates a new matrix with m in the upper left corner
+    /// and the remaining elements taken from the identity matrix.
+    pub const fn from_mat3(m: Mat3f64) -> Self {
+        let m = &m.rows;
+        Self::new([
+            [m[0][0], m[1][0], m[2][0], 0.0],
+            [m[0][1], m[1][1], m[2][1], 0.0],
+            [m[0][2], m[1][2], m[2][2], 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Returns the upper left 3x3 block of self.
+    pub const fn upper_left(&self) -> Mat3f64 {
+        self.upper_left_transposed().transposed()
+    }
+
+    /// Returns the transpose of the upper left 3x3 block of self,
+    /// which has the first three columns of self as rows.
+    const fn upper_left_transposed(&self) -> Mat3f64 {
+        let c = &self.cols;
+        Mat3f64::new([
+            [c[0][0], c[0][1], c[0][2]],
+            [c[1][0], c[1][1], c[1][2]],
+            [c[2][0], c[2][1], c[2][2]],
+        ])
+    }
+
+    /// Decomposes an affine transformation into translation, rotation, scale and shear,
+    /// see Mat4f64::decompose().
+    pub fn decompose(&self) -> Mat4f64Decomposition {
+        self.to_mat4().decompose()
+    }
+
+    /// Computes the LU decomposition of self with partial pivoting, see Mat4f64::lu().
+    /// The factors are row major Mat4f64 matrices.
+    pub fn lu(&self, tolerance: f64) -> Option<Mat4f64Lu> {
+        self.to_mat4().lu(tolerance)
+    }
+
+    /// Solves the linear system self * x = b for x using an LU decomposition.
+    /// Returns None if self is singular (see lu()).
+    pub fn solve(&self, b: Vec4f64, tolerance: f64) -> Option<Vec4f64> {
+        self.to_mat4().solve(b, tolerance)
+    }
+
+    /// Computes the QR decomposition of self and returns (Q, R), see Mat4f64::qr().
+    pub fn qr(&self) -> (Self, Self) {
+        let (q, r) = self.to_mat4().qr();
+        (Self::from_mat4(q), Self::from_mat4(r))
+    }
+
+    /// Returns self but with orthonormal columns, see Mat4f64::orthonormalized().
+    pub fn orthonormalized(&self) -> Self {
+        Self::from_mat4(self.to_mat4().orthonormalized())
+    }
+
+    /// Makes the columns of self orthonormal,
+    /// see orthonormalized().
+    pub fn orthonormalize(&mut self) {
+        *self = self.orthonormalized()
+    }
+
+    /// Computes the Cholesky decomposition of self, which has to be symmetric,
+    /// see Mat4f64::cholesky(). The factor is a row major Mat4f64 matrix.
+    pub fn cholesky(&self, tolerance: f64) -> Option<Mat4f64Cholesky> {
+        self.as_transposed_mat4().cholesky(tolerance)
+    }
+
+    /// Computes the LDL^T decomposition of self, which has to be symmetric,
+    /// see Mat4f64::ldlt(). The factor L is a row major Mat4f64 matrix.
+    pub fn ldlt(&self, tolerance: f64) -> Option<Mat4f64Ldlt> {
+        self.as_transposed_mat4().ldlt(tolerance)
+    }
+
+    /// Returns the inverse of self, which has to be symmetric,
+    /// see Mat4f64::symmetric_inverse().
+    pub fn symmetric_inverse(&self, tolerance: f64) -> Option<Self> {
+        self.as_transposed_mat4()
+            .symmetric_inverse(tolerance)
+            .map(|inverse| Self::new(inverse.rows))
+    }
+
+    /// Returns self raised to the integer power n, see Mat4f64::powi().
+    pub fn powi(&self, n: i32) -> Self {
+        Self::new(self.as_transposed_mat4().powi(n).rows)
+    }
+
+    /// Returns self raised to the real power t, see Mat4f64::powf().
+    pub fn powf(&self, t: f64) -> Option<Self> {
+        self.as_transposed_mat4()
+            .powf(t)
+            .map(|power| Self::new(power.rows))
+    }
+
+    /// Returns the matrix exponential of self, see Mat4f64::exp().
+    pub fn exp(&self) -> Self {
+        Self::new(self.as_transposed_mat4().exp().rows)
+    }
+
+    /// Returns the principal logarithm of self, see Mat4f64::log().
+    pub fn log(&self) -> Option<Self> {
+        self.as_transposed_mat4()
+            .log()
+            .map(|log| Self::new(log.rows))
+    }
+
+    /// Returns the principal square root of self, see Mat4f64::sqrt().
+    pub fn sqrt(&self) -> Option<Self> {
+        self.as_transposed_mat4()
+            .sqrt()
+            .map(|root| Self::new(root.rows))
+    }
+
+    /// Returns the rank of self, see Mat4f64::rank().
+    pub fn rank(&self, tolerance: f64) -> usize {
+        self.as_transposed_mat4().rank(tolerance)
+    }
+
+    /// Returns an orthonormal basis of the null space of self, see Mat4f64::null_space().
+    pub fn null_space(&self, tolerance: f64) -> Vec<Vec4f64> {
+        self.to_mat4().null_space(tolerance)
+    }
+
+    /// Returns the condition number of self in the Frobenius norm,
+    /// see Mat4f64::condition_number().
+    pub fn condition_number(&self) -> f64 {
+        self.as_transposed_mat4().condition_number()
+    }
+
+    /// Returns true if the absolute value of the determinant of self is greater than tolerance,
+    /// meaning try_inverse() with the same tolerance succeeds.
+    pub fn is_invertible(&self, tolerance: f64) -> bool {
+        self.as_transposed_mat4().is_invertible(tolerance)
+    }
+
+    /// Returns true if the columns of self are orthonormal, see Mat4f64::is_orthogonal().
+    pub fn is_orthogonal(&self, tolerance: f64) -> bool {
+        self.as_transposed_mat4().is_orthogonal(tolerance)
+    }
+
+    /// Returns true if self equals its transpose within tolerance in every element.
+    pub fn is_symmetric(&self, tolerance: f64) -> bool {
+        self.as_transposed_mat4().is_symmetric(tolerance)
+    }
+
+    /// Returns the inverse of self, which has to be an affine transformation,
+    /// see Mat4f64::inverse_affine().
+    pub fn inverse_affine(&self) -> Self {
+        let c = &self.cols;
+        debug_assert!(
+            c[0][3] == 0.0 && c[1][3] == 0.0 && c[2][3] == 0.0 && c[3][3] == 1.0,
+            "inverse_affine() requires a last row of 0, 0, 0, 1"
+        );
+        let linear = self.upper_left().inverse();
+        let t = linear * Vec3f64::new([c[3][0], c[3][1], c[3][2]]);
+        let mut inverse = Self::from_mat3(linear);
+        inverse.set_col(3, Vec4f64::new([-t[0], -t[1], -t[2], 1.0]));
+        inverse
+    }
+
+    /// Returns the inverse of self, which has to be a rigid transformation,
+    /// see Mat4f64::inverse_rigid().
+    pub fn inverse_rigid(&self) -> Self {
+        let c = &self.cols;
+        debug_assert!(
+            c[0][3] == 0.0 && c[1][3] == 0.0 && c[2][3] == 0.0 && c[3][3] == 1.0,
+            "inverse_rigid() requires a last row of 0, 0, 0, 1"
+        );
+        let rotation = self.upper_left_transposed();
+        debug_assert!(
+            rotation.is_orthogonal(1e-10),
+            "inverse_rigid() requires an orthonormal upper left 3x3 block"
+        );
+        let t = rotation * Vec3f64::new([c[3][0], c[3][1], c[3][2]]);
+        let mut inverse = Self::from_mat3(rotation);
+        inverse.set_col(3, Vec4f64::new([-t[0], -t[1], -t[2], 1.0]));
+        inverse
+    }
+
+    /// Returns the matrix that transforms surface normals for the transformation self,
+    /// see Mat4f64::normal_matrix().
+    pub const fn normal_matrix(&self) -> Mat3f64 {
+        // (self^-1)^T = (self^T)^-1
+        self.upper_left_transposed().inverse()
+    }
+
+    /// Returns the cofactor matrix of the upper left 3x3 block of self,
+    /// see Mat4f64::normal_matrix_cofactor().
+    pub const fn normal_matrix_cofactor(&self) -> Mat3f64 {
+        self.upper_left_transposed().adjugate()
+    }
+
+    /// Returns the sum of self and rhs.
+    /// Same as the + operator, but usable in const contexts.
+    pub const fn sum(&self, rhs: Self) -> Self {
+        Self::new(self.as_transposed_mat4().sum(rhs.as_transposed_mat4()).rows)
+    }
+
+    /// Returns the difference of self and rhs.
+    /// Same as the - operator, but usable in const contexts.
+    pub const fn difference(&self, rhs: Self) -> Self {
+        let difference = self
+            .as_transposed_mat4()
+            .difference(rhs.as_transposed_mat4());
+        Self::new(difference.rows)
+    }
+
+    /// Returns self with every element multiplied by scalar.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn scaled(&self, scalar: f64) -> Self {
+        Self::new(self.as_transposed_mat4().scaled(scalar).rows)
+    }
+
+    /// Returns self with every element divided by scalar.
+    /// Same as the / operator, but usable in const contexts.
+    pub const fn divided(&self, scalar: f64) -> Self {
+        Self::new(self.as_transposed_mat4().divided(scalar).rows)
+    }
+
+    /// Returns the matrix product of self and rhs.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product(&self, rhs: Self) -> Self {
+        // (self * rhs)^T = rhs^T * self^T
+        let product = rhs.as_transposed_mat4().product(self.as_transposed_mat4());
+        Self::new(product.rows)
+    }
+
+    /// Returns the product of self and the column vector v.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product_vec(&self, v: Vec4f64) -> Vec4f64 {
+        let m = &self.cols;
+        let v = &v.coords;
+        Vec4f64::new([
+            m[0][0] * v[0] + m[1][0] * v[1] + m[2][0] * v[2] + m[3][0] * v[3],
+            m[0][1] * v[0] + m[1][1] * v[1] + m[2][1] * v[2] + m[3][1] * v[3],
+            m[0][2] * v[0] + m[1][2] * v[1] + m[2][2] * v[2] + m[3][2] * v[3],
+            m[0][3] * v[0] + m[1][3] * v[1] + m[2][3] * v[2] + m[3][3] * v[3],
+        ])
+    }
+
+    /// Returns the determinant of self.
+    pub const fn determinant(&self) -> f64 {
+        self.as_transposed_mat4().determinant()
+    }
+
+    /// Returns the adjugate of self (the transpose of the cofactor matrix).
+    pub const fn adjugate(&self) -> Self {
+        Self::new(self.as_transposed_mat4().adjugate().rows)
+    }
+
+    /// Returns the inverse of self.
+    /// If self is not invertible, the result contains non-finite values.
+    /// Use try_inverse() to check for invertibility.
+    pub const fn inverse(&self) -> Self {
+        // The inverse of the transpose is the transpose of the inverse
+        Self::new(self.as_transposed_mat4().inverse().rows)
+    }
+
+    /// Returns the inverse of self, or None if the absolute value
+    /// of the determinant is less than or equal to tolerance.
+    pub const fn try_inverse(&self, tolerance: f64) -> Option<Self> {
+        match self.as_transposed_mat4().try_inverse(tolerance) {
+            Some(inverse) => Some(Self::new(inverse.rows)),
+            None => None,
+        }
+    }
+}
+
+impl Add<ColMat4f64> for ColMat4f64 {
+    type Output = ColMat4f64;
+    fn add(self, rhs: ColMat4f64) -> Self::Output {
+        self.sum(rhs)
+    }
+}
+
+impl AddAssign<ColMat4f64> for ColMat4f64 {
+    fn add_assign(&mut self, rhs: ColMat4f64) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<ColMat4f64> for ColMat4f64 {
+    type Output = ColMat4f64;
+    fn sub(self, rhs: ColMat4f64) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl SubAssign<ColMat4f64> for ColMat4f64 {
+    fn sub_assign(&mut self, rhs: ColMat4f64) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f64> for ColMat4f64 {
+    type Output = ColMat4f64;
+    fn mul(self, scalar: f64) -> Self::Output {
+        self.scaled(scalar)
+    }
+}
+
+impl Mul<ColMat4f64> for f64 {
+    type Output = ColMat4f64;
+    fn mul(self, m: ColMat4f64) -> Self::Output {
+        m * self
+    }
+}
+
+impl MulAssign<f64> for ColMat4f64 {
+    fn mul_assign(&mut self, scalar: f64) {
+        *self = *self * scalar;
+    }
+}
+
+impl Div<f64> for ColMat4f64 {
+    type Output = ColMat4f64;
+    fn div(self, scalar: f64) -> Self::Output {
+        self.divided(scalar)
+    }
+}
+
+impl DivAssign<f64> for ColMat4f64 {
+    fn div_assign(&mut self, scalar: f64) {
+        *self = *self / scalar;
+    }
+}
+
+impl Mul<ColMat4f64> for ColMat4f64 {
+    type Output = ColMat4f64;
+    fn mul(self, rhs: ColMat4f64) -> Self::Output {
+        self.product(rhs)
+    }
+}
+
+impl MulAssign<ColMat4f64> for ColMat4f64 {
+    fn mul_assign(&mut self, rhs: ColMat4f64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Vec4f64> for ColMat4f64 {
+    type Output = Vec4f64;
+    fn mul(self, v: Vec4f64) -> Self::Output {
+        self.product_vec(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mat::colmat4f64::ColMat4f64;
+    use crate::mat::convention::{DepthRange, Handedness, ProjectionConvention};
+    use crate::mat::mat4f64::Mat4f64;
+    use crate::mat::testing::{assert_mat_close, sample_mat4};
+    use crate::vec::vec3f64::Vec3f64;
+    use crate::vec::vec4f64::Vec4f64;
+
+    #[test]
+    fn layout() {
+        let m = sample_mat4();
+        let c = ColMat4f64::from_mat4(m);
+        assert_eq!(c.as_slice(), &m.as_col_major_flat());
+        assert_eq!(c.as_col_major_flat(), m.as_col_major_flat());
+        assert_eq!(c.as_col_major(), m.as_col_major());
+        assert_eq!(c.as_row_major(), m.as_row_major());
+        assert_eq!(
+            std::mem::size_of::<ColMat4f64>(),
+            16 * std::mem::size_of::<f64>()
+        );
+
+        assert_eq!(c.cols[3], [3.0, -2.0, 4.0, 1.0]);
+        assert_eq!(c.col(3).coords, [3.0, -2.0, 4.0, 1.0]);
+        assert_eq!(c.row(3).coords, [0.5, 0.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn conversions() {
+        let m = sample_mat4();
+        assert_eq!(
+            ColMat4f64::from_mat4(m).to_mat4().as_row_major(),
+            m.as_row_major()
+        );
+        assert_mat_close(ColMat4f64::new_from_rows(m.as_row_major()), m, 1e-12);
+        assert_mat_close(ColMat4f64::new(m.as_col_major()), m, 1e-12);
+        assert_mat_close(
+            ColMat4f64::from_col_major_flat(m.as_col_major_flat()),
+            m,
+            1e-12,
+        );
+        assert_mat_close(
+            ColMat4f64::from_rows([m.row(0), m.row(1), m.row(2), m.row(3)]),
+            m,
+            1e-12,
+        );
+        assert_mat_close(
+            ColMat4f64::from_cols([m.col(0), m.col(1), m.col(2), m.col(3)]),
+            m,
+            1e-12,
+        );
+        assert_mat_close(ColMat4f64::from_mat4(m).transposed(), m.transposed(), 1e-12);
+        assert_mat_close(ColMat4f64::identity(), Mat4f64::identity(), 1e-12);
+        assert_mat_close(ColMat4f64::zero(), Mat4f64::zero(), 1e-12);
+
+        let mut c = ColMat4f64::zero();
+        let mut expected = Mat4f64::zero();
+        c.set_row(1, Vec4f64::new([1.0, 2.0, 3.0, 4.0]));
+        expected.set_row(1, Vec4f64::new([1.0, 2.0, 3.0, 4.0]));
+        c.set_col(2, Vec4f64::new([5.0, 6.0, 7.0, 8.0]));
+        expected.set_col(2, Vec4f64::new([5.0, 6.0, 7.0, 8.0]));
+        assert_mat_close(c, expected, 1e-12);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = sample_mat4();
+        let b = sample_mat4().transposed() * 0.5 + Mat4f64::identity();
+        let (ca, cb) = (ColMat4f64::from_mat4(a), ColMat4f64::from_mat4(b));
+        assert_mat_close(ca + cb, a + b, 1e-12);
+        assert_mat_close(ca - cb, a - b, 1e-12);
+        assert_mat_close(ca * 2.0, a * 2.0, 1e-12);
+        assert_mat_close(2.0 * ca, a * 2.0, 1e-12);
+        assert_mat_close(ca / 4.0, a / 4.0, 1e-12);
+        assert_mat_close(ca * cb, a * b, 1e-12);
+        let v = Vec4f64::new([1.0, -2.0, 0.5, 3.0]);
+        assert!((ca * v - a * v).mag() < 1e-12);
+
+        let mut c = ca;
+        c *= cb;
+        c += cb;
+        c -= ca;
+        c *= 3.0;
+        c /= 2.0;
+        assert_mat_close(c, (a * b + b - a) * 1.5, 1e-12);
+
+        assert_eq!(ca.diagonal().coords, a.diagonal().coords);
+        assert_eq!(ca.trace(), a.trace());
+        assert_eq!(ca.frobenius_norm(), a.frobenius_norm());
+    }
+
+    #[test]
+    fn inverse() {
+        let m = sample_mat4();
+        let c = ColMat4f64::from_mat4(m);
+        assert!((c.determinant() - m.determinant()).abs() < 1e-12);
+        assert_mat_close(c.inverse(), m.inverse(), 1e-12);
+        assert_mat_close(c.try_inverse(1e-12).unwrap(), m.inverse(), 1e-12);
+        assert!(ColMat4f64::zero().try_inverse(1e-12).is_none());
+    }
+
+    #[test]
+    fn transformations() {
+        let t = Vec3f64::new([1.0, -2.0, 3.0]);
+        let s = Vec3f64::new([2.0, 0.5, 1.0]);
+        let axis = Vec3f64::new([1.0, 2.0, 2.0]).normed();
+        assert_mat_close(
+            ColMat4f64::from_translation(t),
+            Mat4f64::from_translation(t),
+            1e-12,
+        );
+        assert_mat_close(ColMat4f64::from_scale(s), Mat4f64::from_scale(s), 1e-12);
+        assert_mat_close(
+            ColMat4f64::from_axis_angle(axis, 0.7),
+            Mat4f64::from_axis_angle(axis, 0.7),
+            1e-12,
+        );
+        let rotation = Mat4f64::from_axis_angle(axis, 0.7).upper_left();
+        let trs = Mat4f64::from_trs(t, rotation, s);
+        let c = ColMat4f64::from_trs(t, rotation, s);
+        assert_mat_close(c, trs, 1e-12);
+        // The translation is stored contiguously in the last column
+        assert_eq!(
+            &ColMat4f64::from_translation(t).as_slice()[12..15],
+            &t.coords
+        );
+
+        let p = Vec3f64::new([0.5, 1.0, -1.5]);
+        assert!((c.transform_point3(p) - trs.transform_point3(p)).mag() < 1e-12);
+        assert!((c.transform_vector3(p) - trs.transform_vector3(p)).mag() < 1e-12);
+        let m = sample_mat4();
+        let projective = ColMat4f64::from_mat4(m);
+        assert!((projective.project_point3(p) - m.project_point3(p)).mag() < 1e-12);
+    }
+
+    #[test]
+    fn projections() {
+        let base = ProjectionConvention::new(Handedness::Right, DepthRange::ZeroToOne);
+        for convention in [
+            base,
+            base.with_reversed_z(),
+            ProjectionConvention::new(Handedness::Left, DepthRange::NegativeOneToOne),
+        ] {
+            assert_mat_close(
+                ColMat4f64::perspective(1.0, 1.5, 0.1, 100.0, convention),
+                Mat4f64::perspective(1.0, 1.5, 0.1, 100.0, convention),
+                1e-12,
+            );
+            assert_mat_close(
+                ColMat4f64::perspective(1.0, 1.5, 0.1, f64::INFINITY, convention),
+                Mat4f64::perspective(1.0, 1.5, 0.1, f64::INFINITY, convention),
+                1e-12,
+            );
+            assert_mat_close(
+                ColMat4f64::perspective_off_axis(-0.8, 1.0, -0.9, 0.7, 0.1, 100.0, convention),
+                Mat4f64::perspective_off_axis(-0.8, 1.0, -0.9, 0.7, 0.1, 100.0, convention),
+                1e-12,
+            );
+            assert_mat_close(
+                ColMat4f64::frustum(-1.0, 2.0, -0.5, 1.5, 0.5, 50.0, convention),
+                Mat4f64::frustum(-1.0, 2.0, -0.5, 1.5, 0.5, 50.0, convention),
+                1e-12,
+            );
+            assert_mat_close(
+                ColMat4f64::orthographic(-2.0, 2.0, -1.0, 1.0, 0.1, 10.0, convention),
+                Mat4f64::orthographic(-2.0, 2.0, -1.0, 1.0, 0.1, 10.0, convention),
+                1e-12,
+            );
+
+            let projection = Mat4f64::perspective(1.0, 1.5, 0.1, 100.0, convention);
+            let c = ColMat4f64::from_mat4(projection);
+            assert_mat_close(c.tile(2, 3, 1, 2), projection.tile(2, 3, 1, 2), 1e-12);
+            let plane = Vec4f64::new([0.0, 0.2, -1.0, -0.5]);
+            assert_mat_close(
+                c.with_oblique_near_plane(plane, convention),
+                projection.with_oblique_near_plane(plane, convention),
+                1e-12,
+            );
+        }
+    }
+
+    #[test]
+    fn views() {
+        let eye = Vec3f64::new([1.0, 2.0, 3.0]);
+        let target = Vec3f64::new([-1.0, 0.5, 0.0]);
+        let up = Vec3f64::new([0.0, 1.0, 0.0]);
+        for handedness in [Handedness::Right, Handedness::Left] {
+            let view = Mat4f64::look_at(eye, target, up, handedness);
+            let c = ColMat4f64::look_at(eye, target, up, handedness);
+            assert_mat_close(c, view, 1e-12);
+            assert_mat_close(
+                ColMat4f64::look_to(eye, target - eye, up, handedness),
+                Mat4f64::look_to(eye, target - eye, up, handedness),
+                1e-12,
+            );
+            assert!((c.camera_position() - eye).mag() < 1e-12);
+            assert!((c.camera_forward(handedness) - view.camera_forward(handedness)).mag() < 1e-12);
+            assert!((c.camera_right() - view.camera_right()).mag() < 1e-12);
+            assert!((c.camera_up() - view.camera_up()).mag() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn builders() {
+        let t = Vec3f64::new([1.0, -2.0, 3.0]);
+        let s = Vec3f64::new([2.0, 0.5, 1.0]);
+        let axis = Vec3f64::new([1.0, 2.0, 2.0]).normed();
+        assert_mat_close(
+            ColMat4f64::from_rotation_x(0.7),
+            Mat4f64::from_rotation_x(0.7),
+            1e-12,
+        );
+        assert_mat_close(
+            ColMat4f64::from_rotation_y(0.7),
+            Mat4f64::from_rotation_y(0.7),
+            1e-12,
+        );
+        assert_mat_close(
+            ColMat4f64::from_rotation_z(0.7),
+            Mat4f64::from_rotation_z(0.7),
+            1e-12,
+        );
+        assert_mat_close(
+            ColMat4f64::from_shear(0.5, -1.0, 0.25, 2.0, 0.0, 1.5),
+            Mat4f64::from_shear(0.5, -1.0, 0.25, 2.0, 0.0, 1.5),
+            1e-12,
+        );
+
+        let chained = ColMat4f64::identity()
+            .scale(s)
+            .shear(0.5, 0.0, 0.0, 0.25, 0.0, 0.0)
+            .rotate_x(0.3)
+            .rotate_y(-0.4)
+            .rotate_z(0.5)
+            .rotate(axis, 0.6)
+            .translate(t);
+        let expected = Mat4f64::identity()
+            .scale(s)
+            .shear(0.5, 0.0, 0.0, 0.25, 0.0, 0.0)
+            .rotate_x(0.3)
+            .rotate_y(-0.4)
+            .rotate_z(0.5)
+            .rotate(axis, 0.6)
+            .translate(t);
+        assert_mat_close(chained, expected, 1e-12);
+
+        let plane = Vec4f64::new([0.0, 1.0, 0.5, -2.0]);
+        assert_mat_close(
+            ColMat4f64::from_reflection(plane),
+            Mat4f64::from_reflection(plane),
+            1e-12,
+        );
+        assert_mat_close(
+            ColMat4f64::from_plane_projection(plane),
+            Mat4f64::from_plane_projection(plane),
+            1e-12,
+        );
+        assert_mat_close(
+            ColMat4f64::from_point_shadow(plane, t),
+            Mat4f64::from_point_shadow(plane, t),
+            1e-12,
+        );
+        assert_mat_close(
+            ColMat4f64::from_directional_shadow(plane, -1.0 * axis),
+            Mat4f64::from_directional_shadow(plane, -1.0 * axis),
+            1e-12,
+        );
+        let transformed = ColMat4f64::from_mat4(expected).transform_plane(plane);
+        assert!((transformed - expected.transform_plane(plane)).mag() < 1e-12);
+
+        let v = Vec4f64::new([1.0, -2.0, 0.5, 3.0]);
+        assert_mat_close(
+            ColMat4f64::from_diagonal(v),
+            Mat4f64::from_diagonal(v),
+            1e-12,
+        );
+        assert_mat_close(
+            ColMat4f64::outer_product(v, 2.0 * v),
+            Mat4f64::outer_product(v, 2.0 * v),
+            1e-12,
+        );
+        let m = sample_mat4();
+        assert_eq!(
+            ColMat4f64::from_mat4(m).as_row_major_flat(),
+            m.as_row_major_flat()
+        );
+        assert_mat_close(
+            ColMat4f64::from_row_major_flat(m.as_row_major_flat()),
+            m,
+            1e-12,
+        );
+        let upper_left = m.upper_left();
+        assert_mat_close(
+            ColMat4f64::from_mat3(upper_left),
+            Mat4f64::from_mat3(upper_left),
+            1e-12,
+        );
+        assert_eq!(
+            ColMat4f64::from_mat4(m).upper_left().as_row_major(),
+            upper_left.as_row_major()
+        );
+        assert_eq!(
+            ColMat4f64::from_mat4(m).to_f32().to_mat4().as_row_major(),
+            m.to_f32().as_row_major()
+        );
+    }
+
+    #[test]
+    fn affine_inverses() {
+        let t = Vec3f64::new([1.0, -2.0, 3.0]);
+        let rotation = Mat4f64::from_axis_angle(Vec3f64::new([1.0, 2.0, 2.0]).normed(), 0.7);
+        let rigid = Mat4f64::from_translation(t) * rotation;
+        let affine = rigid * Mat4f64::from_scale(Vec3f64::new([2.0, 0.5, 1.0]));
+        let c = ColMat4f64::from_mat4(affine);
+        assert_mat_close(c.inverse_affine(), affine.inverse_affine(), 1e-12);
+        assert_mat_close(
+            ColMat4f64::from_mat4(rigid).inverse_rigid(),
+            rigid.inverse_rigid(),
+            1e-12,
+        );
+        assert_mat_close(c.normal_matrix(), affine.normal_matrix(), 1e-12);
+        assert_mat_close(
+            c.normal_matrix_cofactor(),
+            affine.normal_matrix_cofactor(),
+            1e-12,
+        );
+
+        let d = c.decompose();
+        let expected = affine.decompose();
+        assert!((d.translation - expected.translation).mag() < 1e-12);
+        assert!((d.scale - expected.scale).mag() < 1e-12);
+        assert!(!d.is_lossy(1e-12));
+        assert_mat_close(c, d.to_mat4(), 1e-12);
+    }
+
+    #[test]
+    fn factorizations() {
+        let m = sample_mat4();
+        let c = ColMat4f64::from_mat4(m);
+        let b = Vec4f64::new([1.0, -2.0, 0.5, 3.0]);
+        assert!((c.solve(b, 1e-12).unwrap() - m.solve(b, 1e-12).unwrap()).mag() < 1e-12);
+        assert!((c.lu(1e-12).unwrap().solve(b) - m.solve(b, 1e-12).unwrap()).mag() < 1e-12);
+        let (q, r) = c.qr();
+        let (expected_q, expected_r) = m.qr();
+        assert_mat_close(q, expected_q, 1e-12);
+        assert_mat_close(r, expected_r, 1e-12);
+        assert_mat_close(c.orthonormalized(), m.orthonormalized(), 1e-12);
+        let mut orthonormal = c;
+        orthonormal.orthonormalize();
+        assert!(orthonormal.is_orthogonal(1e-12));
+        assert!(!c.is_orthogonal(1e-12));
+        assert_mat_close(c.adjugate(), m.adjugate(), 1e-12);
+
+        // Symmetric positive definite
+        let spd = m * m.transposed() + Mat4f64::identity();
+        let c = ColMat4f64::from_mat4(spd);
+        assert!(c.is_symmetric(1e-12));
+        assert!(!ColMat4f64::from_mat4(m).is_symmetric(1e-12));
+        assert!((c.cholesky(1e-12).unwrap().solve(b) - spd.solve(b, 1e-12).unwrap()).mag() < 1e-12);
+        assert!((c.ldlt(1e-12).unwrap().solve(b) - spd.solve(b, 1e-12).unwrap()).mag() < 1e-12);
+        assert_mat_close(c.symmetric_inverse(1e-12).unwrap(), spd.inverse(), 1e-12);
+    }
+
+    #[test]
+    fn queries() {
+        let m = sample_mat4();
+        let c = ColMat4f64::from_mat4(m);
+        assert_eq!(c.rank(1e-12), 4);
+        assert!(c.is_invertible(1e-12));
+        assert!((c.condition_number() - m.condition_number()).abs() < 1e-12);
+        assert!(c.null_space(1e-12).is_empty());
+
+        let mut singular = m;
+        singular.set_col(3, m.col(0) + m.col(1));
+        let c = ColMat4f64::from_mat4(singular);
+        assert_eq!(c.rank(1e-12), 3);
+        assert!(!c.is_invertible(1e-12));
+        let null_space = c.null_space(1e-12);
+        assert_eq!(null_space.len(), 1);
+        assert!((c * null_space[0]).mag() < 1e-12);
+    }
+
+    #[test]
+    fn matrix_functions() {
+        let m = Mat4f64::new([
+            [4.0, 1.0, 0.0, 1.0],
+            [1.0, 3.0, 1.0, 0.0],
+            [0.0, 1.0, 2.0, 1.0],
+            [1.0, 0.0, 1.0, 5.0],
+        ]);
+        let c = ColMat4f64::from_mat4(m);
+        assert_mat_close(c.powi(3), m.powi(3), 1e-12);
+        assert_mat_close(c.powi(-2), m.powi(-2), 1e-12);
+        assert_mat_close(c.powf(0.5).unwrap(), m.powf(0.5).unwrap(), 1e-12);
+        assert_mat_close((c * 0.25).exp(), (m * 0.25).exp(), 1e-12);
+        assert_mat_close(c.log().unwrap(), m.log().unwrap(), 1e-12);
+        assert_mat_close(c.sqrt().unwrap(), m.sqrt().unwrap(), 1e-12);
+        assert!((-1.0 * ColMat4f64::identity()).log().is_none());
+
+        // Not symmetric, so transposing the result matters
+        let m = sample_mat4();
+        let c = ColMat4f64::from_mat4(m);
+        assert_mat_close(c.powi(2), m.powi(2), 1e-12);
+        assert_mat_close((c * 0.25).exp(), (m * 0.25).exp(), 1e-12);
+    }
+}
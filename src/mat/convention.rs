@@ -0,0 +1,52 @@
+/// Handedness of a view space coordinate system.
+/// Right handed view spaces look down the negative z axis (OpenGL style),
+/// left handed view spaces look down the positive z axis (DirectX style).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bitcode", derive(bitcode::Encode, bitcode::Decode))]
+pub enum Handedness {
+    Right,
+    Left,
+}
+
+/// Range of the depth values in normalized device coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bitcode", derive(bitcode::Encode, bitcode::Decode))]
+pub enum DepthRange {
+    /// Depth in [-1, 1], used by OpenGL.
+    NegativeOneToOne,
+    /// Depth in [0, 1], used by Vulkan, DirectX, Metal and WebGPU.
+    ZeroToOne,
+}
+
+/// The conventions a projection matrix is built for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bitcode", derive(bitcode::Encode, bitcode::Decode))]
+pub struct ProjectionConvention {
+    pub handedness: Handedness,
+    pub depth_range: DepthRange,
+    /// If true, the near plane maps to the far end of the depth range and vice versa.
+    /// Combined with a floating point depth buffer this greatly improves depth precision.
+    pub reversed_z: bool,
+}
+
+impl ProjectionConvention {
+    /// Creates a new convention without reversed z.
+    pub const fn new(handedness: Handedness, depth_range: DepthRange) -> Self {
+        Self {
+            handedness,
+            depth_range,
+            reversed_z: false,
+        }
+    }
+
+    /// Returns self but with reversed z enabled.
+    pub const fn with_reversed_z(self) -> Self {
+        Self {
+            reversed_z: true,
+            ..self
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
 
+use crate::mat::mat2f64::Mat2f64;
 use crate::vec::vec2f32::Vec2f32;
 
 /// A 2x2 floating point matrix.
@@ -14,25 +15,25 @@ pub struct Mat2f32 {
 impl Mat2f32 {
     /// Creates a new matrix with user defined elements.
     /// The user defined elements are in row major order.
-    pub fn new(rows: [[f32; 2]; 2]) -> Self {
+    pub const fn new(rows: [[f32; 2]; 2]) -> Self {
         Self { rows }
     }
 
     /// Creates a new matrix with user defined elements.
     /// The user defined elements are in column major order.
-    pub fn new_from_cols(cols: [[f32; 2]; 2]) -> Self {
+    pub const fn new_from_cols(cols: [[f32; 2]; 2]) -> Self {
         Self::new(cols).transposed()
     }
 
     /// Creates a new matrix with all elements equal to 0.0.
-    pub fn zero() -> Self {
+    pub const fn zero() -> Self {
         Self {
             rows: [[0.0, 0.0], [0.0, 0.0]],
         }
     }
 
     /// Creates a new identity matrix.
-    pub fn identity() -> Self {
+    pub const fn identity() -> Self {
         Self {
             rows: [[1.0, 0.0], [0.0, 1.0]],
         }
@@ -40,27 +41,769 @@ impl Mat2f32 {
 
     /// Returns self but transposed
     /// (Rows are now columns and columns are now rows).
-    pub fn transposed(&self) -> Self {
+    pub const fn transposed(&self) -> Self {
+        let m = &self.rows;
         Self {
-            rows: [[self[0][0], self[1][0]], [self[0][1], self[1][1]]],
+            rows: [[m[0][0], m[1][0]], [m[0][1], m[1][1]]],
         }
     }
 
     /// Transposes self
     /// (Rows are now columns and columns are now rows).
-    pub fn transpose(&mut self) {
+    pub const fn transpose(&mut self) {
         *self = self.transposed()
     }
 
     /// Returns the matrix rows as arrays in row major order.
-    pub fn as_row_major(&self) -> [[f32; 2]; 2] {
+    pub const fn as_row_major(&self) -> [[f32; 2]; 2] {
         self.rows
     }
 
     /// Returns the matrix columns as arrays in column major order.
-    pub fn as_col_major(&self) -> [[f32; 2]; 2] {
+    pub const fn as_col_major(&self) -> [[f32; 2]; 2] {
         self.transposed().rows
     }
+
+    /// Creates a new matrix from row vectors.
+    pub const fn from_rows(rows: [Vec2f32; 2]) -> Self {
+        Self::new([rows[0].coords, rows[1].coords])
+    }
+
+    /// Creates a new matrix from column vectors.
+    pub const fn from_cols(cols: [Vec2f32; 2]) -> Self {
+        Self::new_from_cols([cols[0].coords, cols[1].coords])
+    }
+
+    /// Creates a new diagonal matrix with the components of diagonal
+    /// on the main diagonal and all other elements equal to 0.0.
+    pub const fn from_diagonal(diagonal: Vec2f32) -> Self {
+        let d = &diagonal.coords;
+        Self::new([[d[0], 0.0], [0.0, d[1]]])
+    }
+
+    /// Creates the outer product of a and b (a * b^T).
+    /// Element [i][j] of the result is a[i] * b[j].
+    pub const fn outer_product(a: Vec2f32, b: Vec2f32) -> Self {
+        let a = &a.coords;
+        let b = &b.coords;
+        Self::new([[a[0] * b[0], a[0] * b[1]], [a[1] * b[0], a[1] * b[1]]])
+    }
+
+    /// Creates a new matrix reflecting across the line through the origin
+    /// perpendicular to normal, which does not have to be normalized.
+    pub const fn from_reflection(normal: Vec2f32) -> Self {
+        let offset = Self::outer_product(normal, normal).scaled(2.0 / normal.mag_squared());
+        Self::identity().difference(offset)
+    }
+
+    /// Returns the row at index as a vector.
+    pub const fn row(&self, index: usize) -> Vec2f32 {
+        Vec2f32::new(self.rows[index])
+    }
+
+    /// Returns the column at index as a vector.
+    pub const fn col(&self, index: usize) -> Vec2f32 {
+        let m = &self.rows;
+        Vec2f32::new([m[0][index], m[1][index]])
+    }
+
+    /// Replaces the row at index with row.
+    pub const fn set_row(&mut self, index: usize, row: Vec2f32) {
+        self.rows[index] = row.coords;
+    }
+
+    /// Replaces the column at index with col.
+    pub const fn set_col(&mut self, index: usize, col: Vec2f32) {
+        self.rows[0][index] = col.coords[0];
+        self.rows[1][index] = col.coords[1];
+    }
+
+    /// Returns the elements on the main diagonal as a vector.
+    pub const fn diagonal(&self) -> Vec2f32 {
+        let m = &self.rows;
+        Vec2f32::new([m[0][0], m[1][1]])
+    }
+
+    /// Returns the trace of self (the sum of the main diagonal).
+    pub const fn trace(&self) -> f32 {
+        let m = &self.rows;
+        m[0][0] + m[1][1]
+    }
+
+    /// The Frobenius norm of self
+    /// (the square root of the sum of all squared elements).
+    pub fn frobenius_norm(&self) -> f32 {
+        self.frobenius_norm_squared().sqrt()
+    }
+
+    /// The Frobenius norm of self, but squared.
+    /// This is faster to compute than frobenius_norm() and useful in some situations.
+    pub const fn frobenius_norm_squared(&self) -> f32 {
+        let m = &self.rows;
+        m[0][0] * m[0][0] + m[0][1] * m[0][1] + m[1][0] * m[1][0] + m[1][1] * m[1][1]
+    }
+
+    /// Creates a new matrix from a flat array of elements in row major order.
+    pub const fn from_row_major_flat(elements: [f32; 4]) -> Self {
+        let e = &elements;
+        Self::new([[e[0], e[1]], [e[2], e[3]]])
+    }
+
+    /// Creates a new matrix from a flat array of elements in column major order.
+    pub const fn from_col_major_flat(elements: [f32; 4]) -> Self {
+        let e = &elements;
+        Self::new([[e[0], e[2]], [e[1], e[3]]])
+    }
+
+    /// Returns all elements as a flat array in row major order.
+    pub const fn as_row_major_flat(&self) -> [f32; 4] {
+        let m = &self.rows;
+        [m[0][0], m[0][1], m[1][0], m[1][1]]
+    }
+
+    /// Returns all elements as a flat array in column major order.
+    /// This is the layout most graphics APIs expect for uniform uploads.
+    pub const fn as_col_major_flat(&self) -> [f32; 4] {
+        let m = &self.rows;
+        [m[0][0], m[1][0], m[0][1], m[1][1]]
+    }
+
+    /// Returns self converted to double precision.
+    pub const fn to_f64(&self) -> Mat2f64 {
+        let m = &self.rows;
+        Mat2f64::new([
+            [m[0][0] as f64, m[0][1] as f64],
+            [m[1][0] as f64, m[1][1] as f64],
+        ])
+    }
+
+    /// Computes the LU decomposition of self with partial pivoting,
+    /// so that P * self = L * U (see Mat2f32Lu).
+    /// Returns None if self is singular, meaning a pivot has an absolute value
+    /// less than or equal to tolerance.
+    pub fn lu(&self, tolerance: f32) -> Option<Mat2f32Lu> {
+        let mut lu = self.rows;
+        let mut permutation = [0, 1];
+        let mut sign = 1.0;
+        for k in 0..2 {
+            // Use the row with the largest absolute value in column k as pivot
+            let mut pivot = k;
+            for i in k + 1..2 {
+                if lu[i][k].abs() > lu[pivot][k].abs() {
+                    pivot = i;
+                }
+            }
+            if lu[pivot][k].abs() <= tolerance {
+                return None;
+            }
+            if pivot != k {
+                lu.swap(k, pivot);
+                permutation.swap(k, pivot);
+                sign = -sign;
+            }
+            let pivot_row = lu[k];
+            for row in lu.iter_mut().skip(k + 1) {
+                let factor = row[k] / pivot_row[k];
+                row[k] = factor;
+                for j in k + 1..2 {
+                    row[j] -= factor * pivot_row[j];
+                }
+            }
+        }
+        Some(Mat2f32Lu {
+            lu,
+            permutation,
+            sign,
+        })
+    }
+
+    /// Solves the linear system self * x = b for x using an LU decomposition.
+    /// Returns None if self is singular (see lu()).
+    pub fn solve(&self, b: Vec2f32, tolerance: f32) -> Option<Vec2f32> {
+        Some(self.lu(tolerance)?.solve(b))
+    }
+
+    /// Computes the QR decomposition of self using Householder reflections
+    /// and returns (Q, R), where Q is orthogonal, R is upper triangular with
+    /// a non negative diagonal and self = Q * R.
+    pub fn qr(&self) -> (Self, Self) {
+        let mut q = Self::identity();
+        let mut r = *self;
+        for k in 0..1 {
+            // Householder vector reflecting column k below the diagonal onto the k axis
+            let mut v = [0.0; 2];
+            let mut norm = 0.0;
+            for i in k..2 {
+                v[i] = r[i][k];
+                norm += r[i][k] * r[i][k];
+            }
+            let norm = norm.sqrt();
+            v[k] += if v[k] < 0.0 { -norm } else { norm };
+            let v_mag_squared = Vec2f32::new(v).mag_squared();
+            if v_mag_squared == 0.0 {
+                continue;
+            }
+            // r = h * r and q = q * h with h = I - 2 * v * v^T / (v^T * v)
+            for j in 0..2 {
+                let dot = Vec2f32::new(v).dot(r.col(j));
+                let f = 2.0 * dot / v_mag_squared;
+                for i in k..2 {
+                    r[i][j] -= f * v[i];
+                }
+            }
+            for i in 0..2 {
+                let dot = Vec2f32::new(v).dot(q.row(i));
+                let f = 2.0 * dot / v_mag_squared;
+                for j in k..2 {
+                    q[i][j] -= f * v[j];
+                }
+            }
+        }
+        // Make the decomposition unique by flipping signs to get a non negative diagonal
+        for k in 0..2 {
+            if r[k][k] < 0.0 {
+                r.set_row(k, -1.0 * r.row(k));
+                q.set_col(k, -1.0 * q.col(k));
+            }
+        }
+        for i in 1..2 {
+            for j in 0..i {
+                r[i][j] = 0.0;
+            }
+        }
+        (q, r)
+    }
+
+    /// Returns self but with orthonormal columns,
+    /// computed with the Gram-Schmidt process starting at the first column.
+    /// Use this to remove the drift of rotation matrices accumulated over many
+    /// multiplications. The columns of self have to be linearly independent.
+    pub fn orthonormalized(&self) -> Self {
+        let mut cols = [self.col(0), self.col(1)];
+        Vec2f32::gram_schmidt(&mut cols);
+        Self::from_cols(cols)
+    }
+
+    /// Makes the columns of self orthonormal,
+    /// see orthonormalized().
+    pub fn orthonormalize(&mut self) {
+        *self = self.orthonormalized()
+    }
+
+    /// Computes the eigenvalues and eigenvectors of self, which has to be symmetric.
+    /// Returns the eigenvalues sorted in descending order and a rotation matrix
+    /// (orthonormal with a determinant of +1) whose columns are the corresponding
+    /// eigenvectors, so that self = eigenvectors * diag(eigenvalues) * eigenvectors^T.
+    pub fn symmetric_eigen(&self) -> (Vec2f32, Self) {
+        let m = &self.rows;
+        // A single Jacobi rotation diagonalizes a symmetric 2x2 matrix
+        let angle = 0.5 * (2.0 * m[0][1]).atan2(m[0][0] - m[1][1]);
+        let (sin, cos) = angle.sin_cos();
+        let rotation = Self::new([[cos, -sin], [sin, cos]]);
+        let diagonal = rotation.transposed() * *self * rotation;
+        if diagonal[0][0] >= diagonal[1][1] {
+            (diagonal.diagonal(), rotation)
+        } else {
+            // Swapping the columns and negating one keeps the determinant at +1
+            let eigenvectors = Self::from_cols([rotation.col(1), -1.0 * rotation.col(0)]);
+            (Vec2f32::new([diagonal[1][1], diagonal[0][0]]), eigenvectors)
+        }
+    }
+
+    /// Computes the singular value decomposition of self and returns (U, sigma, V),
+    /// so that self = U * diag(sigma) * V^T.
+    /// U and V are rotation matrices (orthonormal with a determinant of +1).
+    /// The singular values are sorted by descending absolute value and all but the
+    /// last are non negative. The last one is negative if self has a negative
+    /// determinant (it contains a reflection).
+    /// Uses one-sided Jacobi rotations on the columns of self, which keeps small singular
+    /// values accurate, unlike going through the eigenvectors of self^T * self.
+    pub fn svd(&self) -> (Self, Vec2f32, Self) {
+        // Rotate the columns of a = self * V until they are orthogonal,
+        // then their lengths are the singular values and their directions U
+        let mut a = *self;
+        let mut v = Self::identity();
+        for _ in 0..32 {
+            let mut converged = true;
+            for (p, q) in [(0, 1)] {
+                let (ap, aq) = (a.col(p), a.col(q));
+                let (alpha, beta, gamma) = (ap.mag_squared(), aq.mag_squared(), ap.dot(aq));
+                if gamma.abs() <= f32::EPSILON * (alpha * beta).sqrt() {
+                    continue;
+                }
+                converged = false;
+                // Jacobi rotation J that makes the columns p and q orthogonal,
+                // the one that zeroes the element p, q of a^T * a
+                let theta = (beta - alpha) / (2.0 * gamma);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let cos = 1.0 / (t * t + 1.0).sqrt();
+                let sin = t * cos;
+                for k in 0..2 {
+                    let (akp, akq) = (a[k][p], a[k][q]);
+                    a[k][p] = cos * akp - sin * akq;
+                    a[k][q] = sin * akp + cos * akq;
+                    let (vkp, vkq) = (v[k][p], v[k][q]);
+                    v[k][p] = cos * vkp - sin * vkq;
+                    v[k][q] = sin * vkp + cos * vkq;
+                }
+            }
+            if converged {
+                break;
+            }
+        }
+
+        // Sort by descending singular value
+        let mut pairs = [
+            (a.col(0).mag(), a.col(0), v.col(0)),
+            (a.col(1).mag(), a.col(1), v.col(1)),
+        ];
+        pairs.sort_by(|x, y| y.0.total_cmp(&x.0));
+        let mut sigma = Vec2f32::new([pairs[0].0, pairs[1].0]);
+        let mut v = Self::from_cols([pairs[0].2, pairs[1].2]);
+
+        // Columns of (nearly) zero length have no direction, complete U with the
+        // coordinate axis that is the least parallel to the previous columns
+        let threshold = f32::EPSILON * sigma[0];
+        let mut u = [Vec2f32::zero(); 2];
+        for k in 0..2 {
+            if sigma[k] > threshold {
+                u[k] = pairs[k].1 / sigma[k];
+                continue;
+            }
+            let mut best = Vec2f32::zero();
+            for axis in [[1.0, 0.0], [0.0, 1.0]] {
+                let mut w = Vec2f32::new(axis);
+                for previous in &u[..k] {
+                    w -= previous.dot(w) * *previous;
+                }
+                if w.mag_squared() > best.mag_squared() {
+                    best = w;
+                }
+            }
+            u[k] = best.normed();
+        }
+        let mut u = Self::from_cols(u);
+
+        // Negating the last column of both U and V keeps the product
+        if v.determinant() < 0.0 {
+            v.set_col(1, -1.0 * v.col(1));
+            u.set_col(1, -1.0 * u.col(1));
+        }
+        // A reflection in self is left in U, move it into the last singular value
+        if u.determinant() < 0.0 {
+            u.set_col(1, -1.0 * u.col(1));
+            sigma[1] = -sigma[1];
+        }
+        (u, sigma, v)
+    }
+
+    /// Returns the Moore-Penrose pseudo inverse of self, computed with svd().
+    /// Singular values with an absolute value less than or equal to tolerance
+    /// are treated as zero. For invertible matrices this equals the inverse.
+    pub fn pseudo_inverse(&self, tolerance: f32) -> Self {
+        let (u, sigma, v) = self.svd();
+        let mut inverted = Vec2f32::zero();
+        for i in 0..2 {
+            if sigma[i].abs() > tolerance {
+                inverted[i] = 1.0 / sigma[i];
+            }
+        }
+        v * Self::from_diagonal(inverted) * u.transposed()
+    }
+
+    /// Computes the Cholesky decomposition of self, which has to be symmetric,
+    /// so that self = L * L^T (see Mat2f32Cholesky).
+    /// Returns None if self is not positive definite, meaning a diagonal element of the
+    /// decomposition would be the square root of a value less than or equal to tolerance.
+    pub fn cholesky(&self, tolerance: f32) -> Option<Mat2f32Cholesky> {
+        let mut l = Self::zero();
+        for j in 0..2 {
+            // Row j of l only contains the columns before j at this point
+            let d = self[j][j] - l.row(j).mag_squared();
+            if d <= tolerance {
+                return None;
+            }
+            l[j][j] = d.sqrt();
+            for i in j + 1..2 {
+                l[i][j] = (self[i][j] - l.row(i).dot(l.row(j))) / l[j][j];
+            }
+        }
+        Some(Mat2f32Cholesky { l })
+    }
+
+    /// Computes the LDL^T decomposition of self, which has to be symmetric,
+    /// so that self = L * D * L^T (see Mat2f32Ldlt).
+    /// Unlike cholesky() this also works for many indefinite matrices and needs no
+    /// square roots. Returns None if an element of D has an absolute value less than
+    /// or equal to tolerance.
+    pub fn ldlt(&self, tolerance: f32) -> Option<Mat2f32Ldlt> {
+        let mut l = Self::identity();
+        let mut d = Vec2f32::zero();
+        for j in 0..2 {
+            let mut dj = self[j][j];
+            for k in 0..j {
+                dj -= l[j][k] * l[j][k] * d[k];
+            }
+            if dj.abs() <= tolerance {
+                return None;
+            }
+            d[j] = dj;
+            for i in j + 1..2 {
+                let mut sum = self[i][j];
+                for k in 0..j {
+                    sum -= l[i][k] * l[j][k] * d[k];
+                }
+                l[i][j] = sum / dj;
+            }
+        }
+        Some(Mat2f32Ldlt { l, d })
+    }
+
+    /// Returns the inverse of self, which has to be symmetric,
+    /// computed with an LDL^T decomposition.
+    /// Returns None if the decomposition fails (see ldlt()).
+    pub fn symmetric_inverse(&self, tolerance: f32) -> Option<Self> {
+        Some(self.ldlt(tolerance)?.inverse())
+    }
+
+    /// Returns self raised to the integer power n, computed by repeated squaring.
+    /// Negative powers use the inverse of self, so the result is not finite
+    /// if self is singular.
+    pub fn powi(&self, n: i32) -> Self {
+        let mut base = if n < 0 { self.inverse() } else { *self };
+        let mut exponent = n.unsigned_abs();
+        let mut result = Self::identity();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// Returns self raised to the real power t, computed as exp(t * log(self)).
+    /// Returns None if self has no principal logarithm (see log()).
+    pub fn powf(&self, t: f32) -> Option<Self> {
+        Some((self.log()? * t).exp())
+    }
+
+    /// Returns the matrix exponential of self,
+    /// computed by scaling and squaring with a Taylor series.
+    pub fn exp(&self) -> Self {
+        // exp(A) = exp(A / 2^s)^(2^s), choose s so that the norm of A / 2^s is at most 0.5
+        let norm = self.frobenius_norm();
+        let squarings = if norm > 0.5 {
+            (norm.log2().ceil() as i32).min(f32::MAX_EXP) + 1
+        } else {
+            0
+        };
+        let scaled = *self * 0.5f32.powi(squarings);
+
+        let mut result = Self::identity();
+        let mut term = Self::identity();
+        for k in 1..32 {
+            term = term * scaled / k as f32;
+            result += term;
+            if term.frobenius_norm() <= f32::EPSILON * result.frobenius_norm() {
+                break;
+            }
+        }
+        for _ in 0..squarings {
+            result *= result;
+        }
+        result
+    }
+
+    /// Returns the principal logarithm of self, computed by inverse scaling and squaring.
+    /// Returns None if self has no principal logarithm, which is the case
+    /// if it has eigenvalues on the closed negative real axis.
+    pub fn log(&self) -> Option<Self> {
+        // log(A) = 2^k * log(A^(1 / 2^k)), take square roots until A is close to the identity
+        let mut a = *self;
+        let mut square_roots = 0;
+        while (a - Self::identity()).frobenius_norm() > 0.25 {
+            if square_roots == 64 {
+                return None;
+            }
+            a = a.sqrt()?;
+            square_roots += 1;
+        }
+
+        // log(I + X) = X - X^2 / 2 + X^3 / 3 - ...
+        let x = a - Self::identity();
+        let mut result = Self::zero();
+        let mut power = Self::identity();
+        for k in 1..64 {
+            power *= x;
+            let term = power / k as f32;
+            if k % 2 == 1 {
+                result += term;
+            } else {
+                result -= term;
+            }
+            if term.frobenius_norm() <= f32::EPSILON * result.frobenius_norm() {
+                break;
+            }
+        }
+        Some(result * 2.0f32.powi(square_roots))
+    }
+
+    /// Returns the principal square root of self. Symmetric matrices are handled
+    /// with symmetric_eigen(), other matrices with the Denman-Beavers iteration.
+    /// Returns None if self has negative real eigenvalues, or if it is singular and
+    /// not symmetric. Singular positive semidefinite matrices like diag(4, 0) are supported.
+    pub fn sqrt(&self) -> Option<Self> {
+        let tolerance = 8.0 * f32::EPSILON * self.frobenius_norm();
+        if self.is_symmetric(tolerance) {
+            let (values, vectors) = self.symmetric_eigen();
+            let mut roots = Vec2f32::zero();
+            for i in 0..2 {
+                if values[i] < -tolerance {
+                    return None;
+                }
+                // Eigenvalues within rounding error of zero are treated as zero
+                if values[i] > tolerance {
+                    roots[i] = values[i].sqrt();
+                }
+            }
+            return Some(vectors * Self::from_diagonal(roots) * vectors.transposed());
+        }
+
+        // y converges to sqrt(A) and z to its inverse
+        let mut y = *self;
+        let mut z = Self::identity();
+        for _ in 0..64 {
+            let y_inverse = y.try_inverse(0.0)?;
+            let z_inverse = z.try_inverse(0.0)?;
+            let next = (y + z_inverse) * 0.5;
+            z = (z + y_inverse) * 0.5;
+            let step = (next - y).frobenius_norm();
+            y = next;
+            if step <= 8.0 * f32::EPSILON * y.frobenius_norm() {
+                return Some(y);
+            }
+        }
+        None
+    }
+
+    /// Reduces self to reduced row echelon form with Gaussian elimination and partial pivoting.
+    /// Returns the reduced rows and which columns contain a pivot. Pivots with an absolute
+    /// value less than or equal to tolerance are treated as zero.
+    fn row_echelon(&self, tolerance: f32) -> ([[f32; 2]; 2], [bool; 2]) {
+        let mut a = self.rows;
+        let mut pivots = [false; 2];
+        let mut rank = 0;
+        for col in 0..2 {
+            if rank == 2 {
+                break;
+            }
+            let mut pivot = rank;
+            for (row, values) in a.iter().enumerate().skip(rank + 1) {
+                if values[col].abs() > a[pivot][col].abs() {
+                    pivot = row;
+                }
+            }
+            if a[pivot][col].abs() <= tolerance {
+                continue;
+            }
+            a.swap(rank, pivot);
+            let pivot_value = a[rank][col];
+            for x in a[rank].iter_mut() {
+                *x /= pivot_value;
+            }
+            let pivot_row = a[rank];
+            for (row, values) in a.iter_mut().enumerate() {
+                if row != rank {
+                    let factor = values[col];
+                    for (x, p) in values.iter_mut().zip(pivot_row) {
+                        *x -= factor * p;
+                    }
+                }
+            }
+            pivots[col] = true;
+            rank += 1;
+        }
+        (a, pivots)
+    }
+
+    /// Returns the rank of self, computed with Gaussian elimination and partial pivoting.
+    /// Pivots with an absolute value less than or equal to tolerance are treated as zero.
+    pub fn rank(&self, tolerance: f32) -> usize {
+        let (_, pivots) = self.row_echelon(tolerance);
+        pivots.iter().filter(|&&pivot| pivot).count()
+    }
+
+    /// Returns an orthonormal basis of the null space of self, the vectors v with self * v = 0.
+    /// The basis is empty if self has full column rank.
+    /// Pivots with an absolute value less than or equal to tolerance are treated as zero (see rank()).
+    pub fn null_space(&self, tolerance: f32) -> Vec<Vec2f32> {
+        let (a, pivots) = self.row_echelon(tolerance);
+        let mut basis = Vec::new();
+        for free in 0..2 {
+            if pivots[free] {
+                continue;
+            }
+            let mut v = Vec2f32::zero();
+            v[free] = 1.0;
+            // The reduced rows have their pivots in the pivot columns in order
+            let mut row = 0;
+            for col in 0..2 {
+                if pivots[col] {
+                    v[col] = -a[row][free];
+                    row += 1;
+                }
+            }
+            basis.push(v);
+        }
+        Vec2f32::gram_schmidt(&mut basis);
+        basis
+    }
+
+    /// Returns the condition number of self in the Frobenius norm,
+    /// ||self|| * ||self^-1||, which is at least 2 and grows as self
+    /// gets closer to singular. Returns infinity if self is singular.
+    pub fn condition_number(&self) -> f32 {
+        match self.try_inverse(0.0) {
+            Some(inverse) => self.frobenius_norm() * inverse.frobenius_norm(),
+            None => f32::INFINITY,
+        }
+    }
+
+    /// Returns true if the absolute value of the determinant of self is greater than tolerance,
+    /// meaning try_inverse() with the same tolerance succeeds.
+    pub fn is_invertible(&self, tolerance: f32) -> bool {
+        self.determinant().abs() > tolerance
+    }
+
+    /// Returns true if the columns of self are orthonormal, meaning self^T * self
+    /// differs from the identity by at most tolerance in every element.
+    pub fn is_orthogonal(&self, tolerance: f32) -> bool {
+        let gram = self.transposed() * *self;
+        let identity = Self::identity();
+        for i in 0..2 {
+            for j in 0..2 {
+                if (gram[i][j] - identity[i][j]).abs() > tolerance {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns true if self equals its transpose within tolerance in every element.
+    pub fn is_symmetric(&self, tolerance: f32) -> bool {
+        for i in 0..2 {
+            for j in i + 1..2 {
+                if (self[i][j] - self[j][i]).abs() > tolerance {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the sum of self and rhs.
+    /// Same as the + operator, but usable in const contexts.
+    pub const fn sum(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [a[0][0] + b[0][0], a[0][1] + b[0][1]],
+            [a[1][0] + b[1][0], a[1][1] + b[1][1]],
+        ])
+    }
+
+    /// Returns the difference of self and rhs.
+    /// Same as the - operator, but usable in const contexts.
+    pub const fn difference(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [a[0][0] - b[0][0], a[0][1] - b[0][1]],
+            [a[1][0] - b[1][0], a[1][1] - b[1][1]],
+        ])
+    }
+
+    /// Returns self with every element multiplied by scalar.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn scaled(&self, scalar: f32) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [a[0][0] * scalar, a[0][1] * scalar],
+            [a[1][0] * scalar, a[1][1] * scalar],
+        ])
+    }
+
+    /// Returns self with every element divided by scalar.
+    /// Same as the / operator, but usable in const contexts.
+    pub const fn divided(&self, scalar: f32) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [a[0][0] / scalar, a[0][1] / scalar],
+            [a[1][0] / scalar, a[1][1] / scalar],
+        ])
+    }
+
+    /// Returns the matrix product of self and rhs.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1],
+            ],
+        ])
+    }
+
+    /// Returns the product of self and the column vector v.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product_vec(&self, v: Vec2f32) -> Vec2f32 {
+        let a = &self.rows;
+        let b = &v.coords;
+        Vec2f32::new([
+            a[0][0] * b[0] + a[0][1] * b[1],
+            a[1][0] * b[0] + a[1][1] * b[1],
+        ])
+    }
+
+    /// Returns the determinant of self.
+    pub const fn determinant(&self) -> f32 {
+        let a = &self.rows;
+        a[0][0] * a[1][1] - a[0][1] * a[1][0]
+    }
+
+    /// Returns the adjugate of self (the transpose of the cofactor matrix).
+    /// Unlike the inverse, the adjugate exists for singular matrices too.
+    pub const fn adjugate(&self) -> Self {
+        let a = &self.rows;
+        Self::new([[a[1][1], -a[0][1]], [-a[1][0], a[0][0]]])
+    }
+
+    /// Returns the inverse of self.
+    /// Singular matrices produce non finite elements,
+    /// use try_inverse() if self might not be invertible.
+    pub const fn inverse(&self) -> Self {
+        self.adjugate().divided(self.determinant())
+    }
+
+    /// Returns the inverse of self, or None if self is singular.
+    /// Self counts as singular if the absolute value of its determinant
+    /// is less than or equal to tolerance.
+    pub const fn try_inverse(&self, tolerance: f32) -> Option<Self> {
+        let det = self.determinant();
+        if det.abs() <= tolerance {
+            return None;
+        }
+        Some(self.adjugate().divided(det))
+    }
 }
 
 impl Index<usize> for Mat2f32 {
@@ -78,12 +821,8 @@ impl IndexMut<usize> for Mat2f32 {
 
 impl Add<Mat2f32> for Mat2f32 {
     type Output = Mat2f32;
-    fn add(mut self, rhs: Mat2f32) -> Self::Output {
-        self[0][0] += rhs[0][0];
-        self[0][1] += rhs[0][1];
-        self[1][0] += rhs[1][0];
-        self[1][1] += rhs[1][1];
-        self
+    fn add(self, rhs: Mat2f32) -> Self::Output {
+        self.sum(rhs)
     }
 }
 
@@ -95,12 +834,8 @@ impl AddAssign<Mat2f32> for Mat2f32 {
 
 impl Sub<Mat2f32> for Mat2f32 {
     type Output = Mat2f32;
-    fn sub(mut self, rhs: Mat2f32) -> Self::Output {
-        self[0][0] -= rhs[0][0];
-        self[0][1] -= rhs[0][1];
-        self[1][0] -= rhs[1][0];
-        self[1][1] -= rhs[1][1];
-        self
+    fn sub(self, rhs: Mat2f32) -> Self::Output {
+        self.difference(rhs)
     }
 }
 
@@ -112,12 +847,8 @@ impl SubAssign<Mat2f32> for Mat2f32 {
 
 impl Mul<f32> for Mat2f32 {
     type Output = Mat2f32;
-    fn mul(mut self, scalar: f32) -> Self::Output {
-        self[0][0] *= scalar;
-        self[0][1] *= scalar;
-        self[1][0] *= scalar;
-        self[1][1] *= scalar;
-        self
+    fn mul(self, scalar: f32) -> Self::Output {
+        self.scaled(scalar)
     }
 }
 
@@ -136,12 +867,8 @@ impl MulAssign<f32> for Mat2f32 {
 
 impl Div<f32> for Mat2f32 {
     type Output = Mat2f32;
-    fn div(mut self, scalar: f32) -> Self::Output {
-        self[0][0] /= scalar;
-        self[0][1] /= scalar;
-        self[1][0] /= scalar;
-        self[1][1] /= scalar;
-        self
+    fn div(self, scalar: f32) -> Self::Output {
+        self.divided(scalar)
     }
 }
 
@@ -154,16 +881,7 @@ impl DivAssign<f32> for Mat2f32 {
 impl Mul<Mat2f32> for Mat2f32 {
     type Output = Mat2f32;
     fn mul(self, rhs: Mat2f32) -> Self::Output {
-        Self::new([
-            [
-                self[0][0] * rhs[0][0] + self[0][1] * rhs[1][0],
-                self[0][0] * rhs[0][1] + self[0][1] * rhs[1][1],
-            ],
-            [
-                self[1][0] * rhs[0][0] + self[1][1] * rhs[1][0],
-                self[1][0] * rhs[0][1] + self[1][1] * rhs[1][1],
-            ],
-        ])
+        self.product(rhs)
     }
 }
 
@@ -176,15 +894,212 @@ impl MulAssign<Mat2f32> for Mat2f32 {
 impl Mul<Vec2f32> for Mat2f32 {
     type Output = Vec2f32;
     fn mul(self, v: Vec2f32) -> Self::Output {
-        Vec2f32::new([
-            self[0][0] * v[0] + self[0][1] * v[1],
-            self[1][0] * v[0] + self[1][1] * v[1],
+        self.product_vec(v)
+    }
+}
+
+/// The LU decomposition of a Mat2f32 with partial pivoting, see Mat2f32::lu().
+/// P * A = L * U, where P is a permutation matrix, L is lower triangular with ones
+/// on the diagonal and U is upper triangular.
+#[derive(Debug, Clone, Copy)]
+pub struct Mat2f32Lu {
+    /// L below the diagonal and U on and above the diagonal.
+    lu: [[f32; 2]; 2],
+    /// Row i of P * A is row permutation[i] of A.
+    permutation: [usize; 2],
+    /// The determinant of P (1.0 or -1.0).
+    sign: f32,
+}
+
+impl Mat2f32Lu {
+    /// Returns the lower triangular matrix L.
+    pub fn l(&self) -> Mat2f32 {
+        let mut l = Mat2f32::identity();
+        for i in 0..2 {
+            for j in 0..i {
+                l[i][j] = self.lu[i][j];
+            }
+        }
+        l
+    }
+
+    /// Returns the upper triangular matrix U.
+    pub fn u(&self) -> Mat2f32 {
+        let mut u = Mat2f32::zero();
+        for i in 0..2 {
+            for j in i..2 {
+                u[i][j] = self.lu[i][j];
+            }
+        }
+        u
+    }
+
+    /// Returns the permutation matrix P.
+    pub fn p(&self) -> Mat2f32 {
+        let mut p = Mat2f32::zero();
+        for i in 0..2 {
+            p[i][self.permutation[i]] = 1.0;
+        }
+        p
+    }
+
+    /// Returns the determinant of the decomposed matrix.
+    pub fn determinant(&self) -> f32 {
+        let mut det = self.sign;
+        for i in 0..2 {
+            det *= self.lu[i][i];
+        }
+        det
+    }
+
+    /// Solves the linear system A * x = b for x, where A is the decomposed matrix.
+    pub fn solve(&self, b: Vec2f32) -> Vec2f32 {
+        let lu = &self.lu;
+        let mut x = [0.0; 2];
+        // Forward substitution: L * y = P * b
+        for i in 0..2 {
+            let mut sum = b[self.permutation[i]];
+            for j in 0..i {
+                sum -= lu[i][j] * x[j];
+            }
+            x[i] = sum;
+        }
+        // Back substitution: U * x = y
+        for i in (0..2).rev() {
+            let mut sum = x[i];
+            for j in i + 1..2 {
+                sum -= lu[i][j] * x[j];
+            }
+            x[i] = sum / lu[i][i];
+        }
+        Vec2f32::new(x)
+    }
+}
+
+/// The Cholesky decomposition of a symmetric positive definite Mat2f32,
+/// see Mat2f32::cholesky(). A = L * L^T, where L is lower triangular
+/// with a positive diagonal.
+#[derive(Debug, Clone, Copy)]
+pub struct Mat2f32Cholesky {
+    l: Mat2f32,
+}
+
+impl Mat2f32Cholesky {
+    /// Returns the lower triangular matrix L.
+    pub fn l(&self) -> Mat2f32 {
+        self.l
+    }
+
+    /// Returns the determinant of the decomposed matrix.
+    pub fn determinant(&self) -> f32 {
+        let d = self.l.diagonal();
+        let mut det = 1.0;
+        for i in 0..2 {
+            det *= d[i] * d[i];
+        }
+        det
+    }
+
+    /// Solves the linear system A * x = b for x, where A is the decomposed matrix.
+    pub fn solve(&self, b: Vec2f32) -> Vec2f32 {
+        let l = &self.l;
+        let mut x = [0.0; 2];
+        // Forward substitution: L * y = b
+        for i in 0..2 {
+            let mut sum = b[i];
+            for j in 0..i {
+                sum -= l[i][j] * x[j];
+            }
+            x[i] = sum / l[i][i];
+        }
+        // Back substitution: L^T * x = y
+        for i in (0..2).rev() {
+            let mut sum = x[i];
+            for j in i + 1..2 {
+                sum -= l[j][i] * x[j];
+            }
+            x[i] = sum / l[i][i];
+        }
+        Vec2f32::new(x)
+    }
+
+    /// Returns the inverse of the decomposed matrix.
+    pub fn inverse(&self) -> Mat2f32 {
+        Mat2f32::from_cols([
+            self.solve(Vec2f32::new([1.0, 0.0])),
+            self.solve(Vec2f32::new([0.0, 1.0])),
+        ])
+    }
+}
+
+/// The LDL^T decomposition of a symmetric Mat2f32, see Mat2f32::ldlt().
+/// A = L * D * L^T, where L is lower triangular with ones on the diagonal
+/// and D is diagonal.
+#[derive(Debug, Clone, Copy)]
+pub struct Mat2f32Ldlt {
+    l: Mat2f32,
+    d: Vec2f32,
+}
+
+impl Mat2f32Ldlt {
+    /// Returns the lower triangular matrix L.
+    pub fn l(&self) -> Mat2f32 {
+        self.l
+    }
+
+    /// Returns the diagonal of D.
+    pub fn d(&self) -> Vec2f32 {
+        self.d
+    }
+
+    /// Returns the determinant of the decomposed matrix.
+    pub fn determinant(&self) -> f32 {
+        let mut det = 1.0;
+        for i in 0..2 {
+            det *= self.d[i];
+        }
+        det
+    }
+
+    /// Solves the linear system A * x = b for x, where A is the decomposed matrix.
+    pub fn solve(&self, b: Vec2f32) -> Vec2f32 {
+        let l = &self.l;
+        let mut x = [0.0; 2];
+        // Forward substitution: L * z = b
+        for i in 0..2 {
+            let mut sum = b[i];
+            for j in 0..i {
+                sum -= l[i][j] * x[j];
+            }
+            x[i] = sum;
+        }
+        // Diagonal: D * y = z
+        for (xi, di) in x.iter_mut().zip(self.d.coords) {
+            *xi /= di;
+        }
+        // Back substitution: L^T * x = y
+        for i in (0..2).rev() {
+            let mut sum = x[i];
+            for j in i + 1..2 {
+                sum -= l[j][i] * x[j];
+            }
+            x[i] = sum;
+        }
+        Vec2f32::new(x)
+    }
+
+    /// Returns the inverse of the decomposed matrix.
+    pub fn inverse(&self) -> Mat2f32 {
+        Mat2f32::from_cols([
+            self.solve(Vec2f32::new([1.0, 0.0])),
+            self.solve(Vec2f32::new([0.0, 1.0])),
         ])
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::mat::testing::{assert_eigen, assert_mat_close, assert_orthonormal, assert_svd};
     use crate::vec::vec2f32::Vec2f32;
 
     use super::Mat2f32;
@@ -348,4 +1263,656 @@ mod tests {
         assert_eq!(w[0], 8.0);
         assert_eq!(w[1], 18.0);
     }
+
+    #[test]
+    fn const_evaluation() {
+        const M: Mat2f32 = Mat2f32::new([[1.0, 2.0], [3.0, 4.0]]);
+        const T: Mat2f32 = M.transposed();
+        const P: Mat2f32 = M.product(M);
+        const S: Mat2f32 = M.sum(Mat2f32::identity()).difference(Mat2f32::identity());
+        const H: Mat2f32 = M.scaled(4.0).divided(2.0);
+        const W: Vec2f32 = M.product_vec(Vec2f32::new([2.0, 3.0]));
+        const Z: Mat2f32 = Mat2f32::zero();
+
+        assert_eq!(T[0][0], 1.0);
+        assert_eq!(T[0][1], 3.0);
+        assert_eq!(T[1][0], 2.0);
+        assert_eq!(T[1][1], 4.0);
+
+        assert_eq!(P[0][0], 7.0);
+        assert_eq!(P[0][1], 10.0);
+        assert_eq!(P[1][0], 15.0);
+        assert_eq!(P[1][1], 22.0);
+
+        assert_eq!(S[0][0], 1.0);
+        assert_eq!(S[0][1], 2.0);
+        assert_eq!(S[1][0], 3.0);
+        assert_eq!(S[1][1], 4.0);
+
+        assert_eq!(H[0][0], 2.0);
+        assert_eq!(H[0][1], 4.0);
+        assert_eq!(H[1][0], 6.0);
+        assert_eq!(H[1][1], 8.0);
+
+        assert_eq!(W[0], 8.0);
+        assert_eq!(W[1], 18.0);
+
+        assert_eq!(Z[0][0], 0.0);
+        assert_eq!(Z[0][1], 0.0);
+        assert_eq!(Z[1][0], 0.0);
+        assert_eq!(Z[1][1], 0.0);
+    }
+
+    #[test]
+    fn determinant_and_adjugate() {
+        let m = Mat2f32::new([[4.0, 7.0], [2.0, 6.0]]);
+        let adj = m.adjugate();
+
+        assert_eq!(m.determinant(), 10.0);
+        assert_eq!(Mat2f32::identity().determinant(), 1.0);
+        assert_eq!(Mat2f32::zero().determinant(), 0.0);
+
+        assert_eq!(adj[0][0], 6.0);
+        assert_eq!(adj[0][1], -7.0);
+        assert_eq!(adj[1][0], -2.0);
+        assert_eq!(adj[1][1], 4.0);
+    }
+
+    #[test]
+    fn inverse() {
+        let m = Mat2f32::new([[4.0, 7.0], [2.0, 6.0]]);
+        let inv = m.inverse();
+        let expected = [[0.6, -0.7], [-0.2, 0.4]];
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((inv[i][j] - expected[i][j]).abs() < 1e-6);
+            }
+        }
+
+        let id = m * m.try_inverse(1e-6).unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((id[i][j] - expected).abs() < 1e-6);
+            }
+        }
+
+        // Singular matrices
+        let singular = Mat2f32::new([[1.0, 2.0], [2.0, 4.0]]);
+        assert!(singular.try_inverse(1e-6).is_none());
+        assert!(Mat2f32::zero().try_inverse(0.0).is_none());
+
+        // Configurable tolerance
+        let almost_singular = Mat2f32::new([[0.1, 0.0], [0.0, 0.01]]);
+        assert!(almost_singular.try_inverse(0.01).is_none());
+        assert!(almost_singular.try_inverse(0.0001).is_some());
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let m = Mat2f32::new([[1.0, 2.0], [3.0, 4.0]]);
+        let from_rows = Mat2f32::from_rows([Vec2f32::new([1.0, 2.0]), Vec2f32::new([3.0, 4.0])]);
+        let from_cols = Mat2f32::from_cols([Vec2f32::new([1.0, 2.0]), Vec2f32::new([3.0, 4.0])]);
+
+        assert_eq!(from_rows[0][0], 1.0);
+        assert_eq!(from_rows[0][1], 2.0);
+        assert_eq!(from_rows[1][0], 3.0);
+        assert_eq!(from_rows[1][1], 4.0);
+
+        assert_eq!(from_cols[0][0], 1.0);
+        assert_eq!(from_cols[0][1], 3.0);
+        assert_eq!(from_cols[1][0], 2.0);
+        assert_eq!(from_cols[1][1], 4.0);
+
+        assert_eq!(m.row(0)[0], 1.0);
+        assert_eq!(m.row(0)[1], 2.0);
+        assert_eq!(m.row(1)[0], 3.0);
+        assert_eq!(m.row(1)[1], 4.0);
+
+        assert_eq!(m.col(0)[0], 1.0);
+        assert_eq!(m.col(0)[1], 3.0);
+        assert_eq!(m.col(1)[0], 2.0);
+        assert_eq!(m.col(1)[1], 4.0);
+
+        let mut n = Mat2f32::zero();
+        n.set_row(1, Vec2f32::ones());
+        n.set_col(0, Vec2f32::new([-1.0, -2.0]));
+        assert_eq!(n[0][0], -1.0);
+        assert_eq!(n[0][1], 0.0);
+        assert_eq!(n[1][0], -2.0);
+        assert_eq!(n[1][1], 1.0);
+    }
+
+    #[test]
+    fn diagonal_and_trace() {
+        let m = Mat2f32::new([[1.0, 2.0], [3.0, 4.0]]);
+        let d = Mat2f32::from_diagonal(Vec2f32::new([2.0, 3.0]));
+
+        assert_eq!(m.diagonal()[0], 1.0);
+        assert_eq!(m.diagonal()[1], 4.0);
+
+        assert_eq!(d[0][0], 2.0);
+        assert_eq!(d[0][1], 0.0);
+        assert_eq!(d[1][0], 0.0);
+        assert_eq!(d[1][1], 3.0);
+
+        assert_eq!(m.trace(), 5.0);
+        assert_eq!(d.trace(), 5.0);
+        assert_eq!(Mat2f32::identity().trace(), 2.0);
+    }
+
+    #[test]
+    fn frobenius_norm() {
+        let m = Mat2f32::new([[1.0, 2.0], [3.0, 4.0]]);
+
+        assert_eq!(m.frobenius_norm_squared(), 30.0);
+        assert_eq!(m.frobenius_norm(), 30.0f32.sqrt());
+        assert_eq!(Mat2f32::identity().frobenius_norm(), 2.0f32.sqrt());
+        assert_eq!(Mat2f32::zero().frobenius_norm(), 0.0);
+    }
+
+    #[test]
+    fn outer_product() {
+        let a = Vec2f32::new([1.0, 2.0]);
+        let b = Vec2f32::new([2.0, -1.0]);
+        let m = Mat2f32::outer_product(a, b);
+
+        assert_eq!(m[0][0], 2.0);
+        assert_eq!(m[0][1], -1.0);
+        assert_eq!(m[1][0], 4.0);
+        assert_eq!(m[1][1], -2.0);
+    }
+
+    #[test]
+    fn flat_arrays() {
+        let m = Mat2f32::new([[1.0, 2.0], [3.0, 4.0]]);
+        let rows = m.as_row_major_flat();
+        let cols = m.as_col_major_flat();
+        let from_rows = Mat2f32::from_row_major_flat([1.0, 2.0, 3.0, 4.0]);
+        let from_cols = Mat2f32::from_col_major_flat([1.0, 3.0, 2.0, 4.0]);
+
+        assert_eq!(rows[0], 1.0);
+        assert_eq!(rows[1], 2.0);
+        assert_eq!(rows[2], 3.0);
+        assert_eq!(rows[3], 4.0);
+
+        assert_eq!(cols[0], 1.0);
+        assert_eq!(cols[1], 3.0);
+        assert_eq!(cols[2], 2.0);
+        assert_eq!(cols[3], 4.0);
+
+        assert_eq!(from_rows[0][0], 1.0);
+        assert_eq!(from_rows[0][1], 2.0);
+        assert_eq!(from_rows[1][0], 3.0);
+        assert_eq!(from_rows[1][1], 4.0);
+
+        assert_eq!(from_cols[0][0], 1.0);
+        assert_eq!(from_cols[0][1], 2.0);
+        assert_eq!(from_cols[1][0], 3.0);
+        assert_eq!(from_cols[1][1], 4.0);
+    }
+
+    #[test]
+    fn precision_conversion() {
+        let m = Mat2f32::new([[1.0, 2.0 / 3.0], [3.0 / 3.0, 4.0]]);
+        let c = m.to_f64();
+
+        assert_eq!(c[0][0], m[0][0] as f64);
+        assert_eq!(c[0][1], m[0][1] as f64);
+        assert_eq!(c[1][0], m[1][0] as f64);
+        assert_eq!(c[1][1], m[1][1] as f64);
+    }
+
+    #[test]
+    fn lu_decomposition() {
+        let m = Mat2f32::new([[0.0, 2.0], [3.0, 1.0]]);
+        let lu = m.lu(1e-6).unwrap();
+        let (l, u, p) = (lu.l(), lu.u(), lu.p());
+        let pa = p * m;
+        let product = l * u;
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((pa[i][j] - product[i][j]).abs() < 1e-5);
+                if j > i {
+                    assert_eq!(l[i][j], 0.0);
+                }
+                if j < i {
+                    assert_eq!(u[i][j], 0.0);
+                }
+            }
+            assert_eq!(l[i][i], 1.0);
+        }
+        // The first pivot is 0.0, so rows have to be swapped
+        assert_eq!(p[0][0], 0.0);
+        assert!((lu.determinant() - m.determinant()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn solve() {
+        let m = Mat2f32::new([[0.0, 2.0], [3.0, 1.0]]);
+        let b = Vec2f32::new([-4.0, 1.0]);
+        let x = m.solve(b, 1e-6).unwrap();
+        let expected = [1.0, -2.0];
+        for i in 0..2 {
+            assert!((x[i] - expected[i]).abs() < 1e-5);
+        }
+
+        // Singular matrices
+        let singular = Mat2f32::new([[1.0, 2.0], [2.0, 4.0]]);
+        assert!(singular.lu(1e-5).is_none());
+        assert!(singular.solve(b, 1e-5).is_none());
+        assert!(Mat2f32::zero().lu(0.0).is_none());
+        assert!(Mat2f32::identity().lu(0.0).is_some());
+    }
+
+    #[test]
+    fn qr_decomposition() {
+        let m = Mat2f32::new([[3.0, 1.0], [4.0, 2.0]]);
+        let (q, r) = m.qr();
+        let product = q * r;
+
+        assert_orthonormal(q);
+        for i in 0..2 {
+            assert!(r[i][i] >= 0.0);
+            for j in 0..2 {
+                if j < i {
+                    assert_eq!(r[i][j], 0.0);
+                }
+                assert!((product[i][j] - m[i][j]).abs() < 1e-3);
+            }
+        }
+
+        let (q, r) = Mat2f32::identity().qr();
+        for i in 0..2 {
+            for j in 0..2 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((q[i][j] - expected).abs() < 1e-6);
+                assert!((r[i][j] - expected).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn orthonormalize() {
+        let rotation = Mat2f32::new([[0.8, -0.6], [0.6, 0.8]]);
+        let mut drifted = rotation;
+        for i in 0..2 {
+            for j in 0..2 {
+                drifted[i][j] += 0.01 * ((i * 2 + j) % 3) as f32 - 0.01;
+            }
+        }
+        let original = drifted.col(0).normed();
+        let orthonormalized = drifted.orthonormalized();
+        drifted.orthonormalize();
+
+        assert_orthonormal(orthonormalized);
+        assert_orthonormal(drifted);
+        assert!((orthonormalized.determinant() - 1.0).abs() < 1e-5);
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((orthonormalized[i][j] - rotation[i][j]).abs() < 0.05);
+            }
+        }
+
+        // The first column keeps its direction
+        assert!((orthonormalized.col(0) - original).mag() < 1e-6);
+    }
+
+    #[test]
+    fn symmetric_eigen() {
+        let m = Mat2f32::new([[2.0, 1.0], [1.0, 2.0]]);
+        let (values, vectors) = m.symmetric_eigen();
+        assert!((values[0] - 3.0).abs() < 1e-5);
+        assert!((values[1] - 1.0).abs() < 1e-5);
+        assert_eigen(m, values, vectors);
+
+        let m = Mat2f32::new([[1.0, 0.0], [0.0, 5.0]]);
+        let (values, vectors) = m.symmetric_eigen();
+        assert!((values[0] - 5.0).abs() < 1e-5);
+        assert!((values[1] - 1.0).abs() < 1e-5);
+        assert_eigen(m, values, vectors);
+
+        let m = Mat2f32::new([[-3.0, 2.5], [2.5, 0.5]]);
+        let (values, vectors) = m.symmetric_eigen();
+        assert_eigen(m, values, vectors);
+
+        let (values, vectors) = Mat2f32::identity().symmetric_eigen();
+        assert_eq!(values[0], 1.0);
+        assert_eq!(values[1], 1.0);
+        assert_eigen(Mat2f32::identity(), values, vectors);
+    }
+
+    #[test]
+    fn svd() {
+        let m = Mat2f32::new([[3.0, 0.0], [4.0, 5.0]]);
+        let (_, sigma, _) = m.svd();
+        assert!((sigma[0] - 45.0f32.sqrt()).abs() < 1e-5);
+        assert!((sigma[1] - 5.0f32.sqrt()).abs() < 1e-5);
+        assert_svd(m, m.svd());
+
+        // Reflections result in a negative last singular value
+        let m = Mat2f32::new([[0.0, 2.0], [1.0, 0.0]]);
+        let (_, sigma, _) = m.svd();
+        assert!((sigma[0] - 2.0).abs() < 1e-5);
+        assert!((sigma[1] + 1.0).abs() < 1e-5);
+        assert_svd(m, m.svd());
+
+        // Rank deficient
+        for m in [
+            Mat2f32::new([[1.0, 2.0], [2.0, 4.0]]),
+            Mat2f32::identity(),
+            Mat2f32::new([[0.8, -0.6], [0.6, 0.8]])
+                * Mat2f32::from_diagonal(Vec2f32::new([2.0, 0.5])),
+        ] {
+            assert_svd(m, m.svd());
+        }
+    }
+
+    #[test]
+    fn svd_small_singular_value() {
+        // self^T * self would square the ratio of 1e-5 between the singular values
+        let u = Mat2f32::new([[0.8, -0.6], [0.6, 0.8]]);
+        let v = Mat2f32::new([[0.6, -0.8], [0.8, 0.6]]);
+        let m = u * Mat2f32::from_diagonal(Vec2f32::new([1.0, 1e-5])) * v.transposed();
+        let (u, sigma, v) = m.svd();
+        assert!((sigma[1] - 1e-5).abs() < 1e-7);
+        assert_mat_close(u.transposed() * u, Mat2f32::identity(), 1e-6);
+        assert_mat_close(v.transposed() * v, Mat2f32::identity(), 1e-6);
+        assert_mat_close(u * Mat2f32::from_diagonal(sigma) * v.transposed(), m, 1e-7);
+        assert_mat_close(m.pseudo_inverse(0.0) * m, Mat2f32::identity(), 1e-2);
+    }
+
+    #[test]
+    fn pseudo_inverse() {
+        let m = Mat2f32::new([[4.0, 7.0], [2.0, 6.0]]);
+        let p = m.pseudo_inverse(1e-6);
+        let inverse = m.inverse();
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((p[i][j] - inverse[i][j]).abs() < 1e-4);
+            }
+        }
+
+        // m * m^+ * m = m for singular matrices
+        let m = Mat2f32::new([[1.0, 2.0], [2.0, 4.0]]);
+        let p = m.pseudo_inverse(1e-5);
+        let r = m * p * m;
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((r[i][j] - m[i][j]).abs() < 1e-4);
+            }
+        }
+        let zero = Mat2f32::zero().pseudo_inverse(1e-6);
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_eq!(zero[i][j], 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn cholesky() {
+        let m = Mat2f32::new([[4.0, 2.0], [2.0, 3.0]]);
+        let cholesky = m.cholesky(1e-6).unwrap();
+        let l = cholesky.l();
+        assert_mat_close(l * l.transposed(), m, 1e-4);
+        for i in 0..2 {
+            assert!(l[i][i] > 0.0);
+            for j in i + 1..2 {
+                assert_eq!(l[i][j], 0.0);
+            }
+        }
+
+        let x = cholesky.solve(Vec2f32::new([0.0, -4.0]));
+        assert!((x - Vec2f32::new([1.0, -2.0])).mag() < 1e-4);
+        assert!((cholesky.determinant() - m.determinant()).abs() < 1e-2);
+        assert_mat_close(cholesky.inverse() * m, Mat2f32::identity(), 1e-3);
+
+        // Not positive definite
+        assert!(Mat2f32::new([[1.0, 2.0], [2.0, 1.0]])
+            .cholesky(1e-6)
+            .is_none());
+        assert!((-1.0 * Mat2f32::identity()).cholesky(1e-6).is_none());
+        assert!(Mat2f32::zero().cholesky(0.0).is_none());
+    }
+
+    #[test]
+    fn ldlt() {
+        for m in [
+            Mat2f32::new([[4.0, 2.0], [2.0, 3.0]]),
+            Mat2f32::new([[1.0, 2.0], [2.0, 1.0]]),
+        ] {
+            let ldlt = m.ldlt(1e-6).unwrap();
+            let l = ldlt.l();
+            let d = Mat2f32::from_diagonal(ldlt.d());
+            assert_mat_close(l * d * l.transposed(), m, 1e-4);
+            for i in 0..2 {
+                assert_eq!(l[i][i], 1.0);
+                for j in i + 1..2 {
+                    assert_eq!(l[i][j], 0.0);
+                }
+            }
+            assert!((ldlt.determinant() - m.determinant()).abs() < 1e-2);
+        }
+
+        let m = Mat2f32::new([[1.0, 2.0], [2.0, 1.0]]);
+        let x = m.ldlt(1e-6).unwrap().solve(Vec2f32::new([-3.0, 0.0]));
+        assert!((x - Vec2f32::new([1.0, -2.0])).mag() < 1e-4);
+
+        assert!(Mat2f32::zero().ldlt(0.0).is_none());
+    }
+
+    #[test]
+    fn symmetric_inverse() {
+        for m in [
+            Mat2f32::new([[4.0, 2.0], [2.0, 3.0]]),
+            Mat2f32::new([[1.0, 2.0], [2.0, 1.0]]),
+        ] {
+            let inverse = m.symmetric_inverse(1e-6).unwrap();
+            assert_mat_close(inverse * m, Mat2f32::identity(), 1e-3);
+            assert_mat_close(inverse, m.inverse(), 1e-3);
+        }
+        assert!(Mat2f32::zero().symmetric_inverse(1e-6).is_none());
+    }
+
+    #[test]
+    fn powi() {
+        let m = Mat2f32::new([[1.0, 2.0], [-1.0, 3.0]]);
+        assert_eq!(m.powi(0).as_row_major(), Mat2f32::identity().as_row_major());
+        assert_eq!(m.powi(1).as_row_major(), m.as_row_major());
+        assert_mat_close(m.powi(3), m * m * m, 1e-3);
+        assert_mat_close(m.powi(-2), m.inverse() * m.inverse(), 1e-3);
+        assert_mat_close(m.powi(5) * m.powi(-5), Mat2f32::identity(), 1e-3);
+    }
+
+    #[test]
+    fn powf() {
+        let rotation = Mat2f32::new([[0.7648422, -0.6442177], [0.6442177, 0.7648422]]);
+        assert_mat_close(
+            rotation.powf(0.5).unwrap(),
+            Mat2f32::new([[0.9393727, -0.3428978], [0.3428978, 0.9393727]]),
+            1e-4,
+        );
+        assert_mat_close(rotation.powf(0.0).unwrap(), Mat2f32::identity(), 1e-4);
+
+        let m = Mat2f32::new([[4.0, 2.0], [2.0, 3.0]]);
+        assert_mat_close(m.powf(2.0).unwrap(), m * m, 1e-3);
+        assert_mat_close(m.powf(-1.0).unwrap(), m.inverse(), 1e-4);
+        assert!((-1.0 * Mat2f32::identity()).powf(0.5).is_none());
+    }
+
+    #[test]
+    fn exp() {
+        assert_eq!(
+            Mat2f32::zero().exp().as_row_major(),
+            Mat2f32::identity().as_row_major()
+        );
+        assert_mat_close(
+            Mat2f32::new([[0.0, -0.7], [0.7, 0.0]]).exp(),
+            Mat2f32::new([[0.7648422, -0.6442177], [0.6442177, 0.7648422]]),
+            1e-5,
+        );
+        assert_mat_close(
+            Mat2f32::new([[0.5, 0.0], [0.0, 2.0]]).exp(),
+            Mat2f32::new([[1.648721, 0.0], [0.0, 7.389056]]),
+            1e-5,
+        );
+        assert_mat_close(
+            Mat2f32::new([[0.0, 0.5], [0.0, 0.0]]).exp(),
+            Mat2f32::new([[1.0, 0.5], [0.0, 1.0]]),
+            1e-5,
+        );
+        assert_mat_close(
+            Mat2f32::new([[0.1, -0.3], [0.25, 0.2]]).exp(),
+            Mat2f32::new([[1.062579, -0.3443539], [0.2869616, 1.177364]]),
+            1e-5,
+        );
+        let large = Mat2f32::new([[1.0, 2.0], [-1.0, 3.0]]);
+        let expected = Mat2f32::new([[-2.225352, 12.43535], [-6.217676, 10.21]]);
+        assert_mat_close(large.exp(), expected, 1e-4 * large.exp().frobenius_norm());
+    }
+
+    #[test]
+    fn log() {
+        assert_eq!(
+            Mat2f32::identity().log().unwrap().as_row_major(),
+            Mat2f32::zero().as_row_major()
+        );
+        assert_mat_close(
+            Mat2f32::new([[0.7648422, -0.6442177], [0.6442177, 0.7648422]])
+                .log()
+                .unwrap(),
+            Mat2f32::new([[0.0, -0.7], [0.7, 0.0]]),
+            1e-4,
+        );
+        assert_mat_close(
+            Mat2f32::new([[0.1, -0.3], [0.25, 0.2]])
+                .exp()
+                .log()
+                .unwrap(),
+            Mat2f32::new([[0.1, -0.3], [0.25, 0.2]]),
+            1e-4,
+        );
+        assert_mat_close(
+            Mat2f32::new([[4.0, 2.0], [2.0, 3.0]]).log().unwrap(),
+            Mat2f32::new([[1.203713, 0.6559682], [0.6559682, 0.8757287]]),
+            1e-4,
+        );
+        assert!((-1.0 * Mat2f32::identity()).log().is_none());
+        assert!(Mat2f32::zero().log().is_none());
+    }
+
+    #[test]
+    fn sqrt() {
+        let m = Mat2f32::new([[4.0, 2.0], [2.0, 3.0]]);
+        let root = m.sqrt().unwrap();
+        assert_mat_close(root * root, m, 1e-4);
+        assert_mat_close(
+            root,
+            Mat2f32::new([[1.919366, 0.5621693], [0.5621693, 1.638281]]),
+            1e-4,
+        );
+        assert_mat_close(
+            Mat2f32::new([[0.7648422, -0.6442177], [0.6442177, 0.7648422]])
+                .sqrt()
+                .unwrap(),
+            Mat2f32::new([[0.9393727, -0.3428978], [0.3428978, 0.9393727]]),
+            1e-5,
+        );
+        assert_eq!(
+            Mat2f32::identity().sqrt().unwrap().as_row_major(),
+            Mat2f32::identity().as_row_major()
+        );
+        assert!((-1.0 * Mat2f32::identity()).sqrt().is_none());
+        assert_eq!(
+            Mat2f32::zero().sqrt().unwrap().as_row_major(),
+            Mat2f32::zero().as_row_major()
+        );
+
+        // Singular positive semidefinite matrices have a square root
+        assert_mat_close(
+            Mat2f32::from_diagonal(Vec2f32::new([4.0, 0.0]))
+                .sqrt()
+                .unwrap(),
+            Mat2f32::from_diagonal(Vec2f32::new([2.0, 0.0])),
+            1e-6,
+        );
+        // A rank one matrix v * v^T has the square root v * v^T / |v|
+        let m = Mat2f32::new([[1.0, 2.0], [2.0, 4.0]]);
+        assert_mat_close(m.sqrt().unwrap(), m / 5.0f32.sqrt(), 1e-5);
+        // Singular matrices that are not symmetric need not have a square root
+        assert!(Mat2f32::new([[0.0, 1.0], [0.0, 0.0]]).sqrt().is_none());
+    }
+
+    #[test]
+    fn rank() {
+        assert_eq!(Mat2f32::new([[2.5, 0.5], [0.0, 2.5]]).rank(1e-6), 2);
+        assert_eq!(Mat2f32::new([[2.0, 1.0], [-4.0, -2.0]]).rank(1e-6), 1);
+        assert_eq!(Mat2f32::zero().rank(1e-6), 0);
+    }
+
+    #[test]
+    fn null_space() {
+        let m = Mat2f32::new([[2.0, 1.0], [-4.0, -2.0]]);
+        let basis = m.null_space(1e-6);
+        assert_eq!(basis.len(), 1);
+        for (i, v) in basis.iter().enumerate() {
+            assert!((m * *v).mag() < 1e-5);
+            assert!((v.mag() - 1.0).abs() < 1e-5);
+            for w in &basis[..i] {
+                assert!(v.dot(*w).abs() < 1e-5);
+            }
+        }
+
+        let m = Mat2f32::new([[2.5, 0.5], [0.0, 2.5]]);
+        let basis = m.null_space(1e-6);
+        assert_eq!(basis.len(), 0);
+        for v in basis {
+            assert!((m * v).mag() < 1e-5);
+        }
+        assert_eq!(Mat2f32::zero().null_space(1e-6).len(), 2);
+    }
+
+    #[test]
+    fn condition_number() {
+        let m = Mat2f32::new([[1.0, 0.0], [0.0, 2.0]]);
+        assert!((Mat2f32::identity().condition_number() - 2.0).abs() < 1e-5);
+        assert!((m.condition_number() - 2.5).abs() < 1e-4);
+        assert!(Mat2f32::new([[2.0, 1.0], [-4.0, -2.0]]).condition_number() > 1e6);
+        assert_eq!(Mat2f32::zero().condition_number(), f32::INFINITY);
+    }
+
+    #[test]
+    fn is_invertible() {
+        assert!(Mat2f32::new([[2.5, 0.5], [0.0, 2.5]]).is_invertible(1e-6));
+        assert!(!Mat2f32::new([[2.0, 1.0], [-4.0, -2.0]]).is_invertible(1e-6));
+        assert!(!Mat2f32::zero().is_invertible(0.0));
+    }
+
+    #[test]
+    fn is_orthogonal() {
+        let rotation = Mat2f32::new([[0.6, -0.8], [0.8, 0.6]]);
+        assert!(rotation.is_orthogonal(1e-6));
+        assert!((-1.0 * rotation).is_orthogonal(1e-6));
+        assert!(!(2.0 * rotation).is_orthogonal(1e-6));
+        assert!(!Mat2f32::new([[2.5, 0.5], [0.0, 2.5]]).is_orthogonal(1e-6));
+    }
+
+    #[test]
+    fn is_symmetric() {
+        let m = Mat2f32::new([[2.5, 0.5], [0.0, 2.5]]);
+        assert!(!m.is_symmetric(1e-6));
+        assert!((m + m.transposed()).is_symmetric(1e-6));
+        let v = Vec2f32::new([1.0, -2.0]);
+        assert!(Mat2f32::outer_product(v, v).is_symmetric(1e-6));
+        assert!(!Mat2f32::outer_product(v, 2.0 * v + Vec2f32::ones()).is_symmetric(1e-6));
+    }
+
+    #[test]
+    fn from_reflection() {
+        let m = Mat2f32::from_reflection(Vec2f32::new([1.0, 1.0]));
+        assert!((m * Vec2f32::new([1.0, 0.0]) - Vec2f32::new([0.0, -1.0])).mag() < 1e-6);
+        assert!((m * Vec2f32::new([1.0, -1.0]) - Vec2f32::new([1.0, -1.0])).mag() < 1e-6);
+        assert!((m.determinant() + 1.0).abs() < 1e-6);
+    }
 }
@@ -0,0 +1,948 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+
+use crate::mat::mat2f64::Mat2f64;
+use crate::mat::mat2x3f64::Mat2x3f64;
+use crate::mat::mat2x4f32::Mat2x4f32;
+use crate::mat::mat4f64::Mat4f64;
+use crate::mat::mat4x2f64::Mat4x2f64;
+use crate::mat::mat4x3f64::Mat4x3f64;
+use crate::vec::vec2f64::Vec2f64;
+use crate::vec::vec4f64::Vec4f64;
+
+/// A 2x4 floating point matrix (2 rows and 4 columns).
+/// Indexing follows row major order, like in most mathematical texts.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bitcode", derive(bitcode::Encode, bitcode::Decode))]
+pub struct Mat2x4f64 {
+    pub rows: [[f64; 4]; 2],
+}
+
+impl Mat2x4f64 {
+    /// Creates a new matrix with user defined elements.
+    /// The user defined elements are in row major order.
+    pub const fn new(rows: [[f64; 4]; 2]) -> Self {
+        Self { rows }
+    }
+
+    /// Creates a new matrix with user defined elements.
+    /// The user defined elements are in column major order.
+    pub const fn new_from_cols(cols: [[f64; 2]; 4]) -> Self {
+        Self::new([
+            [cols[0][0], cols[1][0], cols[2][0], cols[3][0]],
+            [cols[0][1], cols[1][1], cols[2][1], cols[3][1]],
+        ])
+    }
+
+    /// Creates a new matrix with all elements equal to 0.0.
+    pub const fn zero() -> Self {
+        Self {
+            rows: [[0.0, 0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 0.0]],
+        }
+    }
+
+    /// Creates a new matrix from row vectors.
+    pub const fn from_rows(rows: [Vec4f64; 2]) -> Self {
+        Self::new([rows[0].coords, rows[1].coords])
+    }
+
+    /// Creates a new matrix from column vectors.
+    pub const fn from_cols(cols: [Vec2f64; 4]) -> Self {
+        Self::new_from_cols([
+            cols[0].coords,
+            cols[1].coords,
+            cols[2].coords,
+            cols[3].coords,
+        ])
+    }
+
+    /// Returns self but transposed
+    /// (Rows are now columns and columns are now rows).
+    pub const fn transposed(&self) -> Mat4x2f64 {
+        let m = &self.rows;
+        Mat4x2f64::new([
+            [m[0][0], m[1][0]],
+            [m[0][1], m[1][1]],
+            [m[0][2], m[1][2]],
+            [m[0][3], m[1][3]],
+        ])
+    }
+
+    /// Returns the matrix rows as arrays in row major order.
+    pub const fn as_row_major(&self) -> [[f64; 4]; 2] {
+        self.rows
+    }
+
+    /// Returns the matrix columns as arrays in column major order.
+    pub const fn as_col_major(&self) -> [[f64; 2]; 4] {
+        self.transposed().rows
+    }
+
+    /// Returns the row at index as a vector.
+    pub const fn row(&self, index: usize) -> Vec4f64 {
+        Vec4f64::new(self.rows[index])
+    }
+
+    /// Returns the column at index as a vector.
+    pub const fn col(&self, index: usize) -> Vec2f64 {
+        let m = &self.rows;
+        Vec2f64::new([m[0][index], m[1][index]])
+    }
+
+    /// Replaces the row at index with row.
+    pub const fn set_row(&mut self, index: usize, row: Vec4f64) {
+        self.rows[index] = row.coords;
+    }
+
+    /// Replaces the column at index with col.
+    pub const fn set_col(&mut self, index: usize, col: Vec2f64) {
+        self.rows[0][index] = col.coords[0];
+        self.rows[1][index] = col.coords[1];
+    }
+
+    /// Creates a new matrix from a flat array of elements in row major order.
+    pub const fn from_row_major_flat(elements: [f64; 8]) -> Self {
+        let e = &elements;
+        Self::new([[e[0], e[1], e[2], e[3]], [e[4], e[5], e[6], e[7]]])
+    }
+
+    /// Creates a new matrix from a flat array of elements in column major order.
+    pub const fn from_col_major_flat(elements: [f64; 8]) -> Self {
+        let e = &elements;
+        Self::new([[e[0], e[2], e[4], e[6]], [e[1], e[3], e[5], e[7]]])
+    }
+
+    /// Returns all elements as a flat array in row major order.
+    pub const fn as_row_major_flat(&self) -> [f64; 8] {
+        let m = &self.rows;
+        [
+            m[0][0], m[0][1], m[0][2], m[0][3], m[1][0], m[1][1], m[1][2], m[1][3],
+        ]
+    }
+
+    /// Returns all elements as a flat array in column major order.
+    /// This is the layout most graphics APIs expect for uniform uploads.
+    pub const fn as_col_major_flat(&self) -> [f64; 8] {
+        let m = &self.rows;
+        [
+            m[0][0], m[1][0], m[0][1], m[1][1], m[0][2], m[1][2], m[0][3], m[1][3],
+        ]
+    }
+
+    /// Returns self converted to single precision.
+    /// Elements are rounded to the nearest representable f32 value.
+    pub const fn to_f32(&self) -> Mat2x4f32 {
+        let m = &self.rows;
+        Mat2x4f32::new([
+            [
+                m[0][0] as f32,
+                m[0][1] as f32,
+                m[0][2] as f32,
+                m[0][3] as f32,
+            ],
+            [
+                m[1][0] as f32,
+                m[1][1] as f32,
+                m[1][2] as f32,
+                m[1][3] as f32,
+            ],
+        ])
+    }
+
+    /// Reduces self to reduced row echelon form with Gaussian elimination and partial pivoting.
+    /// Returns the reduced rows and which columns contain a pivot. Pivots with an absolute
+    /// value less than or equal to tolerance are treated as zero.
+    fn row_echelon(&self, tolerance: f64) -> ([[f64; 4]; 2], [bool; 4]) {
+        let mut a = self.rows;
+        let mut pivots = [false; 4];
+        let mut rank = 0;
+        for col in 0..4 {
+            if rank == 2 {
+                break;
+            }
+            let mut pivot = rank;
+            for (row, values) in a.iter().enumerate().skip(rank + 1) {
+                if values[col].abs() > a[pivot][col].abs() {
+                    pivot = row;
+                }
+            }
+            if a[pivot][col].abs() <= tolerance {
+                continue;
+            }
+            a.swap(rank, pivot);
+            let pivot_value = a[rank][col];
+            for x in a[rank].iter_mut() {
+                *x /= pivot_value;
+            }
+            let pivot_row = a[rank];
+            for (row, values) in a.iter_mut().enumerate() {
+                if row != rank {
+                    let factor = values[col];
+                    for (x, p) in values.iter_mut().zip(pivot_row) {
+                        *x -= factor * p;
+                    }
+                }
+            }
+            pivots[col] = true;
+            rank += 1;
+        }
+        (a, pivots)
+    }
+
+    /// Returns the rank of self, computed with Gaussian elimination and partial pivoting.
+    /// Pivots with an absolute value less than or equal to tolerance are treated as zero.
+    pub fn rank(&self, tolerance: f64) -> usize {
+        let (_, pivots) = self.row_echelon(tolerance);
+        pivots.iter().filter(|&&pivot| pivot).count()
+    }
+
+    /// Returns an orthonormal basis of the null space of self, the vectors v with self * v = 0.
+    /// The basis has at least two vectors, as self has more columns than rows,
+    /// and exactly two vectors if self has full row rank.
+    /// Pivots with an absolute value less than or equal to tolerance are treated as zero (see rank()).
+    pub fn null_space(&self, tolerance: f64) -> Vec<Vec4f64> {
+        let (a, pivots) = self.row_echelon(tolerance);
+        let mut basis = Vec::new();
+        for free in 0..4 {
+            if pivots[free] {
+                continue;
+            }
+            let mut v = Vec4f64::zero();
+            v[free] = 1.0;
+            // The reduced rows have their pivots in the pivot columns in order
+            let mut row = 0;
+            for col in 0..4 {
+                if pivots[col] {
+                    v[col] = -a[row][free];
+                    row += 1;
+                }
+            }
+            basis.push(v);
+        }
+        Vec4f64::gram_schmidt(&mut basis);
+        basis
+    }
+
+    /// Returns the condition number of self in the Frobenius norm,
+    /// ||self|| * ||self^+||, where self^+ is the pseudo inverse. It is at least 2
+    /// and grows as self gets closer to losing rank.
+    /// Returns infinity if self does not have full rank.
+    pub fn condition_number(&self) -> f64 {
+        // ||self^+||^2 is the trace of the inverse of self * self^T
+        let gram = *self * self.transposed();
+        match gram.try_inverse(0.0) {
+            Some(inverse) => gram.trace().sqrt() * inverse.trace().sqrt(),
+            None => f64::INFINITY,
+        }
+    }
+
+    /// Returns true if the rows of self are orthonormal, meaning self * self^T
+    /// differs from the identity by at most tolerance in every element.
+    pub fn is_orthogonal(&self, tolerance: f64) -> bool {
+        let gram = *self * self.transposed();
+        let identity = Mat2f64::identity();
+        for i in 0..2 {
+            for j in 0..2 {
+                if (gram[i][j] - identity[i][j]).abs() > tolerance {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the sum of self and rhs.
+    /// Same as the + operator, but usable in const contexts.
+    pub const fn sum(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [
+                a[0][0] + b[0][0],
+                a[0][1] + b[0][1],
+                a[0][2] + b[0][2],
+                a[0][3] + b[0][3],
+            ],
+            [
+                a[1][0] + b[1][0],
+                a[1][1] + b[1][1],
+                a[1][2] + b[1][2],
+                a[1][3] + b[1][3],
+            ],
+        ])
+    }
+
+    /// Returns the difference of self and rhs.
+    /// Same as the - operator, but usable in const contexts.
+    pub const fn difference(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [
+                a[0][0] - b[0][0],
+                a[0][1] - b[0][1],
+                a[0][2] - b[0][2],
+                a[0][3] - b[0][3],
+            ],
+            [
+                a[1][0] - b[1][0],
+                a[1][1] - b[1][1],
+                a[1][2] - b[1][2],
+                a[1][3] - b[1][3],
+            ],
+        ])
+    }
+
+    /// Returns self with every element multiplied by scalar.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn scaled(&self, scalar: f64) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [
+                a[0][0] * scalar,
+                a[0][1] * scalar,
+                a[0][2] * scalar,
+                a[0][3] * scalar,
+            ],
+            [
+                a[1][0] * scalar,
+                a[1][1] * scalar,
+                a[1][2] * scalar,
+                a[1][3] * scalar,
+            ],
+        ])
+    }
+
+    /// Returns self with every element divided by scalar.
+    /// Same as the / operator, but usable in const contexts.
+    pub const fn divided(&self, scalar: f64) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [
+                a[0][0] / scalar,
+                a[0][1] / scalar,
+                a[0][2] / scalar,
+                a[0][3] / scalar,
+            ],
+            [
+                a[1][0] / scalar,
+                a[1][1] / scalar,
+                a[1][2] / scalar,
+                a[1][3] / scalar,
+            ],
+        ])
+    }
+
+    /// Returns the product of self and the column vector v.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product_vec(&self, v: Vec4f64) -> Vec2f64 {
+        let a = &self.rows;
+        let b = &v.coords;
+        Vec2f64::new([
+            a[0][0] * b[0] + a[0][1] * b[1] + a[0][2] * b[2] + a[0][3] * b[3],
+            a[1][0] * b[0] + a[1][1] * b[1] + a[1][2] * b[2] + a[1][3] * b[3],
+        ])
+    }
+}
+
+impl Index<usize> for Mat2x4f64 {
+    type Output = [f64; 4];
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.rows[index]
+    }
+}
+
+impl IndexMut<usize> for Mat2x4f64 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.rows[index]
+    }
+}
+
+impl Add<Mat2x4f64> for Mat2x4f64 {
+    type Output = Mat2x4f64;
+    fn add(self, rhs: Mat2x4f64) -> Self::Output {
+        self.sum(rhs)
+    }
+}
+
+impl AddAssign<Mat2x4f64> for Mat2x4f64 {
+    fn add_assign(&mut self, rhs: Mat2x4f64) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Mat2x4f64> for Mat2x4f64 {
+    type Output = Mat2x4f64;
+    fn sub(self, rhs: Mat2x4f64) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl SubAssign<Mat2x4f64> for Mat2x4f64 {
+    fn sub_assign(&mut self, rhs: Mat2x4f64) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f64> for Mat2x4f64 {
+    type Output = Mat2x4f64;
+    fn mul(self, scalar: f64) -> Self::Output {
+        self.scaled(scalar)
+    }
+}
+
+impl Mul<Mat2x4f64> for f64 {
+    type Output = Mat2x4f64;
+    fn mul(self, m: Mat2x4f64) -> Self::Output {
+        m * self
+    }
+}
+
+impl MulAssign<f64> for Mat2x4f64 {
+    fn mul_assign(&mut self, scalar: f64) {
+        *self = *self * scalar;
+    }
+}
+
+impl Div<f64> for Mat2x4f64 {
+    type Output = Mat2x4f64;
+    fn div(self, scalar: f64) -> Self::Output {
+        self.divided(scalar)
+    }
+}
+
+impl DivAssign<f64> for Mat2x4f64 {
+    fn div_assign(&mut self, scalar: f64) {
+        *self = *self / scalar;
+    }
+}
+
+impl Mul<Mat4x2f64> for Mat2x4f64 {
+    type Output = Mat2f64;
+    fn mul(self, rhs: Mat4x2f64) -> Self::Output {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Mat2f64::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0] + a[0][3] * b[3][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1] + a[0][3] * b[3][1],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0] + a[1][3] * b[3][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1] + a[1][3] * b[3][1],
+            ],
+        ])
+    }
+}
+
+impl Mul<Mat4x3f64> for Mat2x4f64 {
+    type Output = Mat2x3f64;
+    fn mul(self, rhs: Mat4x3f64) -> Self::Output {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Mat2x3f64::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0] + a[0][3] * b[3][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1] + a[0][3] * b[3][1],
+                a[0][0] * b[0][2] + a[0][1] * b[1][2] + a[0][2] * b[2][2] + a[0][3] * b[3][2],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0] + a[1][3] * b[3][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1] + a[1][3] * b[3][1],
+                a[1][0] * b[0][2] + a[1][1] * b[1][2] + a[1][2] * b[2][2] + a[1][3] * b[3][2],
+            ],
+        ])
+    }
+}
+
+impl Mul<Mat4f64> for Mat2x4f64 {
+    type Output = Mat2x4f64;
+    fn mul(self, rhs: Mat4f64) -> Self::Output {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Mat2x4f64::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0] + a[0][3] * b[3][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1] + a[0][3] * b[3][1],
+                a[0][0] * b[0][2] + a[0][1] * b[1][2] + a[0][2] * b[2][2] + a[0][3] * b[3][2],
+                a[0][0] * b[0][3] + a[0][1] * b[1][3] + a[0][2] * b[2][3] + a[0][3] * b[3][3],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0] + a[1][3] * b[3][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1] + a[1][3] * b[3][1],
+                a[1][0] * b[0][2] + a[1][1] * b[1][2] + a[1][2] * b[2][2] + a[1][3] * b[3][2],
+                a[1][0] * b[0][3] + a[1][1] * b[1][3] + a[1][2] * b[2][3] + a[1][3] * b[3][3],
+            ],
+        ])
+    }
+}
+
+impl MulAssign<Mat4f64> for Mat2x4f64 {
+    fn mul_assign(&mut self, rhs: Mat4f64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Mat2x4f64> for Mat2f64 {
+    type Output = Mat2x4f64;
+    fn mul(self, rhs: Mat2x4f64) -> Self::Output {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Mat2x4f64::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1],
+                a[0][0] * b[0][2] + a[0][1] * b[1][2],
+                a[0][0] * b[0][3] + a[0][1] * b[1][3],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1],
+                a[1][0] * b[0][2] + a[1][1] * b[1][2],
+                a[1][0] * b[0][3] + a[1][1] * b[1][3],
+            ],
+        ])
+    }
+}
+
+impl Mul<Vec4f64> for Mat2x4f64 {
+    type Output = Vec2f64;
+    fn mul(self, v: Vec4f64) -> Self::Output {
+        self.product_vec(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mat::mat2f64::Mat2f64;
+    use crate::mat::mat4f64::Mat4f64;
+    use crate::mat::mat4x2f64::Mat4x2f64;
+    use crate::mat::mat4x3f64::Mat4x3f64;
+    use crate::vec::vec2f64::Vec2f64;
+    use crate::vec::vec4f64::Vec4f64;
+
+    use super::Mat2x4f64;
+
+    #[test]
+    fn matrix_creation() {
+        let zero = Mat2x4f64::zero();
+        let m = Mat2x4f64::new([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+        let c = Mat2x4f64::new_from_cols([[1.0, 5.0], [2.0, 6.0], [3.0, 7.0], [4.0, 8.0]]);
+        let r = Mat2x4f64::from_rows([
+            Vec4f64::new([1.0, 2.0, 3.0, 4.0]),
+            Vec4f64::new([5.0, 6.0, 7.0, 8.0]),
+        ]);
+
+        assert_eq!(zero[0][0], 0.0);
+        assert_eq!(zero[0][1], 0.0);
+        assert_eq!(zero[0][2], 0.0);
+        assert_eq!(zero[0][3], 0.0);
+        assert_eq!(zero[1][0], 0.0);
+        assert_eq!(zero[1][1], 0.0);
+        assert_eq!(zero[1][2], 0.0);
+        assert_eq!(zero[1][3], 0.0);
+
+        assert_eq!(m[0][0], 1.0);
+        assert_eq!(m[0][1], 2.0);
+        assert_eq!(m[0][2], 3.0);
+        assert_eq!(m[0][3], 4.0);
+        assert_eq!(m[1][0], 5.0);
+        assert_eq!(m[1][1], 6.0);
+        assert_eq!(m[1][2], 7.0);
+        assert_eq!(m[1][3], 8.0);
+
+        assert_eq!(c[0][0], 1.0);
+        assert_eq!(c[0][1], 2.0);
+        assert_eq!(c[0][2], 3.0);
+        assert_eq!(c[0][3], 4.0);
+        assert_eq!(c[1][0], 5.0);
+        assert_eq!(c[1][1], 6.0);
+        assert_eq!(c[1][2], 7.0);
+        assert_eq!(c[1][3], 8.0);
+
+        assert_eq!(r[0][0], 1.0);
+        assert_eq!(r[0][1], 2.0);
+        assert_eq!(r[0][2], 3.0);
+        assert_eq!(r[0][3], 4.0);
+        assert_eq!(r[1][0], 5.0);
+        assert_eq!(r[1][1], 6.0);
+        assert_eq!(r[1][2], 7.0);
+        assert_eq!(r[1][3], 8.0);
+    }
+
+    #[test]
+    fn transpose() {
+        let m = Mat2x4f64::new([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+        let t = m.transposed();
+        let cols = m.as_col_major();
+        let back = t.transposed();
+
+        assert_eq!(t[0][0], 1.0);
+        assert_eq!(t[0][1], 5.0);
+        assert_eq!(t[1][0], 2.0);
+        assert_eq!(t[1][1], 6.0);
+        assert_eq!(t[2][0], 3.0);
+        assert_eq!(t[2][1], 7.0);
+        assert_eq!(t[3][0], 4.0);
+        assert_eq!(t[3][1], 8.0);
+
+        assert_eq!(cols[0][0], 1.0);
+        assert_eq!(cols[0][1], 5.0);
+        assert_eq!(cols[1][0], 2.0);
+        assert_eq!(cols[1][1], 6.0);
+        assert_eq!(cols[2][0], 3.0);
+        assert_eq!(cols[2][1], 7.0);
+        assert_eq!(cols[3][0], 4.0);
+        assert_eq!(cols[3][1], 8.0);
+
+        assert_eq!(back[0][0], 1.0);
+        assert_eq!(back[0][1], 2.0);
+        assert_eq!(back[0][2], 3.0);
+        assert_eq!(back[0][3], 4.0);
+        assert_eq!(back[1][0], 5.0);
+        assert_eq!(back[1][1], 6.0);
+        assert_eq!(back[1][2], 7.0);
+        assert_eq!(back[1][3], 8.0);
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let m = Mat2x4f64::new([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+        let mut n = Mat2x4f64::zero();
+        n.set_row(0, m.row(1));
+        n.set_col(3, m.col(0));
+
+        assert_eq!(m.row(0)[0], 1.0);
+        assert_eq!(m.row(0)[1], 2.0);
+        assert_eq!(m.row(0)[2], 3.0);
+        assert_eq!(m.row(0)[3], 4.0);
+        assert_eq!(m.row(1)[0], 5.0);
+        assert_eq!(m.row(1)[1], 6.0);
+        assert_eq!(m.row(1)[2], 7.0);
+        assert_eq!(m.row(1)[3], 8.0);
+
+        assert_eq!(m.col(0)[0], 1.0);
+        assert_eq!(m.col(0)[1], 5.0);
+        assert_eq!(m.col(1)[0], 2.0);
+        assert_eq!(m.col(1)[1], 6.0);
+        assert_eq!(m.col(2)[0], 3.0);
+        assert_eq!(m.col(2)[1], 7.0);
+        assert_eq!(m.col(3)[0], 4.0);
+        assert_eq!(m.col(3)[1], 8.0);
+
+        assert_eq!(n[0][0], 5.0);
+        assert_eq!(n[0][1], 6.0);
+        assert_eq!(n[0][2], 7.0);
+        assert_eq!(n[0][3], 1.0);
+        assert_eq!(n[1][0], 0.0);
+        assert_eq!(n[1][1], 0.0);
+        assert_eq!(n[1][2], 0.0);
+        assert_eq!(n[1][3], 5.0);
+    }
+
+    #[test]
+    fn matrix_addition_and_subtraction() {
+        let m = Mat2x4f64::new([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+        let mut n = Mat2x4f64::new([[11.0, 12.0, 13.0, 14.0], [15.0, 16.0, 17.0, 18.0]]);
+        let r = n + m;
+        let s = n - m;
+
+        assert_eq!(r[0][0], 12.0);
+        assert_eq!(r[0][1], 14.0);
+        assert_eq!(r[0][2], 16.0);
+        assert_eq!(r[0][3], 18.0);
+        assert_eq!(r[1][0], 20.0);
+        assert_eq!(r[1][1], 22.0);
+        assert_eq!(r[1][2], 24.0);
+        assert_eq!(r[1][3], 26.0);
+
+        assert_eq!(s[0][0], 10.0);
+        assert_eq!(s[0][1], 10.0);
+        assert_eq!(s[0][2], 10.0);
+        assert_eq!(s[0][3], 10.0);
+        assert_eq!(s[1][0], 10.0);
+        assert_eq!(s[1][1], 10.0);
+        assert_eq!(s[1][2], 10.0);
+        assert_eq!(s[1][3], 10.0);
+
+        n += m;
+        n -= m;
+        n -= m;
+        assert_eq!(n[0][0], 10.0);
+        assert_eq!(n[0][1], 10.0);
+        assert_eq!(n[0][2], 10.0);
+        assert_eq!(n[0][3], 10.0);
+        assert_eq!(n[1][0], 10.0);
+        assert_eq!(n[1][1], 10.0);
+        assert_eq!(n[1][2], 10.0);
+        assert_eq!(n[1][3], 10.0);
+    }
+
+    #[test]
+    fn scalar_multiplication_and_division() {
+        let mut m = Mat2x4f64::new([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+        let n = m * 3.0;
+        let o = 3.0 * m;
+        let p = m / 2.0;
+
+        assert_eq!(n[0][0], 3.0);
+        assert_eq!(n[0][1], 6.0);
+        assert_eq!(n[0][2], 9.0);
+        assert_eq!(n[0][3], 12.0);
+        assert_eq!(n[1][0], 15.0);
+        assert_eq!(n[1][1], 18.0);
+        assert_eq!(n[1][2], 21.0);
+        assert_eq!(n[1][3], 24.0);
+
+        assert_eq!(o[0][0], 3.0);
+        assert_eq!(o[0][1], 6.0);
+        assert_eq!(o[0][2], 9.0);
+        assert_eq!(o[0][3], 12.0);
+        assert_eq!(o[1][0], 15.0);
+        assert_eq!(o[1][1], 18.0);
+        assert_eq!(o[1][2], 21.0);
+        assert_eq!(o[1][3], 24.0);
+
+        assert_eq!(p[0][0], 0.5);
+        assert_eq!(p[0][1], 1.0);
+        assert_eq!(p[0][2], 1.5);
+        assert_eq!(p[0][3], 2.0);
+        assert_eq!(p[1][0], 2.5);
+        assert_eq!(p[1][1], 3.0);
+        assert_eq!(p[1][2], 3.5);
+        assert_eq!(p[1][3], 4.0);
+
+        m *= 4.0;
+        m /= 2.0;
+        assert_eq!(m[0][0], 2.0);
+        assert_eq!(m[0][1], 4.0);
+        assert_eq!(m[0][2], 6.0);
+        assert_eq!(m[0][3], 8.0);
+        assert_eq!(m[1][0], 10.0);
+        assert_eq!(m[1][1], 12.0);
+        assert_eq!(m[1][2], 14.0);
+        assert_eq!(m[1][3], 16.0);
+    }
+
+    #[test]
+    fn matrix_multiplication() {
+        let m = Mat2x4f64::new([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+
+        let b = Mat4x2f64::new([[0.0, 1.0], [2.0, 3.0], [4.0, 0.0], [1.0, 2.0]]);
+        let r = m * b;
+        assert_eq!(r[0][0], 20.0);
+        assert_eq!(r[0][1], 15.0);
+        assert_eq!(r[1][0], 48.0);
+        assert_eq!(r[1][1], 39.0);
+
+        let b = Mat4x3f64::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 1.0],
+            [2.0, 3.0, 4.0],
+            [5.0, 1.0, 2.0],
+        ]);
+        let r = m * b;
+        assert_eq!(r[0][0], 35.0);
+        assert_eq!(r[0][1], 25.0);
+        assert_eq!(r[0][2], 25.0);
+        assert_eq!(r[1][0], 83.0);
+        assert_eq!(r[1][1], 69.0);
+        assert_eq!(r[1][2], 65.0);
+
+        let b = Mat4f64::new([
+            [2.0, 3.0, 4.0, 5.0],
+            [6.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0, 2.0],
+        ]);
+        let r = m * b;
+        assert_eq!(r[0][0], 45.0);
+        assert_eq!(r[0][1], 45.0);
+        assert_eq!(r[0][2], 40.0);
+        assert_eq!(r[0][3], 30.0);
+        assert_eq!(r[1][0], 113.0);
+        assert_eq!(r[1][1], 109.0);
+        assert_eq!(r[1][2], 100.0);
+        assert_eq!(r[1][3], 86.0);
+
+        let s = Mat2f64::new([[-1.0, 0.0], [1.0, 2.0]]);
+        let r = s * m;
+        assert_eq!(r[0][0], -1.0);
+        assert_eq!(r[0][1], -2.0);
+        assert_eq!(r[0][2], -3.0);
+        assert_eq!(r[0][3], -4.0);
+        assert_eq!(r[1][0], 11.0);
+        assert_eq!(r[1][1], 14.0);
+        assert_eq!(r[1][2], 17.0);
+        assert_eq!(r[1][3], 20.0);
+
+        let mut n = m;
+        n *= Mat4f64::identity();
+        assert_eq!(n[0][0], 1.0);
+        assert_eq!(n[0][1], 2.0);
+        assert_eq!(n[0][2], 3.0);
+        assert_eq!(n[0][3], 4.0);
+        assert_eq!(n[1][0], 5.0);
+        assert_eq!(n[1][1], 6.0);
+        assert_eq!(n[1][2], 7.0);
+        assert_eq!(n[1][3], 8.0);
+    }
+
+    #[test]
+    fn vector_multiplication() {
+        let m = Mat2x4f64::new([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+        let v = Vec4f64::new([2.0, 3.0, 4.0, 5.0]);
+        let w = m * v;
+
+        assert_eq!(w[0], 40.0);
+        assert_eq!(w[1], 96.0);
+    }
+
+    #[test]
+    fn const_evaluation() {
+        const M: Mat2x4f64 = Mat2x4f64::new([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+        const T: Mat4x2f64 = M.transposed();
+        const S: Mat2x4f64 = M.sum(M).difference(M).scaled(4.0).divided(2.0);
+        const W: Vec2f64 = M.product_vec(Vec4f64::new([2.0, 3.0, 4.0, 5.0]));
+
+        assert_eq!(T[0][0], 1.0);
+        assert_eq!(T[0][1], 5.0);
+        assert_eq!(T[1][0], 2.0);
+        assert_eq!(T[1][1], 6.0);
+        assert_eq!(T[2][0], 3.0);
+        assert_eq!(T[2][1], 7.0);
+        assert_eq!(T[3][0], 4.0);
+        assert_eq!(T[3][1], 8.0);
+
+        assert_eq!(S[0][0], 2.0);
+        assert_eq!(S[0][1], 4.0);
+        assert_eq!(S[0][2], 6.0);
+        assert_eq!(S[0][3], 8.0);
+        assert_eq!(S[1][0], 10.0);
+        assert_eq!(S[1][1], 12.0);
+        assert_eq!(S[1][2], 14.0);
+        assert_eq!(S[1][3], 16.0);
+
+        assert_eq!(W[0], 40.0);
+        assert_eq!(W[1], 96.0);
+    }
+
+    #[test]
+    fn flat_arrays() {
+        let m = Mat2x4f64::new([[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]]);
+        let rows = m.as_row_major_flat();
+        let cols = m.as_col_major_flat();
+        let from_rows = Mat2x4f64::from_row_major_flat([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+        let from_cols = Mat2x4f64::from_col_major_flat([1.0, 5.0, 2.0, 6.0, 3.0, 7.0, 4.0, 8.0]);
+
+        assert_eq!(rows[0], 1.0);
+        assert_eq!(rows[1], 2.0);
+        assert_eq!(rows[2], 3.0);
+        assert_eq!(rows[3], 4.0);
+        assert_eq!(rows[4], 5.0);
+        assert_eq!(rows[5], 6.0);
+        assert_eq!(rows[6], 7.0);
+        assert_eq!(rows[7], 8.0);
+
+        assert_eq!(cols[0], 1.0);
+        assert_eq!(cols[1], 5.0);
+        assert_eq!(cols[2], 2.0);
+        assert_eq!(cols[3], 6.0);
+        assert_eq!(cols[4], 3.0);
+        assert_eq!(cols[5], 7.0);
+        assert_eq!(cols[6], 4.0);
+        assert_eq!(cols[7], 8.0);
+
+        assert_eq!(from_rows[0][0], 1.0);
+        assert_eq!(from_rows[0][1], 2.0);
+        assert_eq!(from_rows[0][2], 3.0);
+        assert_eq!(from_rows[0][3], 4.0);
+        assert_eq!(from_rows[1][0], 5.0);
+        assert_eq!(from_rows[1][1], 6.0);
+        assert_eq!(from_rows[1][2], 7.0);
+        assert_eq!(from_rows[1][3], 8.0);
+
+        assert_eq!(from_cols[0][0], 1.0);
+        assert_eq!(from_cols[0][1], 2.0);
+        assert_eq!(from_cols[0][2], 3.0);
+        assert_eq!(from_cols[0][3], 4.0);
+        assert_eq!(from_cols[1][0], 5.0);
+        assert_eq!(from_cols[1][1], 6.0);
+        assert_eq!(from_cols[1][2], 7.0);
+        assert_eq!(from_cols[1][3], 8.0);
+    }
+
+    #[test]
+    fn precision_conversion() {
+        let m = Mat2x4f64::new([
+            [1.0, 2.0 / 3.0, 3.0, 4.0 / 3.0],
+            [5.0 / 3.0, 6.0, 7.0 / 3.0, 8.0],
+        ]);
+        let c = m.to_f32();
+
+        assert_eq!(c[0][0], m[0][0] as f32);
+        assert_eq!(c[0][1], m[0][1] as f32);
+        assert_eq!(c[0][2], m[0][2] as f32);
+        assert_eq!(c[0][3], m[0][3] as f32);
+        assert_eq!(c[1][0], m[1][0] as f32);
+        assert_eq!(c[1][1], m[1][1] as f32);
+        assert_eq!(c[1][2], m[1][2] as f32);
+        assert_eq!(c[1][3], m[1][3] as f32);
+    }
+
+    #[test]
+    fn rank() {
+        assert_eq!(
+            Mat2x4f64::new([[2.5, 0.5, 0.0, -0.5], [0.0, 2.5, 0.5, 0.0]]).rank(1e-12),
+            2
+        );
+        assert_eq!(
+            Mat2x4f64::new([[2.0, 1.0, -1.0, 0.5], [-4.0, -2.0, 2.0, -1.0]]).rank(1e-12),
+            1
+        );
+        assert_eq!(Mat2x4f64::zero().rank(1e-12), 0);
+    }
+
+    #[test]
+    fn null_space() {
+        let m = Mat2x4f64::new([[2.0, 1.0, -1.0, 0.5], [-4.0, -2.0, 2.0, -1.0]]);
+        let basis = m.null_space(1e-12);
+        assert_eq!(basis.len(), 3);
+        for (i, v) in basis.iter().enumerate() {
+            assert!((m * *v).mag() < 1e-12);
+            assert!((v.mag() - 1.0).abs() < 1e-12);
+            for w in &basis[..i] {
+                assert!(v.dot(*w).abs() < 1e-12);
+            }
+        }
+
+        let m = Mat2x4f64::new([[2.5, 0.5, 0.0, -0.5], [0.0, 2.5, 0.5, 0.0]]);
+        let basis = m.null_space(1e-12);
+        assert_eq!(basis.len(), 2);
+        for v in basis {
+            assert!((m * v).mag() < 1e-12);
+        }
+        assert_eq!(Mat2x4f64::zero().null_space(1e-12).len(), 4);
+    }
+
+    #[test]
+    fn condition_number() {
+        let m = Mat2x4f64::new([[1.0, 0.0, 0.0, 0.0], [0.0, 2.0, 0.0, 0.0]]);
+        assert!((m.condition_number() - 2.5).abs() < 1e-12);
+        assert!(
+            Mat2x4f64::new([[2.0, 1.0, -1.0, 0.5], [-4.0, -2.0, 2.0, -1.0]]).condition_number()
+                > 1e6
+        );
+        assert_eq!(Mat2x4f64::zero().condition_number(), f64::INFINITY);
+    }
+
+    #[test]
+    fn is_orthogonal() {
+        let m = Mat2x4f64::new([[0.6, 0.8, 0.0, 0.0], [-0.8, 0.6, 0.0, 0.0]]);
+        assert!(m.is_orthogonal(1e-12));
+        assert!(!(2.0 * m).is_orthogonal(1e-12));
+        assert!(
+            !Mat2x4f64::new([[2.5, 0.5, 0.0, -0.5], [0.0, 2.5, 0.5, 0.0]]).is_orthogonal(1e-12)
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
 
+use crate::mat::mat2f64::Mat2f64;
+use crate::mat::mat3f32::Mat3f32;
+use crate::vec::vec2f64::Vec2f64;
 use crate::vec::vec3f64::Vec3f64;
 
 /// A 3x3 floating point matrix.
@@ -14,25 +17,25 @@ pub struct Mat3f64 {
 impl Mat3f64 {
     /// Creates a new matrix with user defined elements.
     /// The user defined elements are in row major order.
-    pub fn new(rows: [[f64; 3]; 3]) -> Self {
+    pub const fn new(rows: [[f64; 3]; 3]) -> Self {
         Self { rows }
     }
 
     /// Creates a new matrix with user defined elements.
     /// The user defined elements are in column major order.
-    pub fn new_from_cols(cols: [[f64; 3]; 3]) -> Self {
+    pub const fn new_from_cols(cols: [[f64; 3]; 3]) -> Self {
         Self::new(cols).transposed()
     }
 
     /// Creates a new matrix with all elements equal to 0.0.
-    pub fn zero() -> Self {
+    pub const fn zero() -> Self {
         Self {
             rows: [[0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 0.0]],
         }
     }
 
     /// Creates a new identity matrix.
-    pub fn identity() -> Self {
+    pub const fn identity() -> Self {
         Self {
             rows: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
         }
@@ -40,31 +43,1031 @@ impl Mat3f64 {
 
     /// Returns self but transposed
     /// (Rows are now columns and columns are now rows).
-    pub fn transposed(&self) -> Self {
+    pub const fn transposed(&self) -> Self {
+        let m = &self.rows;
         Self {
             rows: [
-                [self[0][0], self[1][0], self[2][0]],
-                [self[0][1], self[1][1], self[2][1]],
-                [self[0][2], self[1][2], self[2][2]],
+                [m[0][0], m[1][0], m[2][0]],
+                [m[0][1], m[1][1], m[2][1]],
+                [m[0][2], m[1][2], m[2][2]],
             ],
         }
     }
 
     /// Transposes self
     /// (Rows are now columns and columns are now rows).
-    pub fn transpose(&mut self) {
+    pub const fn transpose(&mut self) {
         *self = self.transposed()
     }
 
     /// Returns the matrix rows as arrays in row major order.
-    pub fn as_row_major(&self) -> [[f64; 3]; 3] {
+    pub const fn as_row_major(&self) -> [[f64; 3]; 3] {
         self.rows
     }
 
     /// Returns the matrix columns as arrays in column major order.
-    pub fn as_col_major(&self) -> [[f64; 3]; 3] {
+    pub const fn as_col_major(&self) -> [[f64; 3]; 3] {
         self.transposed().rows
     }
+
+    /// Creates a new matrix from row vectors.
+    pub const fn from_rows(rows: [Vec3f64; 3]) -> Self {
+        Self::new([rows[0].coords, rows[1].coords, rows[2].coords])
+    }
+
+    /// Creates a new matrix from column vectors.
+    pub const fn from_cols(cols: [Vec3f64; 3]) -> Self {
+        Self::new_from_cols([cols[0].coords, cols[1].coords, cols[2].coords])
+    }
+
+    /// Creates a new diagonal matrix with the components of diagonal
+    /// on the main diagonal and all other elements equal to 0.0.
+    pub const fn from_diagonal(diagonal: Vec3f64) -> Self {
+        let d = &diagonal.coords;
+        Self::new([[d[0], 0.0, 0.0], [0.0, d[1], 0.0], [0.0, 0.0, d[2]]])
+    }
+
+    /// Creates the outer product of a and b (a * b^T).
+    /// Element [i][j] of the result is a[i] * b[j].
+    pub const fn outer_product(a: Vec3f64, b: Vec3f64) -> Self {
+        let a = &a.coords;
+        let b = &b.coords;
+        Self::new([
+            [a[0] * b[0], a[0] * b[1], a[0] * b[2]],
+            [a[1] * b[0], a[1] * b[1], a[1] * b[2]],
+            [a[2] * b[0], a[2] * b[1], a[2] * b[2]],
+        ])
+    }
+
+    /// Returns the row at index as a vector.
+    pub const fn row(&self, index: usize) -> Vec3f64 {
+        Vec3f64::new(self.rows[index])
+    }
+
+    /// Returns the column at index as a vector.
+    pub const fn col(&self, index: usize) -> Vec3f64 {
+        let m = &self.rows;
+        Vec3f64::new([m[0][index], m[1][index], m[2][index]])
+    }
+
+    /// Replaces the row at index with row.
+    pub const fn set_row(&mut self, index: usize, row: Vec3f64) {
+        self.rows[index] = row.coords;
+    }
+
+    /// Replaces the column at index with col.
+    pub const fn set_col(&mut self, index: usize, col: Vec3f64) {
+        self.rows[0][index] = col.coords[0];
+        self.rows[1][index] = col.coords[1];
+        self.rows[2][index] = col.coords[2];
+    }
+
+    /// Returns the elements on the main diagonal as a vector.
+    pub const fn diagonal(&self) -> Vec3f64 {
+        let m = &self.rows;
+        Vec3f64::new([m[0][0], m[1][1], m[2][2]])
+    }
+
+    /// Returns the trace of self (the sum of the main diagonal).
+    pub const fn trace(&self) -> f64 {
+        let m = &self.rows;
+        m[0][0] + m[1][1] + m[2][2]
+    }
+
+    /// The Frobenius norm of self
+    /// (the square root of the sum of all squared elements).
+    pub fn frobenius_norm(&self) -> f64 {
+        self.frobenius_norm_squared().sqrt()
+    }
+
+    /// The Frobenius norm of self, but squared.
+    /// This is faster to compute than frobenius_norm() and useful in some situations.
+    pub const fn frobenius_norm_squared(&self) -> f64 {
+        let m = &self.rows;
+        m[0][0] * m[0][0]
+            + m[0][1] * m[0][1]
+            + m[0][2] * m[0][2]
+            + m[1][0] * m[1][0]
+            + m[1][1] * m[1][1]
+            + m[1][2] * m[1][2]
+            + m[2][0] * m[2][0]
+            + m[2][1] * m[2][1]
+            + m[2][2] * m[2][2]
+    }
+
+    /// Creates a new 2D translation matrix for homogeneous coordinates.
+    pub const fn from_translation_2d(translation: Vec2f64) -> Self {
+        let t = &translation.coords;
+        Self::new([[1.0, 0.0, t[0]], [0.0, 1.0, t[1]], [0.0, 0.0, 1.0]])
+    }
+
+    /// Creates a new 2D rotation matrix for homogeneous coordinates.
+    /// Positive angles (in radians) rotate counterclockwise.
+    pub fn from_rotation_2d(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new([[cos, -sin, 0.0], [sin, cos, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Creates a new 2D scaling matrix for homogeneous coordinates.
+    pub const fn from_scale_2d(scale: Vec2f64) -> Self {
+        let s = &scale.coords;
+        Self::new([[s[0], 0.0, 0.0], [0.0, s[1], 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Creates a new 2D shear matrix for homogeneous coordinates.
+    /// The resulting x coordinate is x + x_by_y * y,
+    /// the resulting y coordinate is y + y_by_x * x.
+    pub const fn from_shear_2d(x_by_y: f64, y_by_x: f64) -> Self {
+        Self::new([[1.0, x_by_y, 0.0], [y_by_x, 1.0, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Creates a new matrix reflecting across a 2D line for homogeneous coordinates.
+    /// The line contains the points p with line[0] * p[0] + line[1] * p[1] + line[2] = 0,
+    /// its normal (the first two components) does not have to be normalized.
+    pub const fn from_reflection_2d(line: Vec3f64) -> Self {
+        let l = &line.coords;
+        let normal = Vec3f64::new([l[0], l[1], 0.0]);
+        let offset = Self::outer_product(normal, line).scaled(2.0 / normal.mag_squared());
+        Self::identity().difference(offset)
+    }
+
+    /// Returns self followed by a 2D translation (translation * self).
+    pub const fn translate_2d(&self, translation: Vec2f64) -> Self {
+        Self::from_translation_2d(translation).product(*self)
+    }
+
+    /// Returns self followed by a 2D rotation (rotation * self).
+    /// Positive angles (in radians) rotate counterclockwise.
+    pub fn rotate_2d(&self, angle: f64) -> Self {
+        Self::from_rotation_2d(angle) * *self
+    }
+
+    /// Returns self followed by a 2D scaling (scale * self).
+    pub const fn scale_2d(&self, scale: Vec2f64) -> Self {
+        Self::from_scale_2d(scale).product(*self)
+    }
+
+    /// Returns self followed by a 2D shear (shear * self).
+    pub const fn shear_2d(&self, x_by_y: f64, y_by_x: f64) -> Self {
+        Self::from_shear_2d(x_by_y, y_by_x).product(*self)
+    }
+
+    /// Transforms a 2D point (w = 1) by self and returns the x and y coordinates.
+    /// The resulting w coordinate is ignored, which is correct for affine
+    /// transformations. Use project_point2() for projective transformations.
+    pub const fn transform_point2(&self, point: Vec2f64) -> Vec2f64 {
+        let m = &self.rows;
+        let p = &point.coords;
+        Vec2f64::new([
+            m[0][0] * p[0] + m[0][1] * p[1] + m[0][2],
+            m[1][0] * p[0] + m[1][1] * p[1] + m[1][2],
+        ])
+    }
+
+    /// Transforms a 2D direction (w = 0) by self and returns the x and y coordinates.
+    /// Directions are not affected by the translation of self.
+    pub const fn transform_vector2(&self, vector: Vec2f64) -> Vec2f64 {
+        let m = &self.rows;
+        let v = &vector.coords;
+        Vec2f64::new([
+            m[0][0] * v[0] + m[0][1] * v[1],
+            m[1][0] * v[0] + m[1][1] * v[1],
+        ])
+    }
+
+    /// Transforms a 2D point (w = 1) by self and divides the result by its w coordinate
+    /// (perspective divide).
+    pub const fn project_point2(&self, point: Vec2f64) -> Vec2f64 {
+        let m = &self.rows;
+        let p = &point.coords;
+        let w = m[2][0] * p[0] + m[2][1] * p[1] + m[2][2];
+        self.transform_point2(point).divided(w)
+    }
+
+    /// Creates a new matrix from a flat array of elements in row major order.
+    pub const fn from_row_major_flat(elements: [f64; 9]) -> Self {
+        let e = &elements;
+        Self::new([[e[0], e[1], e[2]], [e[3], e[4], e[5]], [e[6], e[7], e[8]]])
+    }
+
+    /// Creates a new matrix from a flat array of elements in column major order.
+    pub const fn from_col_major_flat(elements: [f64; 9]) -> Self {
+        let e = &elements;
+        Self::new([[e[0], e[3], e[6]], [e[1], e[4], e[7]], [e[2], e[5], e[8]]])
+    }
+
+    /// Returns all elements as a flat array in row major order.
+    pub const fn as_row_major_flat(&self) -> [f64; 9] {
+        let m = &self.rows;
+        [
+            m[0][0], m[0][1], m[0][2], m[1][0], m[1][1], m[1][2], m[2][0], m[2][1], m[2][2],
+        ]
+    }
+
+    /// Returns all elements as a flat array in column major order.
+    /// This is the layout most graphics APIs expect for uniform uploads.
+    pub const fn as_col_major_flat(&self) -> [f64; 9] {
+        let m = &self.rows;
+        [
+            m[0][0], m[1][0], m[2][0], m[0][1], m[1][1], m[2][1], m[0][2], m[1][2], m[2][2],
+        ]
+    }
+
+    /// Returns self converted to single precision.
+    /// Elements are rounded to the nearest representable f32 value.
+    pub const fn to_f32(&self) -> Mat3f32 {
+        let m = &self.rows;
+        Mat3f32::new([
+            [m[0][0] as f32, m[0][1] as f32, m[0][2] as f32],
+            [m[1][0] as f32, m[1][1] as f32, m[1][2] as f32],
+            [m[2][0] as f32, m[2][1] as f32, m[2][2] as f32],
+        ])
+    }
+
+    /// Creates a new matrix with m in the upper left corner
+    /// and the remaining elements taken from the identity matrix.
+    pub const fn from_mat2(m: Mat2f64) -> Self {
+        let m = &m.rows;
+        Self::new([
+            [m[0][0], m[0][1], 0.0],
+            [m[1][0], m[1][1], 0.0],
+            [0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Returns the upper left 2x2 block of self.
+    pub const fn upper_left(&self) -> Mat2f64 {
+        let m = &self.rows;
+        Mat2f64::new([[m[0][0], m[0][1]], [m[1][0], m[1][1]]])
+    }
+
+    /// Computes the LU decomposition of self with partial pivoting,
+    /// so that P * self = L * U (see Mat3f64Lu).
+    /// Returns None if self is singular, meaning a pivot has an absolute value
+    /// less than or equal to tolerance.
+    pub fn lu(&self, tolerance: f64) -> Option<Mat3f64Lu> {
+        let mut lu = self.rows;
+        let mut permutation = [0, 1, 2];
+        let mut sign = 1.0;
+        for k in 0..3 {
+            // Use the row with the largest absolute value in column k as pivot
+            let mut pivot = k;
+            for i in k + 1..3 {
+                if lu[i][k].abs() > lu[pivot][k].abs() {
+                    pivot = i;
+                }
+            }
+            if lu[pivot][k].abs() <= tolerance {
+                return None;
+            }
+            if pivot != k {
+                lu.swap(k, pivot);
+                permutation.swap(k, pivot);
+                sign = -sign;
+            }
+            let pivot_row = lu[k];
+            for row in lu.iter_mut().skip(k + 1) {
+                let factor = row[k] / pivot_row[k];
+                row[k] = factor;
+                for j in k + 1..3 {
+                    row[j] -= factor * pivot_row[j];
+                }
+            }
+        }
+        Some(Mat3f64Lu {
+            lu,
+            permutation,
+            sign,
+        })
+    }
+
+    /// Solves the linear system self * x = b for x using an LU decomposition.
+    /// Returns None if self is singular (see lu()).
+    pub fn solve(&self, b: Vec3f64, tolerance: f64) -> Option<Vec3f64> {
+        Some(self.lu(tolerance)?.solve(b))
+    }
+
+    /// Computes the QR decomposition of self using Householder reflections
+    /// and returns (Q, R), where Q is orthogonal, R is upper triangular with
+    /// a non negative diagonal and self = Q * R.
+    pub fn qr(&self) -> (Self, Self) {
+        let mut q = Self::identity();
+        let mut r = *self;
+        for k in 0..2 {
+            // Householder vector reflecting column k below the diagonal onto the k axis
+            let mut v = [0.0; 3];
+            let mut norm = 0.0;
+            for i in k..3 {
+                v[i] = r[i][k];
+                norm += r[i][k] * r[i][k];
+            }
+            let norm = norm.sqrt();
+            v[k] += if v[k] < 0.0 { -norm } else { norm };
+            let v_mag_squared = Vec3f64::new(v).mag_squared();
+            if v_mag_squared == 0.0 {
+                continue;
+            }
+            // r = h * r and q = q * h with h = I - 2 * v * v^T / (v^T * v)
+            for j in 0..3 {
+                let dot = Vec3f64::new(v).dot(r.col(j));
+                let f = 2.0 * dot / v_mag_squared;
+                for i in k..3 {
+                    r[i][j] -= f * v[i];
+                }
+            }
+            for i in 0..3 {
+                let dot = Vec3f64::new(v).dot(q.row(i));
+                let f = 2.0 * dot / v_mag_squared;
+                for j in k..3 {
+                    q[i][j] -= f * v[j];
+                }
+            }
+        }
+        // Make the decomposition unique by flipping signs to get a non negative diagonal
+        for k in 0..3 {
+            if r[k][k] < 0.0 {
+                r.set_row(k, -1.0 * r.row(k));
+                q.set_col(k, -1.0 * q.col(k));
+            }
+        }
+        for i in 1..3 {
+            for j in 0..i {
+                r[i][j] = 0.0;
+            }
+        }
+        (q, r)
+    }
+
+    /// Returns self but with orthonormal columns,
+    /// computed with the Gram-Schmidt process starting at the first column.
+    /// Use this to remove the drift of rotation matrices accumulated over many
+    /// multiplications. The columns of self have to be linearly independent.
+    pub fn orthonormalized(&self) -> Self {
+        let mut cols = [self.col(0), self.col(1), self.col(2)];
+        Vec3f64::gram_schmidt(&mut cols);
+        Self::from_cols(cols)
+    }
+
+    /// Makes the columns of self orthonormal,
+    /// see orthonormalized().
+    pub fn orthonormalize(&mut self) {
+        *self = self.orthonormalized()
+    }
+
+    /// Computes the eigenvalues and eigenvectors of self, which has to be symmetric.
+    /// Returns the eigenvalues sorted in descending order and a rotation matrix
+    /// (orthonormal with a determinant of +1) whose columns are the corresponding
+    /// eigenvectors, so that self = eigenvectors * diag(eigenvalues) * eigenvectors^T.
+    /// Uses the cyclic Jacobi eigenvalue algorithm.
+    pub fn symmetric_eigen(&self) -> (Vec3f64, Self) {
+        let mut a = *self;
+        let mut v = Self::identity();
+        let tolerance = f64::EPSILON * f64::EPSILON * self.frobenius_norm_squared();
+        for _ in 0..32 {
+            let off_diagonal = a[0][1] * a[0][1] + a[0][2] * a[0][2] + a[1][2] * a[1][2];
+            if off_diagonal <= tolerance {
+                break;
+            }
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                if a[p][q] == 0.0 {
+                    continue;
+                }
+                // Jacobi rotation J that zeroes a[p][q], a = J^T * a * J and v = v * J
+                // only change rows and columns p and q
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let cos = 1.0 / (t * t + 1.0).sqrt();
+                let sin = t * cos;
+                for k in 0..3 {
+                    let (akp, akq) = (a[k][p], a[k][q]);
+                    a[k][p] = cos * akp - sin * akq;
+                    a[k][q] = sin * akp + cos * akq;
+                    let (vkp, vkq) = (v[k][p], v[k][q]);
+                    v[k][p] = cos * vkp - sin * vkq;
+                    v[k][q] = sin * vkp + cos * vkq;
+                }
+                for k in 0..3 {
+                    let (apk, aqk) = (a[p][k], a[q][k]);
+                    a[p][k] = cos * apk - sin * aqk;
+                    a[q][k] = sin * apk + cos * aqk;
+                }
+            }
+        }
+
+        // Sort the eigenpairs by descending eigenvalue
+        let mut pairs = [
+            (a[0][0], v.col(0)),
+            (a[1][1], v.col(1)),
+            (a[2][2], v.col(2)),
+        ];
+        pairs.sort_by(|x, y| y.0.total_cmp(&x.0));
+        let mut eigenvectors = Self::from_cols([pairs[0].1, pairs[1].1, pairs[2].1]);
+        if eigenvectors.determinant() < 0.0 {
+            eigenvectors.set_col(2, -1.0 * pairs[2].1);
+        }
+        (
+            Vec3f64::new([pairs[0].0, pairs[1].0, pairs[2].0]),
+            eigenvectors,
+        )
+    }
+
+    /// Computes the singular value decomposition of self and returns (U, sigma, V),
+    /// so that self = U * diag(sigma) * V^T.
+    /// U and V are rotation matrices (orthonormal with a determinant of +1).
+    /// The singular values are sorted by descending absolute value and all but the
+    /// last are non negative. The last one is negative if self has a negative
+    /// determinant (it contains a reflection).
+    /// Uses one-sided Jacobi rotations on the columns of self, which keeps small singular
+    /// values accurate, unlike going through the eigenvectors of self^T * self.
+    pub fn svd(&self) -> (Self, Vec3f64, Self) {
+        // Rotate the columns of a = self * V until they are orthogonal,
+        // then their lengths are the singular values and their directions U
+        let mut a = *self;
+        let mut v = Self::identity();
+        for _ in 0..32 {
+            let mut converged = true;
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                let (ap, aq) = (a.col(p), a.col(q));
+                let (alpha, beta, gamma) = (ap.mag_squared(), aq.mag_squared(), ap.dot(aq));
+                if gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+                    continue;
+                }
+                converged = false;
+                // Jacobi rotation J that makes the columns p and q orthogonal,
+                // the one that zeroes the element p, q of a^T * a
+                let theta = (beta - alpha) / (2.0 * gamma);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let cos = 1.0 / (t * t + 1.0).sqrt();
+                let sin = t * cos;
+                for k in 0..3 {
+                    let (akp, akq) = (a[k][p], a[k][q]);
+                    a[k][p] = cos * akp - sin * akq;
+                    a[k][q] = sin * akp + cos * akq;
+                    let (vkp, vkq) = (v[k][p], v[k][q]);
+                    v[k][p] = cos * vkp - sin * vkq;
+                    v[k][q] = sin * vkp + cos * vkq;
+                }
+            }
+            if converged {
+                break;
+            }
+        }
+
+        // Sort by descending singular value
+        let mut pairs = [
+            (a.col(0).mag(), a.col(0), v.col(0)),
+            (a.col(1).mag(), a.col(1), v.col(1)),
+            (a.col(2).mag(), a.col(2), v.col(2)),
+        ];
+        pairs.sort_by(|x, y| y.0.total_cmp(&x.0));
+        let mut sigma = Vec3f64::new([pairs[0].0, pairs[1].0, pairs[2].0]);
+        let mut v = Self::from_cols([pairs[0].2, pairs[1].2, pairs[2].2]);
+
+        // Columns of (nearly) zero length have no direction, complete U with the
+        // coordinate axis that is the least parallel to the previous columns
+        let threshold = f64::EPSILON * sigma[0];
+        let mut u = [Vec3f64::zero(); 3];
+        for k in 0..3 {
+            if sigma[k] > threshold {
+                u[k] = pairs[k].1 / sigma[k];
+                continue;
+            }
+            let mut best = Vec3f64::zero();
+            for axis in [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]] {
+                let mut w = Vec3f64::new(axis);
+                for previous in &u[..k] {
+                    w -= previous.dot(w) * *previous;
+                }
+                if w.mag_squared() > best.mag_squared() {
+                    best = w;
+                }
+            }
+            u[k] = best.normed();
+        }
+        let mut u = Self::from_cols(u);
+
+        // Negating the last column of both U and V keeps the product
+        if v.determinant() < 0.0 {
+            v.set_col(2, -1.0 * v.col(2));
+            u.set_col(2, -1.0 * u.col(2));
+        }
+        // A reflection in self is left in U, move it into the last singular value
+        if u.determinant() < 0.0 {
+            u.set_col(2, -1.0 * u.col(2));
+            sigma[2] = -sigma[2];
+        }
+        (u, sigma, v)
+    }
+
+    /// Returns the Moore-Penrose pseudo inverse of self, computed with svd().
+    /// Singular values with an absolute value less than or equal to tolerance
+    /// are treated as zero. For invertible matrices this equals the inverse.
+    pub fn pseudo_inverse(&self, tolerance: f64) -> Self {
+        let (u, sigma, v) = self.svd();
+        let mut inverted = Vec3f64::zero();
+        for i in 0..3 {
+            if sigma[i].abs() > tolerance {
+                inverted[i] = 1.0 / sigma[i];
+            }
+        }
+        v * Self::from_diagonal(inverted) * u.transposed()
+    }
+
+    /// Computes the polar decomposition of self and returns (rotation, stretch),
+    /// so that self = rotation * stretch.
+    /// The rotation is orthonormal with a determinant of +1 and the stretch is symmetric.
+    /// The stretch is positive semi definite if self has a non negative determinant,
+    /// otherwise it contains the reflection of self as one negative eigenvalue.
+    /// Well conditioned matrices without reflection use a fast Newton iteration,
+    /// all others fall back to svd().
+    pub fn polar_decompose(&self) -> (Self, Self) {
+        let rotation = self.polar_rotation_newton().unwrap_or_else(|| {
+            let (u, _, v) = self.svd();
+            u * v.transposed()
+        });
+        let stretch = rotation.transposed() * *self;
+        // Remove the asymmetry caused by rounding errors
+        let stretch = (stretch + stretch.transposed()) * 0.5;
+        (rotation, stretch)
+    }
+
+    /// Scaled Newton iteration converging to the rotation of the polar decomposition.
+    /// Returns None if self has no positive determinant or the iteration does not converge.
+    fn polar_rotation_newton(&self) -> Option<Self> {
+        if self.determinant() <= 0.0 {
+            return None;
+        }
+        let mut rotation = *self;
+        for _ in 0..16 {
+            let inverse_transposed = rotation.try_inverse(0.0)?.transposed();
+            let scale = (inverse_transposed.frobenius_norm() / rotation.frobenius_norm()).sqrt();
+            let next = (rotation * scale + inverse_transposed / scale) * 0.5;
+            let change = (next - rotation).frobenius_norm();
+            rotation = next;
+            if change <= 8.0 * f64::EPSILON {
+                return Some(rotation);
+            }
+        }
+        None
+    }
+
+    /// Computes the Cholesky decomposition of self, which has to be symmetric,
+    /// so that self = L * L^T (see Mat3f64Cholesky).
+    /// Returns None if self is not positive definite, meaning a diagonal element of the
+    /// decomposition would be the square root of a value less than or equal to tolerance.
+    pub fn cholesky(&self, tolerance: f64) -> Option<Mat3f64Cholesky> {
+        let mut l = Self::zero();
+        for j in 0..3 {
+            // Row j of l only contains the columns before j at this point
+            let d = self[j][j] - l.row(j).mag_squared();
+            if d <= tolerance {
+                return None;
+            }
+            l[j][j] = d.sqrt();
+            for i in j + 1..3 {
+                l[i][j] = (self[i][j] - l.row(i).dot(l.row(j))) / l[j][j];
+            }
+        }
+        Some(Mat3f64Cholesky { l })
+    }
+
+    /// Computes the LDL^T decomposition of self, which has to be symmetric,
+    /// so that self = L * D * L^T (see Mat3f64Ldlt).
+    /// Unlike cholesky() this also works for many indefinite matrices and needs no
+    /// square roots. Returns None if an element of D has an absolute value less than
+    /// or equal to tolerance.
+    pub fn ldlt(&self, tolerance: f64) -> Option<Mat3f64Ldlt> {
+        let mut l = Self::identity();
+        let mut d = Vec3f64::zero();
+        for j in 0..3 {
+            let mut dj = self[j][j];
+            for k in 0..j {
+                dj -= l[j][k] * l[j][k] * d[k];
+            }
+            if dj.abs() <= tolerance {
+                return None;
+            }
+            d[j] = dj;
+            for i in j + 1..3 {
+                let mut sum = self[i][j];
+                for k in 0..j {
+                    sum -= l[i][k] * l[j][k] * d[k];
+                }
+                l[i][j] = sum / dj;
+            }
+        }
+        Some(Mat3f64Ldlt { l, d })
+    }
+
+    /// Returns the inverse of self, which has to be symmetric,
+    /// computed with an LDL^T decomposition.
+    /// Returns None if the decomposition fails (see ldlt()).
+    pub fn symmetric_inverse(&self, tolerance: f64) -> Option<Self> {
+        Some(self.ldlt(tolerance)?.inverse())
+    }
+
+    /// Returns self raised to the integer power n, computed by repeated squaring.
+    /// Negative powers use the inverse of self, so the result is not finite
+    /// if self is singular.
+    pub fn powi(&self, n: i32) -> Self {
+        let mut base = if n < 0 { self.inverse() } else { *self };
+        let mut exponent = n.unsigned_abs();
+        let mut result = Self::identity();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// Returns self raised to the real power t, computed as exp(t * log(self)).
+    /// Returns None if self has no principal logarithm (see log()).
+    pub fn powf(&self, t: f64) -> Option<Self> {
+        Some((self.log()? * t).exp())
+    }
+
+    /// Returns the matrix exponential of self,
+    /// computed by scaling and squaring with a Taylor series.
+    pub fn exp(&self) -> Self {
+        // exp(A) = exp(A / 2^s)^(2^s), choose s so that the norm of A / 2^s is at most 0.5
+        let norm = self.frobenius_norm();
+        let squarings = if norm > 0.5 {
+            (norm.log2().ceil() as i32).min(f64::MAX_EXP) + 1
+        } else {
+            0
+        };
+        let scaled = *self * 0.5f64.powi(squarings);
+
+        let mut result = Self::identity();
+        let mut term = Self::identity();
+        for k in 1..32 {
+            term = term * scaled / k as f64;
+            result += term;
+            if term.frobenius_norm() <= f64::EPSILON * result.frobenius_norm() {
+                break;
+            }
+        }
+        for _ in 0..squarings {
+            result *= result;
+        }
+        result
+    }
+
+    /// Returns the principal logarithm of self, computed by inverse scaling and squaring.
+    /// Returns None if self has no principal logarithm, which is the case
+    /// if it has eigenvalues on the closed negative real axis.
+    pub fn log(&self) -> Option<Self> {
+        // log(A) = 2^k * log(A^(1 / 2^k)), take square roots until A is close to the identity
+        let mut a = *self;
+        let mut square_roots = 0;
+        while (a - Self::identity()).frobenius_norm() > 0.25 {
+            if square_roots == 64 {
+                return None;
+            }
+            a = a.sqrt()?;
+            square_roots += 1;
+        }
+
+        // log(I + X) = X - X^2 / 2 + X^3 / 3 - ...
+        let x = a - Self::identity();
+        let mut result = Self::zero();
+        let mut power = Self::identity();
+        for k in 1..64 {
+            power *= x;
+            let term = power / k as f64;
+            if k % 2 == 1 {
+                result += term;
+            } else {
+                result -= term;
+            }
+            if term.frobenius_norm() <= f64::EPSILON * result.frobenius_norm() {
+                break;
+            }
+        }
+        Some(result * 2.0f64.powi(square_roots))
+    }
+
+    /// Returns the principal square root of self. Symmetric matrices are handled
+    /// with symmetric_eigen(), other matrices with the Denman-Beavers iteration.
+    /// Returns None if self has negative real eigenvalues, or if it is singular and
+    /// not symmetric. Singular positive semidefinite matrices like diag(4, 0) are supported.
+    pub fn sqrt(&self) -> Option<Self> {
+        let tolerance = 8.0 * f64::EPSILON * self.frobenius_norm();
+        if self.is_symmetric(tolerance) {
+            let (values, vectors) = self.symmetric_eigen();
+            let mut roots = Vec3f64::zero();
+            for i in 0..3 {
+                if values[i] < -tolerance {
+                    return None;
+                }
+                // Eigenvalues within rounding error of zero are treated as zero
+                if values[i] > tolerance {
+                    roots[i] = values[i].sqrt();
+                }
+            }
+            return Some(vectors * Self::from_diagonal(roots) * vectors.transposed());
+        }
+
+        // y converges to sqrt(A) and z to its inverse
+        let mut y = *self;
+        let mut z = Self::identity();
+        for _ in 0..64 {
+            let y_inverse = y.try_inverse(0.0)?;
+            let z_inverse = z.try_inverse(0.0)?;
+            let next = (y + z_inverse) * 0.5;
+            z = (z + y_inverse) * 0.5;
+            let step = (next - y).frobenius_norm();
+            y = next;
+            if step <= 8.0 * f64::EPSILON * y.frobenius_norm() {
+                return Some(y);
+            }
+        }
+        None
+    }
+
+    /// Reduces self to reduced row echelon form with Gaussian elimination and partial pivoting.
+    /// Returns the reduced rows and which columns contain a pivot. Pivots with an absolute
+    /// value less than or equal to tolerance are treated as zero.
+    fn row_echelon(&self, tolerance: f64) -> ([[f64; 3]; 3], [bool; 3]) {
+        let mut a = self.rows;
+        let mut pivots = [false; 3];
+        let mut rank = 0;
+        for col in 0..3 {
+            if rank == 3 {
+                break;
+            }
+            let mut pivot = rank;
+            for (row, values) in a.iter().enumerate().skip(rank + 1) {
+                if values[col].abs() > a[pivot][col].abs() {
+                    pivot = row;
+                }
+            }
+            if a[pivot][col].abs() <= tolerance {
+                continue;
+            }
+            a.swap(rank, pivot);
+            let pivot_value = a[rank][col];
+            for x in a[rank].iter_mut() {
+                *x /= pivot_value;
+            }
+            let pivot_row = a[rank];
+            for (row, values) in a.iter_mut().enumerate() {
+                if row != rank {
+                    let factor = values[col];
+                    for (x, p) in values.iter_mut().zip(pivot_row) {
+                        *x -= factor * p;
+                    }
+                }
+            }
+            pivots[col] = true;
+            rank += 1;
+        }
+        (a, pivots)
+    }
+
+    /// Returns the rank of self, computed with Gaussian elimination and partial pivoting.
+    /// Pivots with an absolute value less than or equal to tolerance are treated as zero.
+    pub fn rank(&self, tolerance: f64) -> usize {
+        let (_, pivots) = self.row_echelon(tolerance);
+        pivots.iter().filter(|&&pivot| pivot).count()
+    }
+
+    /// Returns an orthonormal basis of the null space of self, the vectors v with self * v = 0.
+    /// The basis is empty if self has full column rank.
+    /// Pivots with an absolute value less than or equal to tolerance are treated as zero (see rank()).
+    pub fn null_space(&self, tolerance: f64) -> Vec<Vec3f64> {
+        let (a, pivots) = self.row_echelon(tolerance);
+        let mut basis = Vec::new();
+        for free in 0..3 {
+            if pivots[free] {
+                continue;
+            }
+            let mut v = Vec3f64::zero();
+            v[free] = 1.0;
+            // The reduced rows have their pivots in the pivot columns in order
+            let mut row = 0;
+            for col in 0..3 {
+                if pivots[col] {
+                    v[col] = -a[row][free];
+                    row += 1;
+                }
+            }
+            basis.push(v);
+        }
+        Vec3f64::gram_schmidt(&mut basis);
+        basis
+    }
+
+    /// Returns the condition number of self in the Frobenius norm,
+    /// ||self|| * ||self^-1||, which is at least 3 and grows as self
+    /// gets closer to singular. Returns infinity if self is singular.
+    pub fn condition_number(&self) -> f64 {
+        match self.try_inverse(0.0) {
+            Some(inverse) => self.frobenius_norm() * inverse.frobenius_norm(),
+            None => f64::INFINITY,
+        }
+    }
+
+    /// Returns true if the absolute value of the determinant of self is greater than tolerance,
+    /// meaning try_inverse() with the same tolerance succeeds.
+    pub fn is_invertible(&self, tolerance: f64) -> bool {
+        self.determinant().abs() > tolerance
+    }
+
+    /// Returns true if the columns of self are orthonormal, meaning self^T * self
+    /// differs from the identity by at most tolerance in every element.
+    pub fn is_orthogonal(&self, tolerance: f64) -> bool {
+        let gram = self.transposed() * *self;
+        let identity = Self::identity();
+        for i in 0..3 {
+            for j in 0..3 {
+                if (gram[i][j] - identity[i][j]).abs() > tolerance {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns true if self equals its transpose within tolerance in every element.
+    pub fn is_symmetric(&self, tolerance: f64) -> bool {
+        for i in 0..3 {
+            for j in i + 1..3 {
+                if (self[i][j] - self[j][i]).abs() > tolerance {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the inverse of self, which has to be a 2D affine transformation
+    /// with a last row of 0, 0, 1. Only the upper left 2x2 block is inverted,
+    /// which is cheaper than inverse().
+    pub fn inverse_affine_2d(&self) -> Self {
+        debug_assert!(
+            self.rows[2] == [0.0, 0.0, 1.0],
+            "inverse_affine_2d() requires a last row of 0, 0, 1"
+        );
+        let linear = self.upper_left().inverse();
+        let t = linear * Vec2f64::new([self[0][2], self[1][2]]);
+        let mut inverse = Self::from_mat2(linear);
+        inverse.set_col(2, Vec3f64::new([-t[0], -t[1], 1.0]));
+        inverse
+    }
+
+    /// Returns the inverse of self, which has to be a 2D rigid transformation:
+    /// an orthonormal upper left 2x2 block, a translation and a last row of 0, 0, 1.
+    /// The rotation is inverted by transposing it, which is cheaper than inverse_affine_2d().
+    pub fn inverse_rigid_2d(&self) -> Self {
+        debug_assert!(
+            self.rows[2] == [0.0, 0.0, 1.0],
+            "inverse_rigid_2d() requires a last row of 0, 0, 1"
+        );
+        debug_assert!(
+            self.upper_left().is_orthogonal(1e-10),
+            "inverse_rigid_2d() requires an orthonormal upper left 2x2 block"
+        );
+        let rotation = self.upper_left().transposed();
+        let t = rotation * Vec2f64::new([self[0][2], self[1][2]]);
+        let mut inverse = Self::from_mat2(rotation);
+        inverse.set_col(2, Vec3f64::new([-t[0], -t[1], 1.0]));
+        inverse
+    }
+
+    /// Returns the sum of self and rhs.
+    /// Same as the + operator, but usable in const contexts.
+    pub const fn sum(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [a[0][0] + b[0][0], a[0][1] + b[0][1], a[0][2] + b[0][2]],
+            [a[1][0] + b[1][0], a[1][1] + b[1][1], a[1][2] + b[1][2]],
+            [a[2][0] + b[2][0], a[2][1] + b[2][1], a[2][2] + b[2][2]],
+        ])
+    }
+
+    /// Returns the difference of self and rhs.
+    /// Same as the - operator, but usable in const contexts.
+    pub const fn difference(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [a[0][0] - b[0][0], a[0][1] - b[0][1], a[0][2] - b[0][2]],
+            [a[1][0] - b[1][0], a[1][1] - b[1][1], a[1][2] - b[1][2]],
+            [a[2][0] - b[2][0], a[2][1] - b[2][1], a[2][2] - b[2][2]],
+        ])
+    }
+
+    /// Returns self with every element multiplied by scalar.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn scaled(&self, scalar: f64) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [a[0][0] * scalar, a[0][1] * scalar, a[0][2] * scalar],
+            [a[1][0] * scalar, a[1][1] * scalar, a[1][2] * scalar],
+            [a[2][0] * scalar, a[2][1] * scalar, a[2][2] * scalar],
+        ])
+    }
+
+    /// Returns self with every element divided by scalar.
+    /// Same as the / operator, but usable in const contexts.
+    pub const fn divided(&self, scalar: f64) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [a[0][0] / scalar, a[0][1] / scalar, a[0][2] / scalar],
+            [a[1][0] / scalar, a[1][1] / scalar, a[1][2] / scalar],
+            [a[2][0] / scalar, a[2][1] / scalar, a[2][2] / scalar],
+        ])
+    }
+
+    /// Returns the matrix product of self and rhs.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1],
+                a[0][0] * b[0][2] + a[0][1] * b[1][2] + a[0][2] * b[2][2],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1],
+                a[1][0] * b[0][2] + a[1][1] * b[1][2] + a[1][2] * b[2][2],
+            ],
+            [
+                a[2][0] * b[0][0] + a[2][1] * b[1][0] + a[2][2] * b[2][0],
+                a[2][0] * b[0][1] + a[2][1] * b[1][1] + a[2][2] * b[2][1],
+                a[2][0] * b[0][2] + a[2][1] * b[1][2] + a[2][2] * b[2][2],
+            ],
+        ])
+    }
+
+    /// Returns the product of self and the column vector v.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product_vec(&self, v: Vec3f64) -> Vec3f64 {
+        let a = &self.rows;
+        let b = &v.coords;
+        Vec3f64::new([
+            a[0][0] * b[0] + a[0][1] * b[1] + a[0][2] * b[2],
+            a[1][0] * b[0] + a[1][1] * b[1] + a[1][2] * b[2],
+            a[2][0] * b[0] + a[2][1] * b[1] + a[2][2] * b[2],
+        ])
+    }
+
+    /// Returns the determinant of self.
+    pub const fn determinant(&self) -> f64 {
+        let a = &self.rows;
+        a[0][0] * (a[1][1] * a[2][2] - a[1][2] * a[2][1])
+            + a[0][1] * (a[1][2] * a[2][0] - a[1][0] * a[2][2])
+            + a[0][2] * (a[1][0] * a[2][1] - a[1][1] * a[2][0])
+    }
+
+    /// Returns the adjugate of self (the transpose of the cofactor matrix).
+    /// Unlike the inverse, the adjugate exists for singular matrices too.
+    pub const fn adjugate(&self) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [
+                a[1][1] * a[2][2] - a[1][2] * a[2][1],
+                a[0][2] * a[2][1] - a[0][1] * a[2][2],
+                a[0][1] * a[1][2] - a[0][2] * a[1][1],
+            ],
+            [
+                a[1][2] * a[2][0] - a[1][0] * a[2][2],
+                a[0][0] * a[2][2] - a[0][2] * a[2][0],
+                a[0][2] * a[1][0] - a[0][0] * a[1][2],
+            ],
+            [
+                a[1][0] * a[2][1] - a[1][1] * a[2][0],
+                a[0][1] * a[2][0] - a[0][0] * a[2][1],
+                a[0][0] * a[1][1] - a[0][1] * a[1][0],
+            ],
+        ])
+    }
+
+    /// Returns the inverse of self.
+    /// Singular matrices produce non finite elements,
+    /// use try_inverse() if self might not be invertible.
+    pub const fn inverse(&self) -> Self {
+        self.adjugate().divided(self.determinant())
+    }
+
+    /// Returns the inverse of self, or None if self is singular.
+    /// Self counts as singular if the absolute value of its determinant
+    /// is less than or equal to tolerance.
+    pub const fn try_inverse(&self, tolerance: f64) -> Option<Self> {
+        let det = self.determinant();
+        if det.abs() <= tolerance {
+            return None;
+        }
+        Some(self.adjugate().divided(det))
+    }
 }
 
 impl Index<usize> for Mat3f64 {
@@ -82,17 +1085,8 @@ impl IndexMut<usize> for Mat3f64 {
 
 impl Add<Mat3f64> for Mat3f64 {
     type Output = Mat3f64;
-    fn add(mut self, rhs: Mat3f64) -> Self::Output {
-        self[0][0] += rhs[0][0];
-        self[0][1] += rhs[0][1];
-        self[0][2] += rhs[0][2];
-        self[1][0] += rhs[1][0];
-        self[1][1] += rhs[1][1];
-        self[1][2] += rhs[1][2];
-        self[2][0] += rhs[2][0];
-        self[2][1] += rhs[2][1];
-        self[2][2] += rhs[2][2];
-        self
+    fn add(self, rhs: Mat3f64) -> Self::Output {
+        self.sum(rhs)
     }
 }
 
@@ -104,17 +1098,8 @@ impl AddAssign<Mat3f64> for Mat3f64 {
 
 impl Sub<Mat3f64> for Mat3f64 {
     type Output = Mat3f64;
-    fn sub(mut self, rhs: Mat3f64) -> Self::Output {
-        self[0][0] -= rhs[0][0];
-        self[0][1] -= rhs[0][1];
-        self[0][2] -= rhs[0][2];
-        self[1][0] -= rhs[1][0];
-        self[1][1] -= rhs[1][1];
-        self[1][2] -= rhs[1][2];
-        self[2][0] -= rhs[2][0];
-        self[2][1] -= rhs[2][1];
-        self[2][2] -= rhs[2][2];
-        self
+    fn sub(self, rhs: Mat3f64) -> Self::Output {
+        self.difference(rhs)
     }
 }
 
@@ -126,17 +1111,8 @@ impl SubAssign<Mat3f64> for Mat3f64 {
 
 impl Mul<f64> for Mat3f64 {
     type Output = Mat3f64;
-    fn mul(mut self, scalar: f64) -> Self::Output {
-        self[0][0] *= scalar;
-        self[0][1] *= scalar;
-        self[0][2] *= scalar;
-        self[1][0] *= scalar;
-        self[1][1] *= scalar;
-        self[1][2] *= scalar;
-        self[2][0] *= scalar;
-        self[2][1] *= scalar;
-        self[2][2] *= scalar;
-        self
+    fn mul(self, scalar: f64) -> Self::Output {
+        self.scaled(scalar)
     }
 }
 
@@ -155,17 +1131,8 @@ impl MulAssign<f64> for Mat3f64 {
 
 impl Div<f64> for Mat3f64 {
     type Output = Mat3f64;
-    fn div(mut self, scalar: f64) -> Self::Output {
-        self[0][0] /= scalar;
-        self[0][1] /= scalar;
-        self[0][2] /= scalar;
-        self[1][0] /= scalar;
-        self[1][1] /= scalar;
-        self[1][2] /= scalar;
-        self[2][0] /= scalar;
-        self[2][1] /= scalar;
-        self[2][2] /= scalar;
-        self
+    fn div(self, scalar: f64) -> Self::Output {
+        self.divided(scalar)
     }
 }
 
@@ -177,25 +1144,8 @@ impl DivAssign<f64> for Mat3f64 {
 
 impl Mul<Mat3f64> for Mat3f64 {
     type Output = Mat3f64;
-    fn mul(self, b: Mat3f64) -> Self::Output {
-        let a = self;
-        Self::new([
-            [
-                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0],
-                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1],
-                a[0][0] * b[0][2] + a[0][1] * b[1][2] + a[0][2] * b[2][2],
-            ],
-            [
-                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0],
-                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1],
-                a[1][0] * b[0][2] + a[1][1] * b[1][2] + a[1][2] * b[2][2],
-            ],
-            [
-                a[2][0] * b[0][0] + a[2][1] * b[1][0] + a[2][2] * b[2][0],
-                a[2][0] * b[0][1] + a[2][1] * b[1][1] + a[2][2] * b[2][1],
-                a[2][0] * b[0][2] + a[2][1] * b[1][2] + a[2][2] * b[2][2],
-            ],
-        ])
+    fn mul(self, rhs: Mat3f64) -> Self::Output {
+        self.product(rhs)
     }
 }
 
@@ -208,16 +1158,218 @@ impl MulAssign<Mat3f64> for Mat3f64 {
 impl Mul<Vec3f64> for Mat3f64 {
     type Output = Vec3f64;
     fn mul(self, v: Vec3f64) -> Self::Output {
-        Vec3f64::new([
-            self[0][0] * v[0] + self[0][1] * v[1] + self[0][2] * v[2],
-            self[1][0] * v[0] + self[1][1] * v[1] + self[1][2] * v[2],
-            self[2][0] * v[0] + self[2][1] * v[1] + self[2][2] * v[2],
+        self.product_vec(v)
+    }
+}
+
+/// The LU decomposition of a Mat3f64 with partial pivoting, see Mat3f64::lu().
+/// P * A = L * U, where P is a permutation matrix, L is lower triangular with ones
+/// on the diagonal and U is upper triangular.
+#[derive(Debug, Clone, Copy)]
+pub struct Mat3f64Lu {
+    /// L below the diagonal and U on and above the diagonal.
+    lu: [[f64; 3]; 3],
+    /// Row i of P * A is row permutation[i] of A.
+    permutation: [usize; 3],
+    /// The determinant of P (1.0 or -1.0).
+    sign: f64,
+}
+
+impl Mat3f64Lu {
+    /// Returns the lower triangular matrix L.
+    pub fn l(&self) -> Mat3f64 {
+        let mut l = Mat3f64::identity();
+        for i in 0..3 {
+            for j in 0..i {
+                l[i][j] = self.lu[i][j];
+            }
+        }
+        l
+    }
+
+    /// Returns the upper triangular matrix U.
+    pub fn u(&self) -> Mat3f64 {
+        let mut u = Mat3f64::zero();
+        for i in 0..3 {
+            for j in i..3 {
+                u[i][j] = self.lu[i][j];
+            }
+        }
+        u
+    }
+
+    /// Returns the permutation matrix P.
+    pub fn p(&self) -> Mat3f64 {
+        let mut p = Mat3f64::zero();
+        for i in 0..3 {
+            p[i][self.permutation[i]] = 1.0;
+        }
+        p
+    }
+
+    /// Returns the determinant of the decomposed matrix.
+    pub fn determinant(&self) -> f64 {
+        let mut det = self.sign;
+        for i in 0..3 {
+            det *= self.lu[i][i];
+        }
+        det
+    }
+
+    /// Solves the linear system A * x = b for x, where A is the decomposed matrix.
+    pub fn solve(&self, b: Vec3f64) -> Vec3f64 {
+        let lu = &self.lu;
+        let mut x = [0.0; 3];
+        // Forward substitution: L * y = P * b
+        for i in 0..3 {
+            let mut sum = b[self.permutation[i]];
+            for j in 0..i {
+                sum -= lu[i][j] * x[j];
+            }
+            x[i] = sum;
+        }
+        // Back substitution: U * x = y
+        for i in (0..3).rev() {
+            let mut sum = x[i];
+            for j in i + 1..3 {
+                sum -= lu[i][j] * x[j];
+            }
+            x[i] = sum / lu[i][i];
+        }
+        Vec3f64::new(x)
+    }
+}
+
+/// The Cholesky decomposition of a symmetric positive definite Mat3f64,
+/// see Mat3f64::cholesky(). A = L * L^T, where L is lower triangular
+/// with a positive diagonal.
+#[derive(Debug, Clone, Copy)]
+pub struct Mat3f64Cholesky {
+    l: Mat3f64,
+}
+
+impl Mat3f64Cholesky {
+    /// Returns the lower triangular matrix L.
+    pub fn l(&self) -> Mat3f64 {
+        self.l
+    }
+
+    /// Returns the determinant of the decomposed matrix.
+    pub fn determinant(&self) -> f64 {
+        let d = self.l.diagonal();
+        let mut det = 1.0;
+        for i in 0..3 {
+            det *= d[i] * d[i];
+        }
+        det
+    }
+
+    /// Solves the linear system A * x = b for x, where A is the decomposed matrix.
+    pub fn solve(&self, b: Vec3f64) -> Vec3f64 {
+        let l = &self.l;
+        let mut x = [0.0; 3];
+        // Forward substitution: L * y = b
+        for i in 0..3 {
+            let mut sum = b[i];
+            for j in 0..i {
+                sum -= l[i][j] * x[j];
+            }
+            x[i] = sum / l[i][i];
+        }
+        // Back substitution: L^T * x = y
+        for i in (0..3).rev() {
+            let mut sum = x[i];
+            for j in i + 1..3 {
+                sum -= l[j][i] * x[j];
+            }
+            x[i] = sum / l[i][i];
+        }
+        Vec3f64::new(x)
+    }
+
+    /// Returns the inverse of the decomposed matrix.
+    pub fn inverse(&self) -> Mat3f64 {
+        Mat3f64::from_cols([
+            self.solve(Vec3f64::new([1.0, 0.0, 0.0])),
+            self.solve(Vec3f64::new([0.0, 1.0, 0.0])),
+            self.solve(Vec3f64::new([0.0, 0.0, 1.0])),
+        ])
+    }
+}
+
+/// The LDL^T decomposition of a symmetric Mat3f64, see Mat3f64::ldlt().
+/// A = L * D * L^T, where L is lower triangular with ones on the diagonal
+/// and D is diagonal.
+#[derive(Debug, Clone, Copy)]
+pub struct Mat3f64Ldlt {
+    l: Mat3f64,
+    d: Vec3f64,
+}
+
+impl Mat3f64Ldlt {
+    /// Returns the lower triangular matrix L.
+    pub fn l(&self) -> Mat3f64 {
+        self.l
+    }
+
+    /// Returns the diagonal of D.
+    pub fn d(&self) -> Vec3f64 {
+        self.d
+    }
+
+    /// Returns the determinant of the decomposed matrix.
+    pub fn determinant(&self) -> f64 {
+        let mut det = 1.0;
+        for i in 0..3 {
+            det *= self.d[i];
+        }
+        det
+    }
+
+    /// Solves the linear system A * x = b for x, where A is the decomposed matrix.
+    pub fn solve(&self, b: Vec3f64) -> Vec3f64 {
+        let l = &self.l;
+        let mut x = [0.0; 3];
+        // Forward substitution: L * z = b
+        for i in 0..3 {
+            let mut sum = b[i];
+            for j in 0..i {
+                sum -= l[i][j] * x[j];
+            }
+            x[i] = sum;
+        }
+        // Diagonal: D * y = z
+        for (xi, di) in x.iter_mut().zip(self.d.coords) {
+            *xi /= di;
+        }
+        // Back substitution: L^T * x = y
+        for i in (0..3).rev() {
+            let mut sum = x[i];
+            for j in i + 1..3 {
+                sum -= l[j][i] * x[j];
+            }
+            x[i] = sum;
+        }
+        Vec3f64::new(x)
+    }
+
+    /// Returns the inverse of the decomposed matrix.
+    pub fn inverse(&self) -> Mat3f64 {
+        Mat3f64::from_cols([
+            self.solve(Vec3f64::new([1.0, 0.0, 0.0])),
+            self.solve(Vec3f64::new([0.0, 1.0, 0.0])),
+            self.solve(Vec3f64::new([0.0, 0.0, 1.0])),
         ])
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::mat::mat2f64::Mat2f64;
+    use crate::mat::testing::{
+        assert_eigen, assert_mat_close, assert_orthonormal, assert_polar, assert_svd,
+    };
+    use crate::vec::vec2f64::Vec2f64;
     use crate::vec::vec3f64::Vec3f64;
 
     use super::Mat3f64;
@@ -481,4 +1633,1057 @@ mod tests {
         assert_eq!(w[1], 47.0);
         assert_eq!(w[2], 74.0);
     }
+
+    #[test]
+    fn const_evaluation() {
+        const M: Mat3f64 = Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        const T: Mat3f64 = M.transposed();
+        const P: Mat3f64 = M.product(M);
+        const S: Mat3f64 = M.sum(Mat3f64::identity()).difference(Mat3f64::identity());
+        const H: Mat3f64 = M.scaled(4.0).divided(2.0);
+        const W: Vec3f64 = M.product_vec(Vec3f64::new([2.0, 3.0, 4.0]));
+        const Z: Mat3f64 = Mat3f64::zero();
+
+        assert_eq!(T[0][0], 1.0);
+        assert_eq!(T[0][1], 4.0);
+        assert_eq!(T[0][2], 7.0);
+        assert_eq!(T[1][0], 2.0);
+        assert_eq!(T[1][1], 5.0);
+        assert_eq!(T[1][2], 8.0);
+        assert_eq!(T[2][0], 3.0);
+        assert_eq!(T[2][1], 6.0);
+        assert_eq!(T[2][2], 9.0);
+
+        assert_eq!(P[0][0], 30.0);
+        assert_eq!(P[0][1], 36.0);
+        assert_eq!(P[0][2], 42.0);
+        assert_eq!(P[1][0], 66.0);
+        assert_eq!(P[1][1], 81.0);
+        assert_eq!(P[1][2], 96.0);
+        assert_eq!(P[2][0], 102.0);
+        assert_eq!(P[2][1], 126.0);
+        assert_eq!(P[2][2], 150.0);
+
+        assert_eq!(S[0][0], 1.0);
+        assert_eq!(S[0][1], 2.0);
+        assert_eq!(S[0][2], 3.0);
+        assert_eq!(S[1][0], 4.0);
+        assert_eq!(S[1][1], 5.0);
+        assert_eq!(S[1][2], 6.0);
+        assert_eq!(S[2][0], 7.0);
+        assert_eq!(S[2][1], 8.0);
+        assert_eq!(S[2][2], 9.0);
+
+        assert_eq!(H[0][0], 2.0);
+        assert_eq!(H[0][1], 4.0);
+        assert_eq!(H[0][2], 6.0);
+        assert_eq!(H[1][0], 8.0);
+        assert_eq!(H[1][1], 10.0);
+        assert_eq!(H[1][2], 12.0);
+        assert_eq!(H[2][0], 14.0);
+        assert_eq!(H[2][1], 16.0);
+        assert_eq!(H[2][2], 18.0);
+
+        assert_eq!(W[0], 20.0);
+        assert_eq!(W[1], 47.0);
+        assert_eq!(W[2], 74.0);
+
+        assert_eq!(Z[0][0], 0.0);
+        assert_eq!(Z[0][1], 0.0);
+        assert_eq!(Z[0][2], 0.0);
+        assert_eq!(Z[1][0], 0.0);
+        assert_eq!(Z[1][1], 0.0);
+        assert_eq!(Z[1][2], 0.0);
+        assert_eq!(Z[2][0], 0.0);
+        assert_eq!(Z[2][1], 0.0);
+        assert_eq!(Z[2][2], 0.0);
+    }
+
+    #[test]
+    fn determinant_and_adjugate() {
+        let m = Mat3f64::new([[1.0, 2.0, 3.0], [0.0, 1.0, 4.0], [5.0, 6.0, 0.0]]);
+        let adj = m.adjugate();
+
+        assert_eq!(m.determinant(), 1.0);
+        assert_eq!(Mat3f64::identity().determinant(), 1.0);
+        assert_eq!(
+            Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]).determinant(),
+            0.0
+        );
+
+        assert_eq!(adj[0][0], -24.0);
+        assert_eq!(adj[0][1], 18.0);
+        assert_eq!(adj[0][2], 5.0);
+        assert_eq!(adj[1][0], 20.0);
+        assert_eq!(adj[1][1], -15.0);
+        assert_eq!(adj[1][2], -4.0);
+        assert_eq!(adj[2][0], -5.0);
+        assert_eq!(adj[2][1], 4.0);
+        assert_eq!(adj[2][2], 1.0);
+    }
+
+    #[test]
+    fn inverse() {
+        let m = Mat3f64::new([[2.0, 0.0, 0.0], [0.0, 4.0, 0.0], [1.0, 0.0, 8.0]]);
+        let inv = m.inverse();
+        let expected = [[0.5, 0.0, 0.0], [0.0, 0.25, 0.0], [-0.0625, 0.0, 0.125]];
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((inv[i][j] - expected[i][j]).abs() < 1e-12);
+            }
+        }
+
+        let m = Mat3f64::new([[1.0, 2.0, 3.0], [0.0, 1.0, 4.0], [5.0, 6.0, 0.0]]);
+        let id = m * m.try_inverse(1e-12).unwrap();
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((id[i][j] - expected).abs() < 1e-12);
+            }
+        }
+
+        // Singular matrices
+        let singular = Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        assert!(singular.try_inverse(1e-12).is_none());
+        assert!(Mat3f64::zero().try_inverse(0.0).is_none());
+
+        // Configurable tolerance
+        let almost_singular = Mat3f64::new([[0.1, 0.0, 0.0], [0.0, 0.1, 0.0], [0.0, 0.0, 0.1]]);
+        assert!(almost_singular.try_inverse(0.01).is_none());
+        assert!(almost_singular.try_inverse(0.0001).is_some());
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let m = Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        let from_rows = Mat3f64::from_rows([
+            Vec3f64::new([1.0, 2.0, 3.0]),
+            Vec3f64::new([4.0, 5.0, 6.0]),
+            Vec3f64::new([7.0, 8.0, 9.0]),
+        ]);
+        let from_cols = Mat3f64::from_cols([
+            Vec3f64::new([1.0, 2.0, 3.0]),
+            Vec3f64::new([4.0, 5.0, 6.0]),
+            Vec3f64::new([7.0, 8.0, 9.0]),
+        ]);
+
+        assert_eq!(from_rows[0][0], 1.0);
+        assert_eq!(from_rows[0][1], 2.0);
+        assert_eq!(from_rows[0][2], 3.0);
+        assert_eq!(from_rows[1][0], 4.0);
+        assert_eq!(from_rows[1][1], 5.0);
+        assert_eq!(from_rows[1][2], 6.0);
+        assert_eq!(from_rows[2][0], 7.0);
+        assert_eq!(from_rows[2][1], 8.0);
+        assert_eq!(from_rows[2][2], 9.0);
+
+        assert_eq!(from_cols[0][0], 1.0);
+        assert_eq!(from_cols[0][1], 4.0);
+        assert_eq!(from_cols[0][2], 7.0);
+        assert_eq!(from_cols[1][0], 2.0);
+        assert_eq!(from_cols[1][1], 5.0);
+        assert_eq!(from_cols[1][2], 8.0);
+        assert_eq!(from_cols[2][0], 3.0);
+        assert_eq!(from_cols[2][1], 6.0);
+        assert_eq!(from_cols[2][2], 9.0);
+
+        assert_eq!(m.row(0)[0], 1.0);
+        assert_eq!(m.row(0)[1], 2.0);
+        assert_eq!(m.row(0)[2], 3.0);
+        assert_eq!(m.row(1)[0], 4.0);
+        assert_eq!(m.row(1)[1], 5.0);
+        assert_eq!(m.row(1)[2], 6.0);
+        assert_eq!(m.row(2)[0], 7.0);
+        assert_eq!(m.row(2)[1], 8.0);
+        assert_eq!(m.row(2)[2], 9.0);
+
+        assert_eq!(m.col(0)[0], 1.0);
+        assert_eq!(m.col(0)[1], 4.0);
+        assert_eq!(m.col(0)[2], 7.0);
+        assert_eq!(m.col(1)[0], 2.0);
+        assert_eq!(m.col(1)[1], 5.0);
+        assert_eq!(m.col(1)[2], 8.0);
+        assert_eq!(m.col(2)[0], 3.0);
+        assert_eq!(m.col(2)[1], 6.0);
+        assert_eq!(m.col(2)[2], 9.0);
+
+        let mut n = Mat3f64::zero();
+        n.set_row(1, Vec3f64::ones());
+        n.set_col(0, Vec3f64::new([-1.0, -2.0, -3.0]));
+        assert_eq!(n[0][0], -1.0);
+        assert_eq!(n[0][1], 0.0);
+        assert_eq!(n[0][2], 0.0);
+        assert_eq!(n[1][0], -2.0);
+        assert_eq!(n[1][1], 1.0);
+        assert_eq!(n[1][2], 1.0);
+        assert_eq!(n[2][0], -3.0);
+        assert_eq!(n[2][1], 0.0);
+        assert_eq!(n[2][2], 0.0);
+    }
+
+    #[test]
+    fn diagonal_and_trace() {
+        let m = Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        let d = Mat3f64::from_diagonal(Vec3f64::new([2.0, 3.0, 4.0]));
+
+        assert_eq!(m.diagonal()[0], 1.0);
+        assert_eq!(m.diagonal()[1], 5.0);
+        assert_eq!(m.diagonal()[2], 9.0);
+
+        assert_eq!(d[0][0], 2.0);
+        assert_eq!(d[0][1], 0.0);
+        assert_eq!(d[0][2], 0.0);
+        assert_eq!(d[1][0], 0.0);
+        assert_eq!(d[1][1], 3.0);
+        assert_eq!(d[1][2], 0.0);
+        assert_eq!(d[2][0], 0.0);
+        assert_eq!(d[2][1], 0.0);
+        assert_eq!(d[2][2], 4.0);
+
+        assert_eq!(m.trace(), 15.0);
+        assert_eq!(d.trace(), 9.0);
+        assert_eq!(Mat3f64::identity().trace(), 3.0);
+    }
+
+    #[test]
+    fn frobenius_norm() {
+        let m = Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+
+        assert_eq!(m.frobenius_norm_squared(), 285.0);
+        assert_eq!(m.frobenius_norm(), 285.0f64.sqrt());
+        assert_eq!(Mat3f64::identity().frobenius_norm(), 3.0f64.sqrt());
+        assert_eq!(Mat3f64::zero().frobenius_norm(), 0.0);
+    }
+
+    #[test]
+    fn outer_product() {
+        let a = Vec3f64::new([1.0, 2.0, 3.0]);
+        let b = Vec3f64::new([2.0, -1.0, 3.0]);
+        let m = Mat3f64::outer_product(a, b);
+
+        assert_eq!(m[0][0], 2.0);
+        assert_eq!(m[0][1], -1.0);
+        assert_eq!(m[0][2], 3.0);
+        assert_eq!(m[1][0], 4.0);
+        assert_eq!(m[1][1], -2.0);
+        assert_eq!(m[1][2], 6.0);
+        assert_eq!(m[2][0], 6.0);
+        assert_eq!(m[2][1], -3.0);
+        assert_eq!(m[2][2], 9.0);
+    }
+
+    #[test]
+    fn affine_constructors() {
+        let p = Vec3f64::new([2.0, 3.0, 1.0]);
+
+        let t = Mat3f64::from_translation_2d(Vec2f64::new([4.0, -1.0])) * p;
+        assert_eq!(t[0], 6.0);
+        assert_eq!(t[1], 2.0);
+        assert_eq!(t[2], 1.0);
+
+        let s = Mat3f64::from_scale_2d(Vec2f64::new([2.0, -3.0])) * p;
+        assert_eq!(s[0], 4.0);
+        assert_eq!(s[1], -9.0);
+        assert_eq!(s[2], 1.0);
+
+        let r = Mat3f64::from_rotation_2d(std::f64::consts::FRAC_PI_2) * p;
+        assert!((r[0] + 3.0).abs() < 1e-12);
+        assert!((r[1] - 2.0).abs() < 1e-12);
+        assert_eq!(r[2], 1.0);
+
+        let sh = Mat3f64::from_shear_2d(2.0, 0.5) * p;
+        assert_eq!(sh[0], 8.0);
+        assert_eq!(sh[1], 4.0);
+        assert_eq!(sh[2], 1.0);
+
+        // Directions (w = 0) are not affected by translations
+        let d =
+            Mat3f64::from_translation_2d(Vec2f64::new([4.0, -1.0])) * Vec3f64::new([2.0, 3.0, 0.0]);
+        assert_eq!(d[0], 2.0);
+        assert_eq!(d[1], 3.0);
+        assert_eq!(d[2], 0.0);
+    }
+
+    #[test]
+    fn affine_builders() {
+        let p = Vec3f64::new([1.0, 0.0, 1.0]);
+        let m = Mat3f64::identity()
+            .scale_2d(Vec2f64::new([2.0, 2.0]))
+            .rotate_2d(std::f64::consts::FRAC_PI_2)
+            .translate_2d(Vec2f64::new([10.0, 20.0]));
+        let r = m * p;
+        assert!((r[0] - 10.0).abs() < 1e-12);
+        assert!((r[1] - 22.0).abs() < 1e-12);
+        assert_eq!(r[2], 1.0);
+
+        let sh = Mat3f64::identity().shear_2d(1.0, 0.0) * Vec3f64::new([1.0, 1.0, 1.0]);
+        assert_eq!(sh[0], 2.0);
+        assert_eq!(sh[1], 1.0);
+    }
+
+    #[test]
+    fn transform_points_and_vectors() {
+        let m = Mat3f64::from_translation_2d(Vec2f64::new([1.0, 2.0]))
+            * Mat3f64::from_scale_2d(Vec2f64::new([2.0, 3.0]));
+        let v = Vec2f64::new([1.0, -1.0]);
+
+        let p = m.transform_point2(v);
+        assert_eq!(p[0], 3.0);
+        assert_eq!(p[1], -1.0);
+
+        let d = m.transform_vector2(v);
+        assert_eq!(d[0], 2.0);
+        assert_eq!(d[1], -3.0);
+
+        // Affine matrices have w = 1, so projecting equals transforming
+        let p = m.project_point2(v);
+        assert_eq!(p[0], 3.0);
+        assert_eq!(p[1], -1.0);
+
+        // Projective matrices need the perspective divide
+        let m = Mat3f64::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 1.0, 0.0]]);
+        let p = m.project_point2(Vec2f64::new([4.0, 2.0]));
+        assert_eq!(p[0], 2.0);
+        assert_eq!(p[1], 1.0);
+    }
+
+    #[test]
+    fn flat_arrays() {
+        let m = Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        let rows = m.as_row_major_flat();
+        let cols = m.as_col_major_flat();
+        let from_rows = Mat3f64::from_row_major_flat([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+        let from_cols = Mat3f64::from_col_major_flat([1.0, 4.0, 7.0, 2.0, 5.0, 8.0, 3.0, 6.0, 9.0]);
+
+        assert_eq!(rows[0], 1.0);
+        assert_eq!(rows[1], 2.0);
+        assert_eq!(rows[2], 3.0);
+        assert_eq!(rows[3], 4.0);
+        assert_eq!(rows[4], 5.0);
+        assert_eq!(rows[5], 6.0);
+        assert_eq!(rows[6], 7.0);
+        assert_eq!(rows[7], 8.0);
+        assert_eq!(rows[8], 9.0);
+
+        assert_eq!(cols[0], 1.0);
+        assert_eq!(cols[1], 4.0);
+        assert_eq!(cols[2], 7.0);
+        assert_eq!(cols[3], 2.0);
+        assert_eq!(cols[4], 5.0);
+        assert_eq!(cols[5], 8.0);
+        assert_eq!(cols[6], 3.0);
+        assert_eq!(cols[7], 6.0);
+        assert_eq!(cols[8], 9.0);
+
+        assert_eq!(from_rows[0][0], 1.0);
+        assert_eq!(from_rows[0][1], 2.0);
+        assert_eq!(from_rows[0][2], 3.0);
+        assert_eq!(from_rows[1][0], 4.0);
+        assert_eq!(from_rows[1][1], 5.0);
+        assert_eq!(from_rows[1][2], 6.0);
+        assert_eq!(from_rows[2][0], 7.0);
+        assert_eq!(from_rows[2][1], 8.0);
+        assert_eq!(from_rows[2][2], 9.0);
+
+        assert_eq!(from_cols[0][0], 1.0);
+        assert_eq!(from_cols[0][1], 2.0);
+        assert_eq!(from_cols[0][2], 3.0);
+        assert_eq!(from_cols[1][0], 4.0);
+        assert_eq!(from_cols[1][1], 5.0);
+        assert_eq!(from_cols[1][2], 6.0);
+        assert_eq!(from_cols[2][0], 7.0);
+        assert_eq!(from_cols[2][1], 8.0);
+        assert_eq!(from_cols[2][2], 9.0);
+    }
+
+    #[test]
+    fn precision_conversion() {
+        let m = Mat3f64::new([
+            [1.0, 2.0 / 3.0, 3.0],
+            [4.0 / 3.0, 5.0, 6.0 / 3.0],
+            [7.0, 8.0 / 3.0, 9.0],
+        ]);
+        let c = m.to_f32();
+
+        assert_eq!(c[0][0], m[0][0] as f32);
+        assert_eq!(c[0][1], m[0][1] as f32);
+        assert_eq!(c[0][2], m[0][2] as f32);
+        assert_eq!(c[1][0], m[1][0] as f32);
+        assert_eq!(c[1][1], m[1][1] as f32);
+        assert_eq!(c[1][2], m[1][2] as f32);
+        assert_eq!(c[2][0], m[2][0] as f32);
+        assert_eq!(c[2][1], m[2][1] as f32);
+        assert_eq!(c[2][2], m[2][2] as f32);
+    }
+
+    #[test]
+    fn embedding_and_extraction() {
+        let s = Mat2f64::new([[11.0, 12.0], [21.0, 22.0]]);
+        let e = Mat3f64::from_mat2(s);
+        let m = Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        let u = m.upper_left();
+
+        assert_eq!(e[0][0], 11.0);
+        assert_eq!(e[0][1], 12.0);
+        assert_eq!(e[0][2], 0.0);
+        assert_eq!(e[1][0], 21.0);
+        assert_eq!(e[1][1], 22.0);
+        assert_eq!(e[1][2], 0.0);
+        assert_eq!(e[2][0], 0.0);
+        assert_eq!(e[2][1], 0.0);
+        assert_eq!(e[2][2], 1.0);
+
+        assert_eq!(u[0][0], 1.0);
+        assert_eq!(u[0][1], 2.0);
+        assert_eq!(u[1][0], 4.0);
+        assert_eq!(u[1][1], 5.0);
+    }
+
+    #[test]
+    fn lu_decomposition() {
+        let m = Mat3f64::new([[0.0, 2.0, 1.0], [1.0, -2.0, -3.0], [-1.0, 1.0, 2.0]]);
+        let lu = m.lu(1e-12).unwrap();
+        let (l, u, p) = (lu.l(), lu.u(), lu.p());
+        let pa = p * m;
+        let product = l * u;
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((pa[i][j] - product[i][j]).abs() < 1e-12);
+                if j > i {
+                    assert_eq!(l[i][j], 0.0);
+                }
+                if j < i {
+                    assert_eq!(u[i][j], 0.0);
+                }
+            }
+            assert_eq!(l[i][i], 1.0);
+        }
+        // The first pivot is 0.0, so rows have to be swapped
+        assert_eq!(p[0][0], 0.0);
+        assert!((lu.determinant() - m.determinant()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn solve() {
+        let m = Mat3f64::new([[0.0, 2.0, 1.0], [1.0, -2.0, -3.0], [-1.0, 1.0, 2.0]]);
+        let b = Vec3f64::new([-1.0, -4.0, 3.0]);
+        let x = m.solve(b, 1e-12).unwrap();
+        let expected = [1.0, -2.0, 3.0];
+        for i in 0..3 {
+            assert!((x[i] - expected[i]).abs() < 1e-12);
+        }
+
+        // Singular matrices
+        let singular = Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        assert!(singular.lu(1e-12).is_none());
+        assert!(singular.solve(b, 1e-12).is_none());
+        assert!(Mat3f64::zero().lu(0.0).is_none());
+        assert!(Mat3f64::identity().lu(0.0).is_some());
+    }
+
+    #[test]
+    fn qr_decomposition() {
+        let m = Mat3f64::new([[12.0, -51.0, 4.0], [6.0, 167.0, -68.0], [-4.0, 24.0, -41.0]]);
+        let (q, r) = m.qr();
+        let product = q * r;
+
+        assert_orthonormal(q);
+        for i in 0..3 {
+            assert!(r[i][i] >= 0.0);
+            for j in 0..3 {
+                if j < i {
+                    assert_eq!(r[i][j], 0.0);
+                }
+                assert!((product[i][j] - m[i][j]).abs() < 1e-12);
+            }
+        }
+
+        let (q, r) = Mat3f64::identity().qr();
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((q[i][j] - expected).abs() < 1e-12);
+                assert!((r[i][j] - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn orthonormalize() {
+        let rotation = Mat3f64::new([[0.36, 0.48, -0.8], [-0.8, 0.6, 0.0], [0.48, 0.64, 0.6]]);
+        let mut drifted = rotation;
+        for i in 0..3 {
+            for j in 0..3 {
+                drifted[i][j] += 0.01 * ((i * 3 + j) % 3) as f64 - 0.01;
+            }
+        }
+        let original = drifted.col(0).normed();
+        let orthonormalized = drifted.orthonormalized();
+        drifted.orthonormalize();
+
+        assert_orthonormal(orthonormalized);
+        assert_orthonormal(drifted);
+        assert!((orthonormalized.determinant() - 1.0).abs() < 1e-12);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((orthonormalized[i][j] - rotation[i][j]).abs() < 0.05);
+            }
+        }
+
+        // The first column keeps its direction
+        assert!((orthonormalized.col(0) - original).mag() < 1e-12);
+    }
+
+    #[test]
+    fn symmetric_eigen() {
+        let m = Mat3f64::new([[2.0, -1.0, 0.0], [-1.0, 2.0, -1.0], [0.0, -1.0, 2.0]]);
+        let (values, vectors) = m.symmetric_eigen();
+        assert!((values[0] - (2.0 + 2.0f64.sqrt())).abs() < 1e-12);
+        assert!((values[1] - 2.0).abs() < 1e-12);
+        assert!((values[2] - (2.0 - 2.0f64.sqrt())).abs() < 1e-12);
+        assert_eigen(m, values, vectors);
+
+        // Already diagonal
+        let m = Mat3f64::from_diagonal(Vec3f64::new([1.0, 3.0, 2.0]));
+        let (values, vectors) = m.symmetric_eigen();
+        assert_eq!(values[0], 3.0);
+        assert_eq!(values[1], 2.0);
+        assert_eq!(values[2], 1.0);
+        assert_eigen(m, values, vectors);
+
+        // Repeated eigenvalues
+        let m = Mat3f64::new([[2.0, 1.0, 0.0], [1.0, 2.0, 0.0], [0.0, 0.0, 3.0]]);
+        let (values, vectors) = m.symmetric_eigen();
+        assert!((values[0] - 3.0).abs() < 1e-12);
+        assert!((values[1] - 3.0).abs() < 1e-12);
+        assert!((values[2] - 1.0).abs() < 1e-12);
+        assert_eigen(m, values, vectors);
+
+        // Inertia tensor like matrix, reconstructed from its eigendecomposition
+        let m = Mat3f64::new([[4.0, -2.0, 1.0], [-2.0, 6.0, 0.5], [1.0, 0.5, 3.0]]);
+        let (values, vectors) = m.symmetric_eigen();
+        assert_eigen(m, values, vectors);
+        let r = vectors * Mat3f64::from_diagonal(values) * vectors.transposed();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((r[i][j] - m[i][j]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn svd() {
+        // Rotation times scale times rotation
+        let u = Mat3f64::new([[0.36, 0.48, -0.8], [-0.8, 0.6, 0.0], [0.48, 0.64, 0.6]]);
+        let v = Mat3f64::new([[0.6, 0.0, -0.8], [0.0, 1.0, 0.0], [0.8, 0.0, 0.6]]);
+        let m = u * Mat3f64::from_diagonal(Vec3f64::new([5.0, 3.0, 0.5])) * v.transposed();
+        let (_, sigma, _) = m.svd();
+        assert!((sigma[0] - 5.0).abs() < 1e-12);
+        assert!((sigma[1] - 3.0).abs() < 1e-12);
+        assert!((sigma[2] - 0.5).abs() < 1e-12);
+        assert_svd(m, m.svd());
+
+        // Reflections result in a negative last singular value
+        let m = Mat3f64::from_diagonal(Vec3f64::new([1.0, -2.0, 3.0]));
+        let (_, sigma, _) = m.svd();
+        assert!((sigma[0] - 3.0).abs() < 1e-12);
+        assert!((sigma[1] - 2.0).abs() < 1e-12);
+        assert!((sigma[2] + 1.0).abs() < 1e-12);
+        assert_svd(m, m.svd());
+
+        // Rank deficient
+        for m in [
+            Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]),
+            Mat3f64::outer_product(
+                Vec3f64::new([1.0, 2.0, 3.0]),
+                Vec3f64::new([-1.0, 0.5, 2.0]),
+            ),
+            Mat3f64::identity(),
+            Mat3f64::new([[4.0, -2.0, 1.0], [-2.0, 6.0, 0.5], [1.0, 0.5, 3.0]]),
+        ] {
+            assert_svd(m, m.svd());
+        }
+    }
+
+    #[test]
+    fn svd_small_singular_value() {
+        // self^T * self would square the ratio of 1e-5 between the singular values
+        let u = Mat3f64::new([[0.36, 0.48, -0.8], [-0.8, 0.6, 0.0], [0.48, 0.64, 0.6]]);
+        let v = Mat3f64::new([[0.6, 0.0, -0.8], [0.0, 1.0, 0.0], [0.8, 0.0, 0.6]]);
+        let m = u * Mat3f64::from_diagonal(Vec3f64::new([1.0, 0.5, 1e-5])) * v.transposed();
+        let (u, sigma, v) = m.svd();
+        assert!((sigma[2] - 1e-5).abs() < 1e-14);
+        assert_mat_close(u.transposed() * u, Mat3f64::identity(), 1e-15);
+        assert_mat_close(v.transposed() * v, Mat3f64::identity(), 1e-15);
+        assert_mat_close(u * Mat3f64::from_diagonal(sigma) * v.transposed(), m, 1e-15);
+        assert_mat_close(m.pseudo_inverse(0.0) * m, Mat3f64::identity(), 1e-10);
+    }
+
+    #[test]
+    fn pseudo_inverse() {
+        let m = Mat3f64::new([[1.0, 2.0, 3.0], [0.0, 1.0, 4.0], [5.0, 6.0, 0.0]]);
+        let p = m.pseudo_inverse(1e-12);
+        let inverse = m.inverse();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((p[i][j] - inverse[i][j]).abs() < 1e-12);
+            }
+        }
+
+        // m * m^+ * m = m for singular matrices
+        let m = Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        let p = m.pseudo_inverse(1e-12);
+        let r = m * p * m;
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((r[i][j] - m[i][j]).abs() < 1e-12);
+            }
+        }
+        let zero = Mat3f64::zero().pseudo_inverse(1e-12);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_eq!(zero[i][j], 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn polar_decompose() {
+        let rotation = Mat3f64::new([[0.36, 0.48, -0.8], [-0.8, 0.6, 0.0], [0.48, 0.64, 0.6]]);
+        let stretch = Mat3f64::new([[2.0, 0.5, 0.0], [0.5, 1.5, 0.25], [0.0, 0.25, 1.0]]);
+        let m = rotation * stretch;
+        let (r, s) = m.polar_decompose();
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((r[i][j] - rotation[i][j]).abs() < 1e-12);
+                assert!((s[i][j] - stretch[i][j]).abs() < 1e-12);
+            }
+        }
+        assert_polar(m, (r, s));
+
+        // The stretch of matrices without reflection is positive semi definite
+        let (values, _) = s.symmetric_eigen();
+        assert!(values[2] >= 0.0);
+
+        // Reflections end up in the stretch, the rotation stays a rotation
+        let mirrored = rotation * Mat3f64::from_diagonal(Vec3f64::new([1.0, -2.0, 3.0]));
+        assert_polar(mirrored, mirrored.polar_decompose());
+        let (_, s) = mirrored.polar_decompose();
+        let (values, _) = s.symmetric_eigen();
+        assert!(values[2] < 0.0);
+
+        // Singular matrices
+        for m in [
+            Mat3f64::new([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]),
+            Mat3f64::zero(),
+            Mat3f64::identity(),
+        ] {
+            assert_polar(m, m.polar_decompose());
+        }
+    }
+
+    #[test]
+    fn cholesky() {
+        let m = Mat3f64::new([
+            [4.0, 12.0, -16.0],
+            [12.0, 37.0, -43.0],
+            [-16.0, -43.0, 98.0],
+        ]);
+        let cholesky = m.cholesky(1e-12).unwrap();
+        let l = cholesky.l();
+        assert_mat_close(l * l.transposed(), m, 1e-12);
+        for i in 0..3 {
+            assert!(l[i][i] > 0.0);
+            for j in i + 1..3 {
+                assert_eq!(l[i][j], 0.0);
+            }
+        }
+
+        // Known decomposition
+        let l = cholesky.l();
+        let expected = [[2.0, 0.0, 0.0], [6.0, 1.0, 0.0], [-8.0, 5.0, 3.0]];
+        for i in 0..3 {
+            for j in 0..3 {
+                assert!((l[i][j] - expected[i][j]).abs() < 1e-12);
+            }
+        }
+
+        let x = cholesky.solve(Vec3f64::new([-68.0, -191.0, 364.0]));
+        assert!((x - Vec3f64::new([1.0, -2.0, 3.0])).mag() < 1e-12);
+        assert!((cholesky.determinant() - m.determinant()).abs() < 1e-12);
+        assert_mat_close(cholesky.inverse() * m, Mat3f64::identity(), 1e-12);
+
+        // Not positive definite
+        assert!(
+            Mat3f64::new([[2.0, 1.0, 0.0], [1.0, -1.0, 1.0], [0.0, 1.0, 3.0]])
+                .cholesky(1e-12)
+                .is_none()
+        );
+        assert!((-1.0 * Mat3f64::identity()).cholesky(1e-12).is_none());
+        assert!(Mat3f64::zero().cholesky(0.0).is_none());
+    }
+
+    #[test]
+    fn ldlt() {
+        for m in [
+            Mat3f64::new([
+                [4.0, 12.0, -16.0],
+                [12.0, 37.0, -43.0],
+                [-16.0, -43.0, 98.0],
+            ]),
+            Mat3f64::new([[2.0, 1.0, 0.0], [1.0, -1.0, 1.0], [0.0, 1.0, 3.0]]),
+        ] {
+            let ldlt = m.ldlt(1e-12).unwrap();
+            let l = ldlt.l();
+            let d = Mat3f64::from_diagonal(ldlt.d());
+            assert_mat_close(l * d * l.transposed(), m, 1e-12);
+            for i in 0..3 {
+                assert_eq!(l[i][i], 1.0);
+                for j in i + 1..3 {
+                    assert_eq!(l[i][j], 0.0);
+                }
+            }
+            assert!((ldlt.determinant() - m.determinant()).abs() < 1e-12);
+        }
+
+        let m = Mat3f64::new([[2.0, 1.0, 0.0], [1.0, -1.0, 1.0], [0.0, 1.0, 3.0]]);
+        let x = m.ldlt(1e-12).unwrap().solve(Vec3f64::new([0.0, 6.0, 7.0]));
+        assert!((x - Vec3f64::new([1.0, -2.0, 3.0])).mag() < 1e-12);
+
+        assert!(Mat3f64::zero().ldlt(0.0).is_none());
+    }
+
+    #[test]
+    fn symmetric_inverse() {
+        for m in [
+            Mat3f64::new([
+                [4.0, 12.0, -16.0],
+                [12.0, 37.0, -43.0],
+                [-16.0, -43.0, 98.0],
+            ]),
+            Mat3f64::new([[2.0, 1.0, 0.0], [1.0, -1.0, 1.0], [0.0, 1.0, 3.0]]),
+        ] {
+            let inverse = m.symmetric_inverse(1e-12).unwrap();
+            assert_mat_close(inverse * m, Mat3f64::identity(), 1e-12);
+            assert_mat_close(inverse, m.inverse(), 1e-12);
+        }
+        assert!(Mat3f64::zero().symmetric_inverse(1e-12).is_none());
+    }
+
+    #[test]
+    fn powi() {
+        let m = Mat3f64::new([[1.0, 2.0, 0.0], [0.0, 1.0, -1.0], [2.0, 0.0, 1.0]]);
+        assert_eq!(m.powi(0).as_row_major(), Mat3f64::identity().as_row_major());
+        assert_eq!(m.powi(1).as_row_major(), m.as_row_major());
+        assert_mat_close(m.powi(3), m * m * m, 1e-12);
+        assert_mat_close(m.powi(-2), m.inverse() * m.inverse(), 1e-12);
+        assert_mat_close(m.powi(5) * m.powi(-5), Mat3f64::identity(), 1e-12);
+    }
+
+    #[test]
+    fn powf() {
+        let rotation = Mat3f64::new([
+            [0.7648421872844885, -0.644217687237691, 0.0],
+            [0.644217687237691, 0.7648421872844885, 0.0],
+            [0.0, 0.0, 1.0],
+        ]);
+        assert_mat_close(
+            rotation.powf(0.5).unwrap(),
+            Mat3f64::new([
+                [0.9393727128473789, -0.34289780745545134, 0.0],
+                [0.34289780745545134, 0.9393727128473789, 0.0],
+                [0.0, 0.0, 1.0],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(rotation.powf(0.0).unwrap(), Mat3f64::identity(), 1e-12);
+
+        let m = Mat3f64::new([[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]]);
+        assert_mat_close(m.powf(2.0).unwrap(), m * m, 1e-12);
+        assert_mat_close(m.powf(-1.0).unwrap(), m.inverse(), 1e-12);
+        assert!((-1.0 * Mat3f64::identity()).powf(0.5).is_none());
+    }
+
+    #[test]
+    fn exp() {
+        assert_eq!(
+            Mat3f64::zero().exp().as_row_major(),
+            Mat3f64::identity().as_row_major()
+        );
+        assert_mat_close(
+            Mat3f64::new([[0.0, -0.7, 0.0], [0.7, 0.0, 0.0], [0.0, 0.0, 0.0]]).exp(),
+            Mat3f64::new([
+                [0.7648421872844885, -0.644217687237691, 0.0],
+                [0.644217687237691, 0.7648421872844885, 0.0],
+                [0.0, 0.0, 1.0],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat3f64::new([[0.5, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, -1.0]]).exp(),
+            Mat3f64::new([
+                [1.6487212707001282, 0.0, 0.0],
+                [0.0, 7.38905609893065, 0.0],
+                [0.0, 0.0, 0.36787944117144233],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat3f64::new([[0.0, 0.5, 0.0], [0.0, 0.0, 0.5], [0.0, 0.0, 0.0]]).exp(),
+            Mat3f64::new([[1.0, 0.5, 0.125], [0.0, 1.0, 0.5], [0.0, 0.0, 1.0]]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat3f64::new([[0.1, -0.3, 0.2], [0.25, 0.2, -0.1], [-0.05, 0.15, -0.2]]).exp(),
+            Mat3f64::new([
+                [1.0587377136102092, -0.32767686704585, 0.20301885928247043],
+                [
+                    0.28849644128630453,
+                    1.1705354001946195,
+                    -0.07321672304750323,
+                ],
+                [
+                    -0.027606136698473265,
+                    0.15612224081554352,
+                    0.8082227736381736,
+                ],
+            ]),
+            1e-12,
+        );
+        let large = Mat3f64::new([[1.0, 2.0, 0.0], [0.0, 1.0, -1.0], [2.0, 0.0, 1.0]]);
+        let expected = Mat3f64::new([
+            [0.9660222415349754, 4.547632986623184, -2.5392117057420713],
+            [-2.5392117057420713, 0.9660222415349754, -2.273816493311592],
+            [4.547632986623184, 5.078423411484143, 0.9660222415349754],
+        ]);
+        assert_mat_close(large.exp(), expected, 1e-12 * large.exp().frobenius_norm());
+    }
+
+    #[test]
+    fn log() {
+        assert_eq!(
+            Mat3f64::identity().log().unwrap().as_row_major(),
+            Mat3f64::zero().as_row_major()
+        );
+        assert_mat_close(
+            Mat3f64::new([
+                [0.7648421872844885, -0.644217687237691, 0.0],
+                [0.644217687237691, 0.7648421872844885, 0.0],
+                [0.0, 0.0, 1.0],
+            ])
+            .log()
+            .unwrap(),
+            Mat3f64::new([[0.0, -0.7, 0.0], [0.7, 0.0, 0.0], [0.0, 0.0, 0.0]]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat3f64::new([[0.1, -0.3, 0.2], [0.25, 0.2, -0.1], [-0.05, 0.15, -0.2]])
+                .exp()
+                .log()
+                .unwrap(),
+            Mat3f64::new([[0.1, -0.3, 0.2], [0.25, 0.2, -0.1], [-0.05, 0.15, -0.2]]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat3f64::new([[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]])
+                .log()
+                .unwrap(),
+            Mat3f64::new([
+                [1.3436302507825282, 0.31259548013244576, -0.0675775180180274],
+                [0.31259548013244576, 0.963457252632055, 0.4477505161685006],
+                [-0.0675775180180274, 0.4477505161685006, 0.5832842544815817],
+            ]),
+            1e-12,
+        );
+        assert!((-1.0 * Mat3f64::identity()).log().is_none());
+        assert!(Mat3f64::zero().log().is_none());
+    }
+
+    #[test]
+    fn sqrt() {
+        let m = Mat3f64::new([[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]]);
+        let root = m.sqrt().unwrap();
+        assert_mat_close(root * root, m, 1e-12);
+        assert_mat_close(
+            root,
+            Mat3f64::new([
+                [
+                    1.9807091316411685,
+                    0.27578188529998865,
+                    -0.02712356122769758,
+                ],
+                [0.27578188529998865, 1.677803685113482, 0.3300290077553838],
+                [-0.02712356122769758, 0.3300290077553838, 1.374898238585796],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat3f64::new([
+                [0.7648421872844885, -0.644217687237691, 0.0],
+                [0.644217687237691, 0.7648421872844885, 0.0],
+                [0.0, 0.0, 1.0],
+            ])
+            .sqrt()
+            .unwrap(),
+            Mat3f64::new([
+                [0.9393727128473789, -0.34289780745545134, 0.0],
+                [0.34289780745545134, 0.9393727128473789, 0.0],
+                [0.0, 0.0, 1.0],
+            ]),
+            1e-12,
+        );
+        assert_eq!(
+            Mat3f64::identity().sqrt().unwrap().as_row_major(),
+            Mat3f64::identity().as_row_major()
+        );
+        assert!((-1.0 * Mat3f64::identity()).sqrt().is_none());
+        assert_eq!(
+            Mat3f64::zero().sqrt().unwrap().as_row_major(),
+            Mat3f64::zero().as_row_major()
+        );
+
+        // Singular positive semidefinite matrices have a square root
+        assert_mat_close(
+            Mat3f64::from_diagonal(Vec3f64::new([4.0, 0.0, 9.0]))
+                .sqrt()
+                .unwrap(),
+            Mat3f64::from_diagonal(Vec3f64::new([2.0, 0.0, 3.0])),
+            1e-12,
+        );
+        // A rank one matrix v * v^T has the square root v * v^T / |v|
+        let m = Mat3f64::new([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [3.0, 6.0, 9.0]]);
+        assert_mat_close(m.sqrt().unwrap(), m / 14.0f64.sqrt(), 1e-12);
+        // Singular matrices that are not symmetric need not have a square root
+        assert!(
+            Mat3f64::new([[0.0, 1.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 1.0]])
+                .sqrt()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn rank() {
+        assert_eq!(
+            Mat3f64::new([[2.5, 0.5, 0.0], [0.0, 2.5, 0.5], [0.5, 0.0, 2.5]]).rank(1e-12),
+            3
+        );
+        assert_eq!(
+            Mat3f64::new([[2.0, 1.0, -1.0], [-4.0, -2.0, 2.0], [1.0, 0.5, -0.5]]).rank(1e-12),
+            1
+        );
+        assert_eq!(Mat3f64::zero().rank(1e-12), 0);
+    }
+
+    #[test]
+    fn null_space() {
+        let m = Mat3f64::new([[2.0, 1.0, -1.0], [-4.0, -2.0, 2.0], [1.0, 0.5, -0.5]]);
+        let basis = m.null_space(1e-12);
+        assert_eq!(basis.len(), 2);
+        for (i, v) in basis.iter().enumerate() {
+            assert!((m * *v).mag() < 1e-12);
+            assert!((v.mag() - 1.0).abs() < 1e-12);
+            for w in &basis[..i] {
+                assert!(v.dot(*w).abs() < 1e-12);
+            }
+        }
+
+        let m = Mat3f64::new([[2.5, 0.5, 0.0], [0.0, 2.5, 0.5], [0.5, 0.0, 2.5]]);
+        let basis = m.null_space(1e-12);
+        assert_eq!(basis.len(), 0);
+        for v in basis {
+            assert!((m * v).mag() < 1e-12);
+        }
+        assert_eq!(Mat3f64::zero().null_space(1e-12).len(), 3);
+    }
+
+    #[test]
+    fn condition_number() {
+        let m = Mat3f64::new([[1.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 4.0]]);
+        assert!((Mat3f64::identity().condition_number() - 3.0).abs() < 1e-12);
+        assert!((m.condition_number() - 5.25).abs() < 1e-12);
+        assert!(
+            Mat3f64::new([[2.0, 1.0, -1.0], [-4.0, -2.0, 2.0], [1.0, 0.5, -0.5]])
+                .condition_number()
+                > 1e6
+        );
+        assert_eq!(Mat3f64::zero().condition_number(), f64::INFINITY);
+    }
+
+    #[test]
+    fn is_invertible() {
+        assert!(
+            Mat3f64::new([[2.5, 0.5, 0.0], [0.0, 2.5, 0.5], [0.5, 0.0, 2.5]]).is_invertible(1e-12)
+        );
+        assert!(
+            !Mat3f64::new([[2.0, 1.0, -1.0], [-4.0, -2.0, 2.0], [1.0, 0.5, -0.5]])
+                .is_invertible(1e-12)
+        );
+        assert!(!Mat3f64::zero().is_invertible(0.0));
+    }
+
+    #[test]
+    fn is_orthogonal() {
+        let rotation = Mat3f64::new([[0.6, -0.8, 0.0], [0.8, 0.6, 0.0], [0.0, 0.0, 1.0]]);
+        assert!(rotation.is_orthogonal(1e-12));
+        assert!((-1.0 * rotation).is_orthogonal(1e-12));
+        assert!(!(2.0 * rotation).is_orthogonal(1e-12));
+        assert!(
+            !Mat3f64::new([[2.5, 0.5, 0.0], [0.0, 2.5, 0.5], [0.5, 0.0, 2.5]]).is_orthogonal(1e-12)
+        );
+    }
+
+    #[test]
+    fn is_symmetric() {
+        let m = Mat3f64::new([[2.5, 0.5, 0.0], [0.0, 2.5, 0.5], [0.5, 0.0, 2.5]]);
+        assert!(!m.is_symmetric(1e-12));
+        assert!((m + m.transposed()).is_symmetric(1e-12));
+        let v = Vec3f64::new([1.0, -2.0, 0.5]);
+        assert!(Mat3f64::outer_product(v, v).is_symmetric(1e-12));
+        assert!(!Mat3f64::outer_product(v, 2.0 * v + Vec3f64::ones()).is_symmetric(1e-12));
+    }
+
+    #[test]
+    fn inverse_affine_2d() {
+        let m = Mat3f64::from_translation_2d(Vec2f64::new([1.0, -2.0]))
+            .rotate_2d(0.8)
+            .scale_2d(Vec2f64::new([2.0, -0.5]))
+            .shear_2d(0.3, -0.2);
+        let inverse = m.inverse_affine_2d();
+        assert_mat_close(inverse, m.inverse(), 1e-12);
+        assert_mat_close(inverse * m, Mat3f64::identity(), 1e-12);
+        assert_eq!(inverse.rows[2], [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn inverse_rigid_2d() {
+        let m = Mat3f64::from_rotation_2d(2.1).translate_2d(Vec2f64::new([4.0, 0.5]));
+        let inverse = m.inverse_rigid_2d();
+        assert_mat_close(inverse, m.inverse(), 1e-12);
+        assert_mat_close(inverse * m, Mat3f64::identity(), 1e-12);
+        assert_eq!(inverse.rows[2], [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn inverse_affine_2d_projective() {
+        Mat3f64::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.5, 0.0, 1.0]]).inverse_affine_2d();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn inverse_rigid_2d_scaled() {
+        Mat3f64::from_scale_2d(Vec2f64::new([2.0, 1.0])).inverse_rigid_2d();
+    }
+
+    #[test]
+    fn from_reflection_2d() {
+        // The line x = 1
+        let m = Mat3f64::from_reflection_2d(Vec3f64::new([2.0, 0.0, -2.0]));
+        let p = m.transform_point2(Vec2f64::new([3.0, 2.0]));
+        assert!((p - Vec2f64::new([-1.0, 2.0])).mag() < 1e-12);
+        assert!((m.determinant() + 1.0).abs() < 1e-12);
+        assert_mat_close(m * m, Mat3f64::identity(), 1e-12);
+    }
 }
@@ -1,5 +1,9 @@
 use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
 
+use crate::mat::convention::{DepthRange, Handedness, ProjectionConvention};
+use crate::mat::mat3f64::Mat3f64;
+use crate::mat::mat4f32::Mat4f32;
+use crate::vec::vec3f64::Vec3f64;
 use crate::vec::vec4f64::Vec4f64;
 
 /// A 4x4 floating point matrix.
@@ -14,18 +18,18 @@ pub struct Mat4f64 {
 impl Mat4f64 {
     /// Creates a new matrix with user defined elements.
     /// The user defined elements are in row major order.
-    pub fn new(rows: [[f64; 4]; 4]) -> Self {
+    pub const fn new(rows: [[f64; 4]; 4]) -> Self {
         Self { rows }
     }
 
     /// Creates a new matrix with user defined elements.
     /// The user defined elements are in column major order.
-    pub fn new_from_cols(cols: [[f64; 4]; 4]) -> Self {
+    pub const fn new_from_cols(cols: [[f64; 4]; 4]) -> Self {
         Self::new(cols).transposed()
     }
 
     /// Creates a new matrix with all elements equal to 0.0.
-    pub fn zero() -> Self {
+    pub const fn zero() -> Self {
         Self {
             rows: [
                 [0.0, 0.0, 0.0, 0.0],
@@ -37,7 +41,7 @@ impl Mat4f64 {
     }
 
     /// Creates a new identity matrix.
-    pub fn identity() -> Self {
+    pub const fn identity() -> Self {
         Self {
             rows: [
                 [1.0, 0.0, 0.0, 0.0],
@@ -50,376 +54,2112 @@ impl Mat4f64 {
 
     /// Returns self but transposed
     /// (Rows are now columns and columns are now rows).
-    pub fn transposed(&self) -> Self {
+    pub const fn transposed(&self) -> Self {
+        let m = &self.rows;
         Self {
             rows: [
-                [self[0][0], self[1][0], self[2][0], self[3][0]],
-                [self[0][1], self[1][1], self[2][1], self[3][1]],
-                [self[0][2], self[1][2], self[2][2], self[3][2]],
-                [self[0][3], self[1][3], self[2][3], self[3][3]],
+                [m[0][0], m[1][0], m[2][0], m[3][0]],
+                [m[0][1], m[1][1], m[2][1], m[3][1]],
+                [m[0][2], m[1][2], m[2][2], m[3][2]],
+                [m[0][3], m[1][3], m[2][3], m[3][3]],
             ],
         }
     }
 
     /// Transposes self
     /// (Rows are now columns and columns are now rows).
-    pub fn transpose(&mut self) {
+    pub const fn transpose(&mut self) {
         *self = self.transposed()
     }
 
     /// Returns the matrix rows as arrays in row major order.
-    pub fn as_row_major(&self) -> [[f64; 4]; 4] {
+    pub const fn as_row_major(&self) -> [[f64; 4]; 4] {
         self.rows
     }
 
     /// Returns the matrix columns as arrays in column major order.
-    pub fn as_col_major(&self) -> [[f64; 4]; 4] {
+    pub const fn as_col_major(&self) -> [[f64; 4]; 4] {
         self.transposed().rows
     }
-}
 
-impl Index<usize> for Mat4f64 {
-    type Output = [f64; 4];
-    fn index(&self, index: usize) -> &Self::Output {
-        &self.rows[index]
+    /// Creates a new matrix from row vectors.
+    pub const fn from_rows(rows: [Vec4f64; 4]) -> Self {
+        Self::new([
+            rows[0].coords,
+            rows[1].coords,
+            rows[2].coords,
+            rows[3].coords,
+        ])
     }
-}
 
-impl IndexMut<usize> for Mat4f64 {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.rows[index]
+    /// Creates a new matrix from column vectors.
+    pub const fn from_cols(cols: [Vec4f64; 4]) -> Self {
+        Self::new_from_cols([
+            cols[0].coords,
+            cols[1].coords,
+            cols[2].coords,
+            cols[3].coords,
+        ])
     }
-}
 
-impl Add<Mat4f64> for Mat4f64 {
-    type Output = Mat4f64;
-    fn add(mut self, rhs: Mat4f64) -> Self::Output {
-        self[0][0] += rhs[0][0];
-        self[0][1] += rhs[0][1];
-        self[0][2] += rhs[0][2];
-        self[0][3] += rhs[0][3];
-        self[1][0] += rhs[1][0];
-        self[1][1] += rhs[1][1];
-        self[1][2] += rhs[1][2];
-        self[1][3] += rhs[1][3];
-        self[2][0] += rhs[2][0];
-        self[2][1] += rhs[2][1];
-        self[2][2] += rhs[2][2];
-        self[2][3] += rhs[2][3];
-        self[3][0] += rhs[3][0];
-        self[3][1] += rhs[3][1];
-        self[3][2] += rhs[3][2];
-        self[3][3] += rhs[3][3];
-        self
+    /// Creates a new diagonal matrix with the components of diagonal
+    /// on the main diagonal and all other elements equal to 0.0.
+    pub const fn from_diagonal(diagonal: Vec4f64) -> Self {
+        let d = &diagonal.coords;
+        Self::new([
+            [d[0], 0.0, 0.0, 0.0],
+            [0.0, d[1], 0.0, 0.0],
+            [0.0, 0.0, d[2], 0.0],
+            [0.0, 0.0, 0.0, d[3]],
+        ])
     }
-}
 
-impl AddAssign<Mat4f64> for Mat4f64 {
-    fn add_assign(&mut self, rhs: Mat4f64) {
-        *self = *self + rhs;
+    /// Creates the outer product of a and b (a * b^T).
+    /// Element [i][j] of the result is a[i] * b[j].
+    pub const fn outer_product(a: Vec4f64, b: Vec4f64) -> Self {
+        let a = &a.coords;
+        let b = &b.coords;
+        Self::new([
+            [a[0] * b[0], a[0] * b[1], a[0] * b[2], a[0] * b[3]],
+            [a[1] * b[0], a[1] * b[1], a[1] * b[2], a[1] * b[3]],
+            [a[2] * b[0], a[2] * b[1], a[2] * b[2], a[2] * b[3]],
+            [a[3] * b[0], a[3] * b[1], a[3] * b[2], a[3] * b[3]],
+        ])
     }
-}
 
-impl Sub<Mat4f64> for Mat4f64 {
-    type Output = Mat4f64;
-    fn sub(mut self, rhs: Mat4f64) -> Self::Output {
-        self[0][0] -= rhs[0][0];
-        self[0][1] -= rhs[0][1];
-        self[0][2] -= rhs[0][2];
-        self[0][3] -= rhs[0][3];
-        self[1][0] -= rhs[1][0];
-        self[1][1] -= rhs[1][1];
-        self[1][2] -= rhs[1][2];
-        self[1][3] -= rhs[1][3];
-        self[2][0] -= rhs[2][0];
-        self[2][1] -= rhs[2][1];
-        self[2][2] -= rhs[2][2];
-        self[2][3] -= rhs[2][3];
-        self[3][0] -= rhs[3][0];
-        self[3][1] -= rhs[3][1];
-        self[3][2] -= rhs[3][2];
-        self[3][3] -= rhs[3][3];
-        self
+    /// Returns the row at index as a vector.
+    pub const fn row(&self, index: usize) -> Vec4f64 {
+        Vec4f64::new(self.rows[index])
     }
-}
 
-impl SubAssign<Mat4f64> for Mat4f64 {
-    fn sub_assign(&mut self, rhs: Mat4f64) {
-        *self = *self - rhs;
+    /// Returns the column at index as a vector.
+    pub const fn col(&self, index: usize) -> Vec4f64 {
+        let m = &self.rows;
+        Vec4f64::new([m[0][index], m[1][index], m[2][index], m[3][index]])
     }
-}
 
-impl Mul<f64> for Mat4f64 {
-    type Output = Mat4f64;
-    fn mul(mut self, scalar: f64) -> Self::Output {
-        self[0][0] *= scalar;
-        self[0][1] *= scalar;
-        self[0][2] *= scalar;
-        self[0][3] *= scalar;
-        self[1][0] *= scalar;
-        self[1][1] *= scalar;
-        self[1][2] *= scalar;
-        self[1][3] *= scalar;
-        self[2][0] *= scalar;
-        self[2][1] *= scalar;
-        self[2][2] *= scalar;
-        self[2][3] *= scalar;
-        self[3][0] *= scalar;
-        self[3][1] *= scalar;
-        self[3][2] *= scalar;
-        self[3][3] *= scalar;
-        self
+    /// Replaces the row at index with row.
+    pub const fn set_row(&mut self, index: usize, row: Vec4f64) {
+        self.rows[index] = row.coords;
     }
-}
 
-impl Mul<Mat4f64> for f64 {
-    type Output = Mat4f64;
-    fn mul(self, m: Mat4f64) -> Self::Output {
-        m * self
+    /// Replaces the column at index with col.
+    pub const fn set_col(&mut self, index: usize, col: Vec4f64) {
+        self.rows[0][index] = col.coords[0];
+        self.rows[1][index] = col.coords[1];
+        self.rows[2][index] = col.coords[2];
+        self.rows[3][index] = col.coords[3];
     }
-}
 
-impl MulAssign<f64> for Mat4f64 {
-    fn mul_assign(&mut self, scalar: f64) {
-        *self = *self * scalar;
+    /// Returns the elements on the main diagonal as a vector.
+    pub const fn diagonal(&self) -> Vec4f64 {
+        let m = &self.rows;
+        Vec4f64::new([m[0][0], m[1][1], m[2][2], m[3][3]])
     }
-}
 
-impl Div<f64> for Mat4f64 {
-    type Output = Mat4f64;
-    fn div(mut self, scalar: f64) -> Self::Output {
-        self[0][0] /= scalar;
-        self[0][1] /= scalar;
-        self[0][2] /= scalar;
-        self[0][3] /= scalar;
-        self[1][0] /= scalar;
-        self[1][1] /= scalar;
-        self[1][2] /= scalar;
-        self[1][3] /= scalar;
-        self[2][0] /= scalar;
-        self[2][1] /= scalar;
-        self[2][2] /= scalar;
-        self[2][3] /= scalar;
-        self[3][0] /= scalar;
-        self[3][1] /= scalar;
-        self[3][2] /= scalar;
-        self[3][3] /= scalar;
-        self
+    /// Returns the trace of self (the sum of the main diagonal).
+    pub const fn trace(&self) -> f64 {
+        let m = &self.rows;
+        m[0][0] + m[1][1] + m[2][2] + m[3][3]
     }
-}
 
-impl DivAssign<f64> for Mat4f64 {
-    fn div_assign(&mut self, scalar: f64) {
-        *self = *self / scalar;
+    /// The Frobenius norm of self
+    /// (the square root of the sum of all squared elements).
+    pub fn frobenius_norm(&self) -> f64 {
+        self.frobenius_norm_squared().sqrt()
     }
-}
 
-impl Mul<Mat4f64> for Mat4f64 {
-    type Output = Mat4f64;
-    fn mul(self, b: Mat4f64) -> Self::Output {
-        let a = self;
+    /// The Frobenius norm of self, but squared.
+    /// This is faster to compute than frobenius_norm() and useful in some situations.
+    pub const fn frobenius_norm_squared(&self) -> f64 {
+        let m = &self.rows;
+        m[0][0] * m[0][0]
+            + m[0][1] * m[0][1]
+            + m[0][2] * m[0][2]
+            + m[0][3] * m[0][3]
+            + m[1][0] * m[1][0]
+            + m[1][1] * m[1][1]
+            + m[1][2] * m[1][2]
+            + m[1][3] * m[1][3]
+            + m[2][0] * m[2][0]
+            + m[2][1] * m[2][1]
+            + m[2][2] * m[2][2]
+            + m[2][3] * m[2][3]
+            + m[3][0] * m[3][0]
+            + m[3][1] * m[3][1]
+            + m[3][2] * m[3][2]
+            + m[3][3] * m[3][3]
+    }
+
+    /// Creates a new translation matrix for homogeneous coordinates.
+    pub const fn from_translation(translation: Vec3f64) -> Self {
+        let t = &translation.coords;
+        Self::new([
+            [1.0, 0.0, 0.0, t[0]],
+            [0.0, 1.0, 0.0, t[1]],
+            [0.0, 0.0, 1.0, t[2]],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Creates a new scaling matrix for homogeneous coordinates.
+    pub const fn from_scale(scale: Vec3f64) -> Self {
+        let s = &scale.coords;
+        Self::new([
+            [s[0], 0.0, 0.0, 0.0],
+            [0.0, s[1], 0.0, 0.0],
+            [0.0, 0.0, s[2], 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Creates a new matrix rotating around the x axis for homogeneous coordinates.
+    /// Positive angles (in radians) rotate counterclockwise when looking down the axis
+    /// towards the origin (right hand rule).
+    pub fn from_rotation_x(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, cos, -sin, 0.0],
+            [0.0, sin, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Creates a new matrix rotating around the y axis for homogeneous coordinates.
+    /// Positive angles (in radians) rotate counterclockwise when looking down the axis
+    /// towards the origin (right hand rule).
+    pub fn from_rotation_y(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new([
+            [cos, 0.0, sin, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [-sin, 0.0, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Creates a new matrix rotating around the z axis for homogeneous coordinates.
+    /// Positive angles (in radians) rotate counterclockwise when looking down the axis
+    /// towards the origin (right hand rule).
+    pub fn from_rotation_z(angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new([
+            [cos, -sin, 0.0, 0.0],
+            [sin, cos, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Creates a new matrix rotating around an arbitrary axis for homogeneous coordinates.
+    /// The axis has to be a unit vector.
+    /// Positive angles (in radians) rotate counterclockwise when looking down the axis
+    /// towards the origin (right hand rule).
+    pub fn from_axis_angle(axis: Vec3f64, angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        let t = 1.0 - cos;
+        let [x, y, z] = axis.coords;
         Self::new([
             [
-                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0] + a[0][3] * b[3][0],
-                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1] + a[0][3] * b[3][1],
-                a[0][0] * b[0][2] + a[0][1] * b[1][2] + a[0][2] * b[2][2] + a[0][3] * b[3][2],
-                a[0][0] * b[0][3] + a[0][1] * b[1][3] + a[0][2] * b[2][3] + a[0][3] * b[3][3],
-            ],
-            [
-                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0] + a[1][3] * b[3][0],
-                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1] + a[1][3] * b[3][1],
-                a[1][0] * b[0][2] + a[1][1] * b[1][2] + a[1][2] * b[2][2] + a[1][3] * b[3][2],
-                a[1][0] * b[0][3] + a[1][1] * b[1][3] + a[1][2] * b[2][3] + a[1][3] * b[3][3],
+                t * x * x + cos,
+                t * x * y - sin * z,
+                t * x * z + sin * y,
+                0.0,
             ],
             [
-                a[2][0] * b[0][0] + a[2][1] * b[1][0] + a[2][2] * b[2][0] + a[2][3] * b[3][0],
-                a[2][0] * b[0][1] + a[2][1] * b[1][1] + a[2][2] * b[2][1] + a[2][3] * b[3][1],
-                a[2][0] * b[0][2] + a[2][1] * b[1][2] + a[2][2] * b[2][2] + a[2][3] * b[3][2],
-                a[2][0] * b[0][3] + a[2][1] * b[1][3] + a[2][2] * b[2][3] + a[2][3] * b[3][3],
+                t * x * y + sin * z,
+                t * y * y + cos,
+                t * y * z - sin * x,
+                0.0,
             ],
             [
-                a[3][0] * b[0][0] + a[3][1] * b[1][0] + a[3][2] * b[2][0] + a[3][3] * b[3][0],
-                a[3][0] * b[0][1] + a[3][1] * b[1][1] + a[3][2] * b[2][1] + a[3][3] * b[3][1],
-                a[3][0] * b[0][2] + a[3][1] * b[1][2] + a[3][2] * b[2][2] + a[3][3] * b[3][2],
-                a[3][0] * b[0][3] + a[3][1] * b[1][3] + a[3][2] * b[2][3] + a[3][3] * b[3][3],
+                t * x * z - sin * y,
+                t * y * z + sin * x,
+                t * z * z + cos,
+                0.0,
             ],
+            [0.0, 0.0, 0.0, 1.0],
         ])
     }
-}
 
-impl MulAssign<Mat4f64> for Mat4f64 {
-    fn mul_assign(&mut self, rhs: Mat4f64) {
-        *self = *self * rhs;
+    /// Creates a new shear matrix for homogeneous coordinates.
+    /// Each factor is named after the coordinate it changes and the coordinate it
+    /// is proportional to, e.g. the resulting x coordinate is x + x_by_y * y + x_by_z * z.
+    pub const fn from_shear(
+        x_by_y: f64,
+        x_by_z: f64,
+        y_by_x: f64,
+        y_by_z: f64,
+        z_by_x: f64,
+        z_by_y: f64,
+    ) -> Self {
+        Self::new([
+            [1.0, x_by_y, x_by_z, 0.0],
+            [y_by_x, 1.0, y_by_z, 0.0],
+            [z_by_x, z_by_y, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
     }
-}
 
-impl Mul<Vec4f64> for Mat4f64 {
-    type Output = Vec4f64;
-    fn mul(self, v: Vec4f64) -> Self::Output {
-        Vec4f64::new([
-            self[0][0] * v[0] + self[0][1] * v[1] + self[0][2] * v[2] + self[0][3] * v[3],
-            self[1][0] * v[0] + self[1][1] * v[1] + self[1][2] * v[2] + self[1][3] * v[3],
-            self[2][0] * v[0] + self[2][1] * v[1] + self[2][2] * v[2] + self[2][3] * v[3],
-            self[3][0] * v[0] + self[3][1] * v[1] + self[3][2] * v[2] + self[3][3] * v[3],
-        ])
+    /// Creates a new matrix reflecting across a plane for homogeneous coordinates.
+    /// The plane contains the points p with
+    /// plane[0] * p[0] + plane[1] * p[1] + plane[2] * p[2] + plane[3] = 0,
+    /// its normal (the first three components) does not have to be normalized.
+    pub const fn from_reflection(plane: Vec4f64) -> Self {
+        Self::from_plane_offset(plane, 2.0)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::vec::vec4f64::Vec4f64;
+    /// Creates a new matrix projecting orthogonally onto a plane for homogeneous coordinates.
+    /// The plane is given like in from_reflection().
+    pub const fn from_plane_projection(plane: Vec4f64) -> Self {
+        Self::from_plane_offset(plane, 1.0)
+    }
 
-    use super::Mat4f64;
+    /// Moves points along the plane normal by factor times their signed distance to the plane,
+    /// I - factor * n * plane^T / |n|^2 where n is the plane normal.
+    const fn from_plane_offset(plane: Vec4f64, factor: f64) -> Self {
+        let p = &plane.coords;
+        let normal = Vec4f64::new([p[0], p[1], p[2], 0.0]);
+        let offset = Self::outer_product(normal, plane).scaled(factor / normal.mag_squared());
+        Self::identity().difference(offset)
+    }
 
-    #[test]
-    fn matrix_creation() {
-        let zero = Mat4f64::zero();
-        let id = Mat4f64::identity();
-        let m = Mat4f64::new([
-            [1.0, 2.0, 3.0, 4.0],
-            [5.0, 6.0, 7.0, 8.0],
-            [9.0, 10.0, 11.0, 12.0],
-            [13.0, 14.0, 15.0, 16.0],
-        ]);
-        let t = Mat4f64::new_from_cols([
-            [1.0, 5.0, 9.0, 13.0],
-            [2.0, 6.0, 10.0, 14.0],
-            [3.0, 7.0, 11.0, 15.0],
-            [4.0, 8.0, 12.0, 16.0],
-        ]);
+    /// Creates a new matrix projecting points onto a plane as seen from a point light,
+    /// for rendering planar shadows. The plane is given like in from_reflection().
+    /// The result needs a perspective division (see project_point3()).
+    pub const fn from_point_shadow(plane: Vec4f64, light_position: Vec3f64) -> Self {
+        let l = &light_position.coords;
+        Self::from_shadow(plane, Vec4f64::new([l[0], l[1], l[2], 1.0]))
+    }
 
-        assert_eq!(zero[0][0], 0.0);
-        assert_eq!(zero[0][1], 0.0);
-        assert_eq!(zero[0][2], 0.0);
-        assert_eq!(zero[0][3], 0.0);
-        assert_eq!(zero[1][0], 0.0);
-        assert_eq!(zero[1][1], 0.0);
-        assert_eq!(zero[1][2], 0.0);
-        assert_eq!(zero[1][3], 0.0);
-        assert_eq!(zero[2][0], 0.0);
-        assert_eq!(zero[2][1], 0.0);
-        assert_eq!(zero[2][2], 0.0);
-        assert_eq!(zero[2][3], 0.0);
-        assert_eq!(zero[3][0], 0.0);
-        assert_eq!(zero[3][1], 0.0);
-        assert_eq!(zero[3][2], 0.0);
-        assert_eq!(zero[3][3], 0.0);
+    /// Creates a new matrix projecting points onto a plane along the direction of a
+    /// directional light, for rendering planar shadows. The plane is given like in
+    /// from_reflection(). The result needs a perspective division (see project_point3()).
+    pub const fn from_directional_shadow(plane: Vec4f64, light_direction: Vec3f64) -> Self {
+        let l = &light_direction.coords;
+        Self::from_shadow(plane, Vec4f64::new([l[0], l[1], l[2], 0.0]))
+    }
 
-        assert_eq!(id[0][0], 1.0);
-        assert_eq!(id[0][1], 0.0);
-        assert_eq!(id[0][2], 0.0);
-        assert_eq!(id[0][3], 0.0);
-        assert_eq!(id[1][0], 0.0);
-        assert_eq!(id[1][1], 1.0);
-        assert_eq!(id[1][2], 0.0);
-        assert_eq!(id[1][3], 0.0);
-        assert_eq!(id[2][0], 0.0);
-        assert_eq!(id[2][1], 0.0);
-        assert_eq!(id[2][2], 1.0);
-        assert_eq!(id[2][3], 0.0);
-        assert_eq!(id[3][0], 0.0);
-        assert_eq!(id[3][1], 0.0);
-        assert_eq!(id[3][2], 0.0);
-        assert_eq!(id[3][3], 1.0);
+    /// Projects onto plane from the homogeneous light position,
+    /// (plane . light) * I - light * plane^T.
+    const fn from_shadow(plane: Vec4f64, light: Vec4f64) -> Self {
+        Self::identity()
+            .scaled(plane.dot(light))
+            .difference(Self::outer_product(light, plane))
+    }
 
-        assert_eq!(m[0][0], 1.0);
-        assert_eq!(m[0][1], 2.0);
-        assert_eq!(m[0][2], 3.0);
-        assert_eq!(m[0][3], 4.0);
-        assert_eq!(m[1][0], 5.0);
-        assert_eq!(m[1][1], 6.0);
-        assert_eq!(m[1][2], 7.0);
-        assert_eq!(m[1][3], 8.0);
-        assert_eq!(m[2][0], 9.0);
-        assert_eq!(m[2][1], 10.0);
-        assert_eq!(m[2][2], 11.0);
-        assert_eq!(m[2][3], 12.0);
-        assert_eq!(m[3][0], 13.0);
-        assert_eq!(m[3][1], 14.0);
-        assert_eq!(m[3][2], 15.0);
-        assert_eq!(m[3][3], 16.0);
+    /// Returns self followed by a translation (translation * self).
+    pub const fn translate(&self, translation: Vec3f64) -> Self {
+        Self::from_translation(translation).product(*self)
+    }
 
-        assert_eq!(t[0][0], 1.0);
-        assert_eq!(t[0][1], 2.0);
-        assert_eq!(t[0][2], 3.0);
-        assert_eq!(t[0][3], 4.0);
-        assert_eq!(t[1][0], 5.0);
-        assert_eq!(t[1][1], 6.0);
-        assert_eq!(t[1][2], 7.0);
-        assert_eq!(t[1][3], 8.0);
-        assert_eq!(t[2][0], 9.0);
-        assert_eq!(t[2][1], 10.0);
-        assert_eq!(t[2][2], 11.0);
-        assert_eq!(t[2][3], 12.0);
-        assert_eq!(t[3][0], 13.0);
-        assert_eq!(t[3][1], 14.0);
-        assert_eq!(t[3][2], 15.0);
-        assert_eq!(t[3][3], 16.0);
+    /// Returns self followed by a scaling (scale * self).
+    pub const fn scale(&self, scale: Vec3f64) -> Self {
+        Self::from_scale(scale).product(*self)
     }
 
-    #[test]
-    fn transpose() {
-        let mut m = Mat4f64::new([
-            [1.0, 2.0, 3.0, 4.0],
-            [5.0, 6.0, 7.0, 8.0],
-            [9.0, 10.0, 11.0, 12.0],
-            [13.0, 14.0, 15.0, 16.0],
-        ]);
-        let t = m.transposed();
-        m.transpose();
+    /// Returns self followed by a rotation around the x axis (rotation * self).
+    pub fn rotate_x(&self, angle: f64) -> Self {
+        Self::from_rotation_x(angle) * *self
+    }
 
-        assert_eq!(t[0][0], 1.0);
-        assert_eq!(t[0][1], 5.0);
-        assert_eq!(t[0][2], 9.0);
-        assert_eq!(t[0][3], 13.0);
-        assert_eq!(t[1][0], 2.0);
-        assert_eq!(t[1][1], 6.0);
-        assert_eq!(t[1][2], 10.0);
-        assert_eq!(t[1][3], 14.0);
-        assert_eq!(t[2][0], 3.0);
-        assert_eq!(t[2][1], 7.0);
-        assert_eq!(t[2][2], 11.0);
-        assert_eq!(t[2][3], 15.0);
-        assert_eq!(t[3][0], 4.0);
-        assert_eq!(t[3][1], 8.0);
-        assert_eq!(t[3][2], 12.0);
-        assert_eq!(t[3][3], 16.0);
+    /// Returns self followed by a rotation around the y axis (rotation * self).
+    pub fn rotate_y(&self, angle: f64) -> Self {
+        Self::from_rotation_y(angle) * *self
+    }
 
-        assert_eq!(m[0][0], 1.0);
-        assert_eq!(m[0][1], 5.0);
-        assert_eq!(m[0][2], 9.0);
-        assert_eq!(m[0][3], 13.0);
-        assert_eq!(m[1][0], 2.0);
-        assert_eq!(m[1][1], 6.0);
-        assert_eq!(m[1][2], 10.0);
-        assert_eq!(m[1][3], 14.0);
-        assert_eq!(m[2][0], 3.0);
-        assert_eq!(m[2][1], 7.0);
-        assert_eq!(m[2][2], 11.0);
-        assert_eq!(m[2][3], 15.0);
-        assert_eq!(m[3][0], 4.0);
-        assert_eq!(m[3][1], 8.0);
-        assert_eq!(m[3][2], 12.0);
-        assert_eq!(m[3][3], 16.0);
+    /// Returns self followed by a rotation around the z axis (rotation * self).
+    pub fn rotate_z(&self, angle: f64) -> Self {
+        Self::from_rotation_z(angle) * *self
     }
 
-    #[test]
-    fn as_row_and_col_major() {
-        let m = Mat4f64::new([
-            [1.0, 2.0, 3.0, 4.0],
-            [5.0, 6.0, 7.0, 8.0],
-            [9.0, 10.0, 11.0, 12.0],
-            [13.0, 14.0, 15.0, 16.0],
-        ]);
-        let cols = m.as_col_major();
-        let rows = m.as_row_major();
+    /// Returns self followed by a rotation around an arbitrary unit axis (rotation * self).
+    pub fn rotate(&self, axis: Vec3f64, angle: f64) -> Self {
+        Self::from_axis_angle(axis, angle) * *self
+    }
 
-        assert_eq!(cols[0][0], 1.0);
-        assert_eq!(cols[0][1], 5.0);
-        assert_eq!(cols[0][2], 9.0);
-        assert_eq!(cols[0][3], 13.0);
-        assert_eq!(cols[1][0], 2.0);
-        assert_eq!(cols[1][1], 6.0);
+    /// Returns self followed by a shear (shear * self).
+    /// See from_shear() for the meaning of the factors.
+    pub const fn shear(
+        &self,
+        x_by_y: f64,
+        x_by_z: f64,
+        y_by_x: f64,
+        y_by_z: f64,
+        z_by_x: f64,
+        z_by_y: f64,
+    ) -> Self {
+        Self::from_shear(x_by_y, x_by_z, y_by_x, y_by_z, z_by_x, z_by_y).product(*self)
+    }
+
+    /// Creates a new perspective projection matrix from a vertical field of view
+    /// (in radians) and an aspect ratio (width / height).
+    /// Near and far are positive distances from the camera,
+    /// far may be f64::INFINITY for an infinite far plane.
+    pub fn perspective(
+        fov_y: f64,
+        aspect: f64,
+        near: f64,
+        far: f64,
+        convention: ProjectionConvention,
+    ) -> Self {
+        let top = near * (fov_y / 2.0).tan();
+        let right = top * aspect;
+        Self::frustum(-right, right, -top, top, near, far, convention)
+    }
+
+    /// Creates a new off-axis perspective projection matrix from the tangents of the
+    /// angles between the viewing direction and the four sides of the frustum,
+    /// like the per eye field of view of VR headsets.
+    /// The tangents are signed, left and down are usually negative.
+    /// Near and far are positive distances from the camera,
+    /// far may be f64::INFINITY for an infinite far plane.
+    pub const fn perspective_off_axis(
+        tan_left: f64,
+        tan_right: f64,
+        tan_down: f64,
+        tan_up: f64,
+        near: f64,
+        far: f64,
+        convention: ProjectionConvention,
+    ) -> Self {
+        Self::frustum(
+            tan_left * near,
+            tan_right * near,
+            tan_down * near,
+            tan_up * near,
+            near,
+            far,
+            convention,
+        )
+    }
+
+    /// Creates a new perspective projection matrix from the bounds of the near plane.
+    /// Near and far are positive distances from the camera,
+    /// far may be f64::INFINITY for an infinite far plane.
+    pub const fn frustum(
+        left: f64,
+        right: f64,
+        bottom: f64,
+        top: f64,
+        near: f64,
+        far: f64,
+        convention: ProjectionConvention,
+    ) -> Self {
+        let (forward, ndc_near, ndc_far) = Self::projection_factors(convention);
+        // Clip space z is depth_scale * depth + depth_offset, with w = depth
+        let (depth_scale, depth_offset) = if far.is_infinite() {
+            (ndc_far, (ndc_near - ndc_far) * near)
+        } else {
+            (
+                (ndc_far * far - ndc_near * near) / (far - near),
+                (ndc_near - ndc_far) * near * far / (far - near),
+            )
+        };
+        Self::new([
+            [
+                2.0 * near / (right - left),
+                0.0,
+                -forward * (right + left) / (right - left),
+                0.0,
+            ],
+            [
+                0.0,
+                2.0 * near / (top - bottom),
+                -forward * (top + bottom) / (top - bottom),
+                0.0,
+            ],
+            [0.0, 0.0, forward * depth_scale, depth_offset],
+            [0.0, 0.0, forward, 0.0],
+        ])
+    }
+
+    /// Creates a new orthographic projection matrix from the bounds of the view volume.
+    /// Near and far are distances from the camera and have to be finite.
+    pub const fn orthographic(
+        left: f64,
+        right: f64,
+        bottom: f64,
+        top: f64,
+        near: f64,
+        far: f64,
+        convention: ProjectionConvention,
+    ) -> Self {
+        let (forward, ndc_near, ndc_far) = Self::projection_factors(convention);
+        Self::new([
+            [
+                2.0 / (right - left),
+                0.0,
+                0.0,
+                -(right + left) / (right - left),
+            ],
+            [
+                0.0,
+                2.0 / (top - bottom),
+                0.0,
+                -(top + bottom) / (top - bottom),
+            ],
+            [
+                0.0,
+                0.0,
+                forward * (ndc_far - ndc_near) / (far - near),
+                (ndc_near * far - ndc_far * near) / (far - near),
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Returns the view space z direction the camera looks at (1.0 or -1.0)
+    /// and the normalized device depth of the near and far plane for a convention.
+    const fn projection_factors(convention: ProjectionConvention) -> (f64, f64, f64) {
+        let forward = match convention.handedness {
+            Handedness::Right => -1.0,
+            Handedness::Left => 1.0,
+        };
+        let (ndc_near, ndc_far) = match convention.depth_range {
+            DepthRange::NegativeOneToOne => (-1.0, 1.0),
+            DepthRange::ZeroToOne => (0.0, 1.0),
+        };
+        if convention.reversed_z {
+            (forward, ndc_far, ndc_near)
+        } else {
+            (forward, ndc_near, ndc_far)
+        }
+    }
+
+    /// Returns the projection matrix for one tile of self split into a grid of
+    /// columns x rows tiles, each rendering its part of the image of self at full resolution.
+    /// Column 0 is on the left and row 0 at the bottom (normalized device y points up).
+    /// Depth is not affected.
+    pub const fn tile(&self, columns: usize, rows: usize, column: usize, row: usize) -> Self {
+        // Maps the normalized device rectangle of the tile to -1..1
+        let x_offset = columns as f64 - 1.0 - 2.0 * column as f64;
+        let y_offset = rows as f64 - 1.0 - 2.0 * row as f64;
+        Self::new([
+            [columns as f64, 0.0, 0.0, x_offset],
+            [0.0, rows as f64, 0.0, y_offset],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+        .product(*self)
+    }
+
+    /// Returns the projection matrix self with its near plane replaced by an arbitrary
+    /// clip plane (Lengyel's oblique near-plane clipping), for example for portals and
+    /// reflections. The plane is given in view space like in from_reflection(), points on
+    /// its positive side are kept and the camera has to be on its negative side.
+    /// The far plane is moved to keep the frustum valid, which reduces the depth precision
+    /// the more the clip plane is tilted. Convention has to be the one self was created with.
+    pub fn with_oblique_near_plane(
+        &self,
+        plane: Vec4f64,
+        convention: ProjectionConvention,
+    ) -> Self {
+        let (_, ndc_near, ndc_far) = Self::projection_factors(convention);
+        // The corner of the clip space frustum opposite to the plane, which stays on the far plane
+        let clip_plane = self.transform_plane(plane);
+        let corner = Vec4f64::new([clip_plane[0].signum(), clip_plane[1].signum(), ndc_far, 1.0]);
+        let scale = (ndc_far - ndc_near) / clip_plane.dot(corner);
+        let mut oblique = *self;
+        oblique.set_row(2, ndc_near * self.row(3) + scale * plane);
+        oblique
+    }
+
+    /// Creates a new view matrix for a camera at eye looking at target.
+    /// Up is the approximate up direction of the camera and must not be parallel
+    /// to the viewing direction.
+    pub fn look_at(eye: Vec3f64, target: Vec3f64, up: Vec3f64, handedness: Handedness) -> Self {
+        Self::look_to(eye, target - eye, up, handedness)
+    }
+
+    /// Creates a new view matrix for a camera at eye looking in direction dir.
+    /// Up is the approximate up direction of the camera and must not be parallel to dir.
+    pub fn look_to(eye: Vec3f64, dir: Vec3f64, up: Vec3f64, handedness: Handedness) -> Self {
+        // The view space z axis points away from the viewing direction in right handed
+        // view spaces and towards it in left handed ones.
+        let z = match handedness {
+            Handedness::Right => -1.0 * dir.normed(),
+            Handedness::Left => dir.normed(),
+        };
+        let x = up.cross(z).normed();
+        let y = z.cross(x);
+        Self::new_from_cols([
+            [x[0], y[0], z[0], 0.0],
+            [x[1], y[1], z[1], 0.0],
+            [x[2], y[2], z[2], 0.0],
+            [-x.dot(eye), -y.dot(eye), -z.dot(eye), 1.0],
+        ])
+    }
+
+    /// Returns the camera position of a view matrix.
+    /// Self has to be a rigid transformation, like the matrices created by look_at().
+    pub const fn camera_position(&self) -> Vec3f64 {
+        let m = &self.rows;
+        Vec3f64::new([
+            -(m[0][0] * m[0][3] + m[1][0] * m[1][3] + m[2][0] * m[2][3]),
+            -(m[0][1] * m[0][3] + m[1][1] * m[1][3] + m[2][1] * m[2][3]),
+            -(m[0][2] * m[0][3] + m[1][2] * m[1][3] + m[2][2] * m[2][3]),
+        ])
+    }
+
+    /// Returns the world space direction the camera of a view matrix looks at.
+    /// Self has to be a rigid transformation, like the matrices created by look_at().
+    pub const fn camera_forward(&self, handedness: Handedness) -> Vec3f64 {
+        let m = &self.rows;
+        match handedness {
+            Handedness::Right => Vec3f64::new([-m[2][0], -m[2][1], -m[2][2]]),
+            Handedness::Left => Vec3f64::new([m[2][0], m[2][1], m[2][2]]),
+        }
+    }
+
+    /// Returns the world space right direction of the camera of a view matrix.
+    /// Self has to be a rigid transformation, like the matrices created by look_at().
+    pub const fn camera_right(&self) -> Vec3f64 {
+        let m = &self.rows;
+        Vec3f64::new([m[0][0], m[0][1], m[0][2]])
+    }
+
+    /// Returns the world space up direction of the camera of a view matrix.
+    /// Self has to be a rigid transformation, like the matrices created by look_at().
+    pub const fn camera_up(&self) -> Vec3f64 {
+        let m = &self.rows;
+        Vec3f64::new([m[1][0], m[1][1], m[1][2]])
+    }
+
+    /// Transforms a point (w = 1) by self and returns the x, y and z coordinates.
+    /// The resulting w coordinate is ignored, which is correct for affine
+    /// transformations. Use project_point3() for projective transformations.
+    pub const fn transform_point3(&self, point: Vec3f64) -> Vec3f64 {
+        let m = &self.rows;
+        let p = &point.coords;
+        Vec3f64::new([
+            m[0][0] * p[0] + m[0][1] * p[1] + m[0][2] * p[2] + m[0][3],
+            m[1][0] * p[0] + m[1][1] * p[1] + m[1][2] * p[2] + m[1][3],
+            m[2][0] * p[0] + m[2][1] * p[1] + m[2][2] * p[2] + m[2][3],
+        ])
+    }
+
+    /// Transforms a direction (w = 0) by self and returns the x, y and z coordinates.
+    /// Directions are not affected by the translation of self.
+    pub const fn transform_vector3(&self, vector: Vec3f64) -> Vec3f64 {
+        let m = &self.rows;
+        let v = &vector.coords;
+        Vec3f64::new([
+            m[0][0] * v[0] + m[0][1] * v[1] + m[0][2] * v[2],
+            m[1][0] * v[0] + m[1][1] * v[1] + m[1][2] * v[2],
+            m[2][0] * v[0] + m[2][1] * v[1] + m[2][2] * v[2],
+        ])
+    }
+
+    /// Transforms a point (w = 1) by self and divides the result by its w coordinate
+    /// (perspective divide). Use this with projection matrices.
+    pub const fn project_point3(&self, point: Vec3f64) -> Vec3f64 {
+        let m = &self.rows;
+        let p = &point.coords;
+        let w = m[3][0] * p[0] + m[3][1] * p[1] + m[3][2] * p[2] + m[3][3];
+        self.transform_point3(point).divided(w)
+    }
+
+    /// Transforms a plane by self, using the inverse transpose of self.
+    /// The plane is given like in from_reflection(), if a point p is on the plane,
+    /// self * p is on the returned plane. The normal is not normalized.
+    pub const fn transform_plane(&self, plane: Vec4f64) -> Vec4f64 {
+        self.inverse().transposed().product_vec(plane)
+    }
+
+    /// Creates a new matrix from a flat array of elements in row major order.
+    pub const fn from_row_major_flat(elements: [f64; 16]) -> Self {
+        let e = &elements;
+        Self::new([
+            [e[0], e[1], e[2], e[3]],
+            [e[4], e[5], e[6], e[7]],
+            [e[8], e[9], e[10], e[11]],
+            [e[12], e[13], e[14], e[15]],
+        ])
+    }
+
+    /// Creates a new matrix from a flat array of elements in column major order.
+    pub const fn from_col_major_flat(elements: [f64; 16]) -> Self {
+        let e = &elements;
+        Self::new([
+            [e[0], e[4], e[8], e[12]],
+            [e[1], e[5], e[9], e[13]],
+            [e[2], e[6], e[10], e[14]],
+            [e[3], e[7], e[11], e[15]],
+        ])
+    }
+
+    /// Returns all elements as a flat array in row major order.
+    pub const fn as_row_major_flat(&self) -> [f64; 16] {
+        let m = &self.rows;
+        [
+            m[0][0], m[0][1], m[0][2], m[0][3], m[1][0], m[1][1], m[1][2], m[1][3], m[2][0],
+            m[2][1], m[2][2], m[2][3], m[3][0], m[3][1], m[3][2], m[3][3],
+        ]
+    }
+
+    /// Returns all elements as a flat array in column major order.
+    /// This is the layout most graphics APIs expect for uniform uploads.
+    pub const fn as_col_major_flat(&self) -> [f64; 16] {
+        let m = &self.rows;
+        [
+            m[0][0], m[1][0], m[2][0], m[3][0], m[0][1], m[1][1], m[2][1], m[3][1], m[0][2],
+            m[1][2], m[2][2], m[3][2], m[0][3], m[1][3], m[2][3], m[3][3],
+        ]
+    }
+
+    /// Returns self converted to single precision.
+    /// Elements are rounded to the nearest representable f32 value.
+    pub const fn to_f32(&self) -> Mat4f32 {
+        let m = &self.rows;
+        Mat4f32::new([
+            [
+                m[0][0] as f32,
+                m[0][1] as f32,
+                m[0][2] as f32,
+                m[0][3] as f32,
+            ],
+            [
+                m[1][0] as f32,
+                m[1][1] as f32,
+                m[1][2] as f32,
+                m[1][3] as f32,
+            ],
+            [
+                m[2][0] as f32,
+                m[2][1] as f32,
+                m[2][2] as f32,
+                m[2][3] as f32,
+            ],
+            [
+                m[3][0] as f32,
+                m[3][1] as f32,
+                m[3][2] as f32,
+                m[3][3] as f32,
+            ],
+        ])
+    }
+
+    /// Creates a new matrix with m in the upper left corner
+    /// and the remaining elements taken from the identity matrix.
+    pub const fn from_mat3(m: Mat3f64) -> Self {
+        let m = &m.rows;
+        Self::new([
+            [m[0][0], m[0][1], m[0][2], 0.0],
+            [m[1][0], m[1][1], m[1][2], 0.0],
+            [m[2][0], m[2][1], m[2][2], 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Returns the upper left 3x3 block of self.
+    pub const fn upper_left(&self) -> Mat3f64 {
+        let m = &self.rows;
+        Mat3f64::new([
+            [m[0][0], m[0][1], m[0][2]],
+            [m[1][0], m[1][1], m[1][2]],
+            [m[2][0], m[2][1], m[2][2]],
+        ])
+    }
+
+    /// Creates a new transformation matrix that scales, then rotates, then translates
+    /// (translation * rotation * scale). Rotation has to be a rotation matrix.
+    pub const fn from_trs(translation: Vec3f64, rotation: Mat3f64, scale: Vec3f64) -> Self {
+        let t = &translation.coords;
+        let r = &rotation.rows;
+        let s = &scale.coords;
+        Self::new([
+            [r[0][0] * s[0], r[0][1] * s[1], r[0][2] * s[2], t[0]],
+            [r[1][0] * s[0], r[1][1] * s[1], r[1][2] * s[2], t[1]],
+            [r[2][0] * s[0], r[2][1] * s[1], r[2][2] * s[2], t[2]],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Decomposes an affine transformation into translation, rotation, scale and shear,
+    /// so that self equals translation * rotation * scale * shear.
+    /// Mirroring (a negative determinant) is expressed as a negative x scale,
+    /// so the rotation always has a determinant of +1.
+    /// An axis that is zero or linearly dependent on the previous axes gets a scale of 0.0
+    /// and a rotation axis orthogonal to the other ones (see is_degenerate()).
+    /// The last row of self is kept in last_row, is_lossy() reports if it is not 0, 0, 0, 1.
+    pub fn decompose(&self) -> Mat4f64Decomposition {
+        let m = &self.rows;
+        let mirrored = self.upper_left().determinant() < 0.0;
+        let mut columns = [
+            Vec3f64::new([m[0][0], m[1][0], m[2][0]]),
+            Vec3f64::new([m[0][1], m[1][1], m[2][1]]),
+            Vec3f64::new([m[0][2], m[1][2], m[2][2]]),
+        ];
+        if mirrored {
+            columns[0] = -1.0 * columns[0];
+        }
+        // Axes shorter than this after orthogonalization are treated as zero
+        let threshold = f64::EPSILON * columns[0].mag().max(columns[1].mag()).max(columns[2].mag());
+
+        // Gram-Schmidt orthonormalization of the basis vectors, skipping degenerate axes.
+        // components[i][k] is the component of column k along axis i.
+        let mut basis = [Vec3f64::zero(); 3];
+        let mut scale = [0.0; 3];
+        let mut components = [[0.0; 3]; 3];
+        let mut degenerate = [false; 3];
+        for k in 0..3 {
+            let mut v = columns[k];
+            for i in 0..k {
+                if !degenerate[i] {
+                    components[i][k] = basis[i].dot(v);
+                    v -= components[i][k] * basis[i];
+                }
+            }
+            let length = v.mag();
+            if length > threshold {
+                basis[k] = v / length;
+                scale[k] = length;
+            } else {
+                degenerate[k] = true;
+            }
+        }
+
+        // Degenerate axes have a scale of 0.0, so any direction orthogonal to the other
+        // axes reproduces self. Use the coordinate axis that is the least parallel to them.
+        let mut complete = [!degenerate[0], !degenerate[1], !degenerate[2]];
+        for k in 0..3 {
+            if complete[k] {
+                continue;
+            }
+            let mut best = Vec3f64::zero();
+            for axis in [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]] {
+                let mut v = Vec3f64::new(axis);
+                for i in 0..3 {
+                    if complete[i] {
+                        v -= basis[i].dot(v) * basis[i];
+                    }
+                }
+                if v.mag_squared() > best.mag_squared() {
+                    best = v;
+                }
+            }
+            basis[k] = best.normed();
+            complete[k] = true;
+        }
+        let mut rotation = Mat3f64::from_cols(basis);
+        if rotation.determinant() < 0.0 {
+            // Only possible with a degenerate axis, whose direction does not matter
+            let k = if degenerate[0] {
+                0
+            } else if degenerate[1] {
+                1
+            } else {
+                2
+            };
+            rotation.set_col(k, -1.0 * basis[k]);
+        }
+
+        let shear = |i: usize, k: usize| {
+            if degenerate[i] {
+                0.0
+            } else {
+                components[i][k] / scale[i]
+            }
+        };
+        let sign = if mirrored { -1.0 } else { 1.0 };
+        Mat4f64Decomposition {
+            translation: Vec3f64::new([m[0][3], m[1][3], m[2][3]]),
+            rotation,
+            scale: Vec3f64::new([sign * scale[0], scale[1], scale[2]]),
+            shear: Vec3f64::new([sign * shear(0, 1), sign * shear(0, 2), shear(1, 2)]),
+            last_row: Vec4f64::new(m[3]),
+        }
+    }
+
+    /// Computes the LU decomposition of self with partial pivoting,
+    /// so that P * self = L * U (see Mat4f64Lu).
+    /// Returns None if self is singular, meaning a pivot has an absolute value
+    /// less than or equal to tolerance.
+    pub fn lu(&self, tolerance: f64) -> Option<Mat4f64Lu> {
+        let mut lu = self.rows;
+        let mut permutation = [0, 1, 2, 3];
+        let mut sign = 1.0;
+        for k in 0..4 {
+            // Use the row with the largest absolute value in column k as pivot
+            let mut pivot = k;
+            for i in k + 1..4 {
+                if lu[i][k].abs() > lu[pivot][k].abs() {
+                    pivot = i;
+                }
+            }
+            if lu[pivot][k].abs() <= tolerance {
+                return None;
+            }
+            if pivot != k {
+                lu.swap(k, pivot);
+                permutation.swap(k, pivot);
+                sign = -sign;
+            }
+            let pivot_row = lu[k];
+            for row in lu.iter_mut().skip(k + 1) {
+                let factor = row[k] / pivot_row[k];
+                row[k] = factor;
+                for j in k + 1..4 {
+                    row[j] -= factor * pivot_row[j];
+                }
+            }
+        }
+        Some(Mat4f64Lu {
+            lu,
+            permutation,
+            sign,
+        })
+    }
+
+    /// Solves the linear system self * x = b for x using an LU decomposition.
+    /// Returns None if self is singular (see lu()).
+    pub fn solve(&self, b: Vec4f64, tolerance: f64) -> Option<Vec4f64> {
+        Some(self.lu(tolerance)?.solve(b))
+    }
+
+    /// Computes the QR decomposition of self using Householder reflections
+    /// and returns (Q, R), where Q is orthogonal, R is upper triangular with
+    /// a non negative diagonal and self = Q * R.
+    pub fn qr(&self) -> (Self, Self) {
+        let mut q = Self::identity();
+        let mut r = *self;
+        for k in 0..3 {
+            // Householder vector reflecting column k below the diagonal onto the k axis
+            let mut v = [0.0; 4];
+            let mut norm = 0.0;
+            for i in k..4 {
+                v[i] = r[i][k];
+                norm += r[i][k] * r[i][k];
+            }
+            let norm = norm.sqrt();
+            v[k] += if v[k] < 0.0 { -norm } else { norm };
+            let v_mag_squared = Vec4f64::new(v).mag_squared();
+            if v_mag_squared == 0.0 {
+                continue;
+            }
+            // r = h * r and q = q * h with h = I - 2 * v * v^T / (v^T * v)
+            for j in 0..4 {
+                let dot = Vec4f64::new(v).dot(r.col(j));
+                let f = 2.0 * dot / v_mag_squared;
+                for i in k..4 {
+                    r[i][j] -= f * v[i];
+                }
+            }
+            for i in 0..4 {
+                let dot = Vec4f64::new(v).dot(q.row(i));
+                let f = 2.0 * dot / v_mag_squared;
+                for j in k..4 {
+                    q[i][j] -= f * v[j];
+                }
+            }
+        }
+        // Make the decomposition unique by flipping signs to get a non negative diagonal
+        for k in 0..4 {
+            if r[k][k] < 0.0 {
+                r.set_row(k, -1.0 * r.row(k));
+                q.set_col(k, -1.0 * q.col(k));
+            }
+        }
+        for i in 1..4 {
+            for j in 0..i {
+                r[i][j] = 0.0;
+            }
+        }
+        (q, r)
+    }
+
+    /// Returns self but with orthonormal columns,
+    /// computed with the Gram-Schmidt process starting at the first column.
+    /// Use this to remove the drift of rotation matrices accumulated over many
+    /// multiplications. The columns of self have to be linearly independent.
+    pub fn orthonormalized(&self) -> Self {
+        let mut cols = [self.col(0), self.col(1), self.col(2), self.col(3)];
+        Vec4f64::gram_schmidt(&mut cols);
+        Self::from_cols(cols)
+    }
+
+    /// Makes the columns of self orthonormal,
+    /// see orthonormalized().
+    pub fn orthonormalize(&mut self) {
+        *self = self.orthonormalized()
+    }
+
+    /// Computes the eigenvalues and eigenvectors of self, which has to be symmetric,
+    /// for sqrt(). Returns the eigenvalues sorted in descending order and a rotation
+    /// matrix whose columns are the corresponding eigenvectors, so that
+    /// self = eigenvectors * diag(eigenvalues) * eigenvectors^T.
+    /// Uses the cyclic Jacobi eigenvalue algorithm.
+    fn symmetric_eigen(&self) -> (Vec4f64, Self) {
+        let mut a = *self;
+        let mut v = Self::identity();
+        let tolerance = f64::EPSILON * f64::EPSILON * self.frobenius_norm_squared();
+        let pairs = [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)];
+        for _ in 0..32 {
+            let mut off_diagonal = 0.0;
+            for (p, q) in pairs {
+                off_diagonal += a[p][q] * a[p][q];
+            }
+            if off_diagonal <= tolerance {
+                break;
+            }
+            for (p, q) in pairs {
+                if a[p][q] == 0.0 {
+                    continue;
+                }
+                // Jacobi rotation J that zeroes a[p][q], a = J^T * a * J and v = v * J
+                // only change rows and columns p and q
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let cos = 1.0 / (t * t + 1.0).sqrt();
+                let sin = t * cos;
+                for k in 0..4 {
+                    let (akp, akq) = (a[k][p], a[k][q]);
+                    a[k][p] = cos * akp - sin * akq;
+                    a[k][q] = sin * akp + cos * akq;
+                    let (vkp, vkq) = (v[k][p], v[k][q]);
+                    v[k][p] = cos * vkp - sin * vkq;
+                    v[k][q] = sin * vkp + cos * vkq;
+                }
+                for k in 0..4 {
+                    let (apk, aqk) = (a[p][k], a[q][k]);
+                    a[p][k] = cos * apk - sin * aqk;
+                    a[q][k] = sin * apk + cos * aqk;
+                }
+            }
+        }
+
+        // Sort the eigenpairs by descending eigenvalue
+        let mut pairs = [
+            (a[0][0], v.col(0)),
+            (a[1][1], v.col(1)),
+            (a[2][2], v.col(2)),
+            (a[3][3], v.col(3)),
+        ];
+        pairs.sort_by(|x, y| y.0.total_cmp(&x.0));
+        let mut eigenvectors = Self::from_cols([pairs[0].1, pairs[1].1, pairs[2].1, pairs[3].1]);
+        if eigenvectors.determinant() < 0.0 {
+            eigenvectors.set_col(3, -1.0 * pairs[3].1);
+        }
+        (
+            Vec4f64::new([pairs[0].0, pairs[1].0, pairs[2].0, pairs[3].0]),
+            eigenvectors,
+        )
+    }
+
+    /// Computes the Cholesky decomposition of self, which has to be symmetric,
+    /// so that self = L * L^T (see Mat4f64Cholesky).
+    /// Returns None if self is not positive definite, meaning a diagonal element of the
+    /// decomposition would be the square root of a value less than or equal to tolerance.
+    pub fn cholesky(&self, tolerance: f64) -> Option<Mat4f64Cholesky> {
+        let mut l = Self::zero();
+        for j in 0..4 {
+            // Row j of l only contains the columns before j at this point
+            let d = self[j][j] - l.row(j).mag_squared();
+            if d <= tolerance {
+                return None;
+            }
+            l[j][j] = d.sqrt();
+            for i in j + 1..4 {
+                l[i][j] = (self[i][j] - l.row(i).dot(l.row(j))) / l[j][j];
+            }
+        }
+        Some(Mat4f64Cholesky { l })
+    }
+
+    /// Computes the LDL^T decomposition of self, which has to be symmetric,
+    /// so that self = L * D * L^T (see Mat4f64Ldlt).
+    /// Unlike cholesky() this also works for many indefinite matrices and needs no
+    /// square roots. Returns None if an element of D has an absolute value less than
+    /// or equal to tolerance.
+    pub fn ldlt(&self, tolerance: f64) -> Option<Mat4f64Ldlt> {
+        let mut l = Self::identity();
+        let mut d = Vec4f64::zero();
+        for j in 0..4 {
+            let mut dj = self[j][j];
+            for k in 0..j {
+                dj -= l[j][k] * l[j][k] * d[k];
+            }
+            if dj.abs() <= tolerance {
+                return None;
+            }
+            d[j] = dj;
+            for i in j + 1..4 {
+                let mut sum = self[i][j];
+                for k in 0..j {
+                    sum -= l[i][k] * l[j][k] * d[k];
+                }
+                l[i][j] = sum / dj;
+            }
+        }
+        Some(Mat4f64Ldlt { l, d })
+    }
+
+    /// Returns the inverse of self, which has to be symmetric,
+    /// computed with an LDL^T decomposition.
+    /// Returns None if the decomposition fails (see ldlt()).
+    pub fn symmetric_inverse(&self, tolerance: f64) -> Option<Self> {
+        Some(self.ldlt(tolerance)?.inverse())
+    }
+
+    /// Returns self raised to the integer power n, computed by repeated squaring.
+    /// Negative powers use the inverse of self, so the result is not finite
+    /// if self is singular.
+    pub fn powi(&self, n: i32) -> Self {
+        let mut base = if n < 0 { self.inverse() } else { *self };
+        let mut exponent = n.unsigned_abs();
+        let mut result = Self::identity();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exponent >>= 1;
+        }
+        result
+    }
+
+    /// Returns self raised to the real power t, computed as exp(t * log(self)).
+    /// Returns None if self has no principal logarithm (see log()).
+    pub fn powf(&self, t: f64) -> Option<Self> {
+        Some((self.log()? * t).exp())
+    }
+
+    /// Returns the matrix exponential of self,
+    /// computed by scaling and squaring with a Taylor series.
+    pub fn exp(&self) -> Self {
+        // exp(A) = exp(A / 2^s)^(2^s), choose s so that the norm of A / 2^s is at most 0.5
+        let norm = self.frobenius_norm();
+        let squarings = if norm > 0.5 {
+            (norm.log2().ceil() as i32).min(f64::MAX_EXP) + 1
+        } else {
+            0
+        };
+        let scaled = *self * 0.5f64.powi(squarings);
+
+        let mut result = Self::identity();
+        let mut term = Self::identity();
+        for k in 1..32 {
+            term = term * scaled / k as f64;
+            result += term;
+            if term.frobenius_norm() <= f64::EPSILON * result.frobenius_norm() {
+                break;
+            }
+        }
+        for _ in 0..squarings {
+            result *= result;
+        }
+        result
+    }
+
+    /// Returns the principal logarithm of self, computed by inverse scaling and squaring.
+    /// Returns None if self has no principal logarithm, which is the case
+    /// if it has eigenvalues on the closed negative real axis.
+    pub fn log(&self) -> Option<Self> {
+        // log(A) = 2^k * log(A^(1 / 2^k)), take square roots until A is close to the identity
+        let mut a = *self;
+        let mut square_roots = 0;
+        while (a - Self::identity()).frobenius_norm() > 0.25 {
+            if square_roots == 64 {
+                return None;
+            }
+            a = a.sqrt()?;
+            square_roots += 1;
+        }
+
+        // log(I + X) = X - X^2 / 2 + X^3 / 3 - ...
+        let x = a - Self::identity();
+        let mut result = Self::zero();
+        let mut power = Self::identity();
+        for k in 1..64 {
+            power *= x;
+            let term = power / k as f64;
+            if k % 2 == 1 {
+                result += term;
+            } else {
+                result -= term;
+            }
+            if term.frobenius_norm() <= f64::EPSILON * result.frobenius_norm() {
+                break;
+            }
+        }
+        Some(result * 2.0f64.powi(square_roots))
+    }
+
+    /// Returns the principal square root of self. Symmetric matrices are handled
+    /// with symmetric_eigen(), other matrices with the Denman-Beavers iteration.
+    /// Returns None if self has negative real eigenvalues, or if it is singular and
+    /// not symmetric. Singular positive semidefinite matrices like diag(4, 0) are supported.
+    pub fn sqrt(&self) -> Option<Self> {
+        let tolerance = 8.0 * f64::EPSILON * self.frobenius_norm();
+        if self.is_symmetric(tolerance) {
+            let (values, vectors) = self.symmetric_eigen();
+            let mut roots = Vec4f64::zero();
+            for i in 0..4 {
+                if values[i] < -tolerance {
+                    return None;
+                }
+                // Eigenvalues within rounding error of zero are treated as zero
+                if values[i] > tolerance {
+                    roots[i] = values[i].sqrt();
+                }
+            }
+            return Some(vectors * Self::from_diagonal(roots) * vectors.transposed());
+        }
+
+        // y converges to sqrt(A) and z to its inverse
+        let mut y = *self;
+        let mut z = Self::identity();
+        for _ in 0..64 {
+            let y_inverse = y.try_inverse(0.0)?;
+            let z_inverse = z.try_inverse(0.0)?;
+            let next = (y + z_inverse) * 0.5;
+            z = (z + y_inverse) * 0.5;
+            let step = (next - y).frobenius_norm();
+            y = next;
+            if step <= 8.0 * f64::EPSILON * y.frobenius_norm() {
+                return Some(y);
+            }
+        }
+        None
+    }
+
+    /// Reduces self to reduced row echelon form with Gaussian elimination and partial pivoting.
+    /// Returns the reduced rows and which columns contain a pivot. Pivots with an absolute
+    /// value less than or equal to tolerance are treated as zero.
+    fn row_echelon(&self, tolerance: f64) -> ([[f64; 4]; 4], [bool; 4]) {
+        let mut a = self.rows;
+        let mut pivots = [false; 4];
+        let mut rank = 0;
+        for col in 0..4 {
+            if rank == 4 {
+                break;
+            }
+            let mut pivot = rank;
+            for (row, values) in a.iter().enumerate().skip(rank + 1) {
+                if values[col].abs() > a[pivot][col].abs() {
+                    pivot = row;
+                }
+            }
+            if a[pivot][col].abs() <= tolerance {
+                continue;
+            }
+            a.swap(rank, pivot);
+            let pivot_value = a[rank][col];
+            for x in a[rank].iter_mut() {
+                *x /= pivot_value;
+            }
+            let pivot_row = a[rank];
+            for (row, values) in a.iter_mut().enumerate() {
+                if row != rank {
+                    let factor = values[col];
+                    for (x, p) in values.iter_mut().zip(pivot_row) {
+                        *x -= factor * p;
+                    }
+                }
+            }
+            pivots[col] = true;
+            rank += 1;
+        }
+        (a, pivots)
+    }
+
+    /// Returns the rank of self, computed with Gaussian elimination and partial pivoting.
+    /// Pivots with an absolute value less than or equal to tolerance are treated as zero.
+    pub fn rank(&self, tolerance: f64) -> usize {
+        let (_, pivots) = self.row_echelon(tolerance);
+        pivots.iter().filter(|&&pivot| pivot).count()
+    }
+
+    /// Returns an orthonormal basis of the null space of self, the vectors v with self * v = 0.
+    /// The basis is empty if self has full column rank.
+    /// Pivots with an absolute value less than or equal to tolerance are treated as zero (see rank()).
+    pub fn null_space(&self, tolerance: f64) -> Vec<Vec4f64> {
+        let (a, pivots) = self.row_echelon(tolerance);
+        let mut basis = Vec::new();
+        for free in 0..4 {
+            if pivots[free] {
+                continue;
+            }
+            let mut v = Vec4f64::zero();
+            v[free] = 1.0;
+            // The reduced rows have their pivots in the pivot columns in order
+            let mut row = 0;
+            for col in 0..4 {
+                if pivots[col] {
+                    v[col] = -a[row][free];
+                    row += 1;
+                }
+            }
+            basis.push(v);
+        }
+        Vec4f64::gram_schmidt(&mut basis);
+        basis
+    }
+
+    /// Returns the condition number of self in the Frobenius norm,
+    /// ||self|| * ||self^-1||, which is at least 4 and grows as self
+    /// gets closer to singular. Returns infinity if self is singular.
+    pub fn condition_number(&self) -> f64 {
+        match self.try_inverse(0.0) {
+            Some(inverse) => self.frobenius_norm() * inverse.frobenius_norm(),
+            None => f64::INFINITY,
+        }
+    }
+
+    /// Returns true if the absolute value of the determinant of self is greater than tolerance,
+    /// meaning try_inverse() with the same tolerance succeeds.
+    pub fn is_invertible(&self, tolerance: f64) -> bool {
+        self.determinant().abs() > tolerance
+    }
+
+    /// Returns true if the columns of self are orthonormal, meaning self^T * self
+    /// differs from the identity by at most tolerance in every element.
+    pub fn is_orthogonal(&self, tolerance: f64) -> bool {
+        let gram = self.transposed() * *self;
+        let identity = Self::identity();
+        for i in 0..4 {
+            for j in 0..4 {
+                if (gram[i][j] - identity[i][j]).abs() > tolerance {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns true if self equals its transpose within tolerance in every element.
+    pub fn is_symmetric(&self, tolerance: f64) -> bool {
+        for i in 0..4 {
+            for j in i + 1..4 {
+                if (self[i][j] - self[j][i]).abs() > tolerance {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the inverse of self, which has to be an affine transformation
+    /// with a last row of 0, 0, 0, 1. Only the upper left 3x3 block is inverted,
+    /// which is cheaper than inverse().
+    pub fn inverse_affine(&self) -> Self {
+        debug_assert!(
+            self.rows[3] == [0.0, 0.0, 0.0, 1.0],
+            "inverse_affine() requires a last row of 0, 0, 0, 1"
+        );
+        let linear = self.upper_left().inverse();
+        let t = linear * Vec3f64::new([self[0][3], self[1][3], self[2][3]]);
+        let mut inverse = Self::from_mat3(linear);
+        inverse.set_col(3, Vec4f64::new([-t[0], -t[1], -t[2], 1.0]));
+        inverse
+    }
+
+    /// Returns the inverse of self, which has to be a rigid transformation:
+    /// an orthonormal upper left 3x3 block, a translation and a last row of 0, 0, 0, 1.
+    /// The rotation is inverted by transposing it, which is cheaper than inverse_affine().
+    pub fn inverse_rigid(&self) -> Self {
+        debug_assert!(
+            self.rows[3] == [0.0, 0.0, 0.0, 1.0],
+            "inverse_rigid() requires a last row of 0, 0, 0, 1"
+        );
+        debug_assert!(
+            self.upper_left().is_orthogonal(1e-10),
+            "inverse_rigid() requires an orthonormal upper left 3x3 block"
+        );
+        let rotation = self.upper_left().transposed();
+        let t = rotation * Vec3f64::new([self[0][3], self[1][3], self[2][3]]);
+        let mut inverse = Self::from_mat3(rotation);
+        inverse.set_col(3, Vec4f64::new([-t[0], -t[1], -t[2], 1.0]));
+        inverse
+    }
+
+    /// Returns the matrix that transforms surface normals for the transformation self,
+    /// the inverse transpose of the upper left 3x3 block.
+    /// Transformed normals have to be normalized again if self scales.
+    pub const fn normal_matrix(&self) -> Mat3f64 {
+        self.upper_left().inverse().transposed()
+    }
+
+    /// Returns the cofactor matrix of the upper left 3x3 block of self, which transforms
+    /// surface normals like normal_matrix() but without a division by the determinant.
+    /// This also works if the block is singular, for example when scaling by zero,
+    /// and differs from normal_matrix() only by the factor det, which is removed
+    /// when normalizing (a negative det flips the normals).
+    pub const fn normal_matrix_cofactor(&self) -> Mat3f64 {
+        self.upper_left().adjugate().transposed()
+    }
+
+    /// Returns the sum of self and rhs.
+    /// Same as the + operator, but usable in const contexts.
+    pub const fn sum(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [
+                a[0][0] + b[0][0],
+                a[0][1] + b[0][1],
+                a[0][2] + b[0][2],
+                a[0][3] + b[0][3],
+            ],
+            [
+                a[1][0] + b[1][0],
+                a[1][1] + b[1][1],
+                a[1][2] + b[1][2],
+                a[1][3] + b[1][3],
+            ],
+            [
+                a[2][0] + b[2][0],
+                a[2][1] + b[2][1],
+                a[2][2] + b[2][2],
+                a[2][3] + b[2][3],
+            ],
+            [
+                a[3][0] + b[3][0],
+                a[3][1] + b[3][1],
+                a[3][2] + b[3][2],
+                a[3][3] + b[3][3],
+            ],
+        ])
+    }
+
+    /// Returns the difference of self and rhs.
+    /// Same as the - operator, but usable in const contexts.
+    pub const fn difference(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [
+                a[0][0] - b[0][0],
+                a[0][1] - b[0][1],
+                a[0][2] - b[0][2],
+                a[0][3] - b[0][3],
+            ],
+            [
+                a[1][0] - b[1][0],
+                a[1][1] - b[1][1],
+                a[1][2] - b[1][2],
+                a[1][3] - b[1][3],
+            ],
+            [
+                a[2][0] - b[2][0],
+                a[2][1] - b[2][1],
+                a[2][2] - b[2][2],
+                a[2][3] - b[2][3],
+            ],
+            [
+                a[3][0] - b[3][0],
+                a[3][1] - b[3][1],
+                a[3][2] - b[3][2],
+                a[3][3] - b[3][3],
+            ],
+        ])
+    }
+
+    /// Returns self with every element multiplied by scalar.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn scaled(&self, scalar: f64) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [
+                a[0][0] * scalar,
+                a[0][1] * scalar,
+                a[0][2] * scalar,
+                a[0][3] * scalar,
+            ],
+            [
+                a[1][0] * scalar,
+                a[1][1] * scalar,
+                a[1][2] * scalar,
+                a[1][3] * scalar,
+            ],
+            [
+                a[2][0] * scalar,
+                a[2][1] * scalar,
+                a[2][2] * scalar,
+                a[2][3] * scalar,
+            ],
+            [
+                a[3][0] * scalar,
+                a[3][1] * scalar,
+                a[3][2] * scalar,
+                a[3][3] * scalar,
+            ],
+        ])
+    }
+
+    /// Returns self with every element divided by scalar.
+    /// Same as the / operator, but usable in const contexts.
+    pub const fn divided(&self, scalar: f64) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [
+                a[0][0] / scalar,
+                a[0][1] / scalar,
+                a[0][2] / scalar,
+                a[0][3] / scalar,
+            ],
+            [
+                a[1][0] / scalar,
+                a[1][1] / scalar,
+                a[1][2] / scalar,
+                a[1][3] / scalar,
+            ],
+            [
+                a[2][0] / scalar,
+                a[2][1] / scalar,
+                a[2][2] / scalar,
+                a[2][3] / scalar,
+            ],
+            [
+                a[3][0] / scalar,
+                a[3][1] / scalar,
+                a[3][2] / scalar,
+                a[3][3] / scalar,
+            ],
+        ])
+    }
+
+    /// Returns the matrix product of self and rhs.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0] + a[0][3] * b[3][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1] + a[0][3] * b[3][1],
+                a[0][0] * b[0][2] + a[0][1] * b[1][2] + a[0][2] * b[2][2] + a[0][3] * b[3][2],
+                a[0][0] * b[0][3] + a[0][1] * b[1][3] + a[0][2] * b[2][3] + a[0][3] * b[3][3],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0] + a[1][3] * b[3][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1] + a[1][3] * b[3][1],
+                a[1][0] * b[0][2] + a[1][1] * b[1][2] + a[1][2] * b[2][2] + a[1][3] * b[3][2],
+                a[1][0] * b[0][3] + a[1][1] * b[1][3] + a[1][2] * b[2][3] + a[1][3] * b[3][3],
+            ],
+            [
+                a[2][0] * b[0][0] + a[2][1] * b[1][0] + a[2][2] * b[2][0] + a[2][3] * b[3][0],
+                a[2][0] * b[0][1] + a[2][1] * b[1][1] + a[2][2] * b[2][1] + a[2][3] * b[3][1],
+                a[2][0] * b[0][2] + a[2][1] * b[1][2] + a[2][2] * b[2][2] + a[2][3] * b[3][2],
+                a[2][0] * b[0][3] + a[2][1] * b[1][3] + a[2][2] * b[2][3] + a[2][3] * b[3][3],
+            ],
+            [
+                a[3][0] * b[0][0] + a[3][1] * b[1][0] + a[3][2] * b[2][0] + a[3][3] * b[3][0],
+                a[3][0] * b[0][1] + a[3][1] * b[1][1] + a[3][2] * b[2][1] + a[3][3] * b[3][1],
+                a[3][0] * b[0][2] + a[3][1] * b[1][2] + a[3][2] * b[2][2] + a[3][3] * b[3][2],
+                a[3][0] * b[0][3] + a[3][1] * b[1][3] + a[3][2] * b[2][3] + a[3][3] * b[3][3],
+            ],
+        ])
+    }
+
+    /// Returns the product of self and the column vector v.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product_vec(&self, v: Vec4f64) -> Vec4f64 {
+        let a = &self.rows;
+        let b = &v.coords;
+        Vec4f64::new([
+            a[0][0] * b[0] + a[0][1] * b[1] + a[0][2] * b[2] + a[0][3] * b[3],
+            a[1][0] * b[0] + a[1][1] * b[1] + a[1][2] * b[2] + a[1][3] * b[3],
+            a[2][0] * b[0] + a[2][1] * b[1] + a[2][2] * b[2] + a[2][3] * b[3],
+            a[3][0] * b[0] + a[3][1] * b[1] + a[3][2] * b[2] + a[3][3] * b[3],
+        ])
+    }
+
+    /// Returns the determinant of self.
+    pub const fn determinant(&self) -> f64 {
+        let a = &self.rows;
+        // 2x2 sub-determinants of the upper and lower two rows
+        let s0 = a[0][0] * a[1][1] - a[1][0] * a[0][1];
+        let s1 = a[0][0] * a[1][2] - a[1][0] * a[0][2];
+        let s2 = a[0][0] * a[1][3] - a[1][0] * a[0][3];
+        let s3 = a[0][1] * a[1][2] - a[1][1] * a[0][2];
+        let s4 = a[0][1] * a[1][3] - a[1][1] * a[0][3];
+        let s5 = a[0][2] * a[1][3] - a[1][2] * a[0][3];
+        let c5 = a[2][2] * a[3][3] - a[3][2] * a[2][3];
+        let c4 = a[2][1] * a[3][3] - a[3][1] * a[2][3];
+        let c3 = a[2][1] * a[3][2] - a[3][1] * a[2][2];
+        let c2 = a[2][0] * a[3][3] - a[3][0] * a[2][3];
+        let c1 = a[2][0] * a[3][2] - a[3][0] * a[2][2];
+        let c0 = a[2][0] * a[3][1] - a[3][0] * a[2][1];
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+
+    /// Returns the adjugate of self (the transpose of the cofactor matrix).
+    /// Unlike the inverse, the adjugate exists for singular matrices too.
+    pub const fn adjugate(&self) -> Self {
+        let a = &self.rows;
+        // 2x2 sub-determinants of the upper and lower two rows
+        let s0 = a[0][0] * a[1][1] - a[1][0] * a[0][1];
+        let s1 = a[0][0] * a[1][2] - a[1][0] * a[0][2];
+        let s2 = a[0][0] * a[1][3] - a[1][0] * a[0][3];
+        let s3 = a[0][1] * a[1][2] - a[1][1] * a[0][2];
+        let s4 = a[0][1] * a[1][3] - a[1][1] * a[0][3];
+        let s5 = a[0][2] * a[1][3] - a[1][2] * a[0][3];
+        let c5 = a[2][2] * a[3][3] - a[3][2] * a[2][3];
+        let c4 = a[2][1] * a[3][3] - a[3][1] * a[2][3];
+        let c3 = a[2][1] * a[3][2] - a[3][1] * a[2][2];
+        let c2 = a[2][0] * a[3][3] - a[3][0] * a[2][3];
+        let c1 = a[2][0] * a[3][2] - a[3][0] * a[2][2];
+        let c0 = a[2][0] * a[3][1] - a[3][0] * a[2][1];
+        Self::new([
+            [
+                a[1][1] * c5 - a[1][2] * c4 + a[1][3] * c3,
+                -a[0][1] * c5 + a[0][2] * c4 - a[0][3] * c3,
+                a[3][1] * s5 - a[3][2] * s4 + a[3][3] * s3,
+                -a[2][1] * s5 + a[2][2] * s4 - a[2][3] * s3,
+            ],
+            [
+                -a[1][0] * c5 + a[1][2] * c2 - a[1][3] * c1,
+                a[0][0] * c5 - a[0][2] * c2 + a[0][3] * c1,
+                -a[3][0] * s5 + a[3][2] * s2 - a[3][3] * s1,
+                a[2][0] * s5 - a[2][2] * s2 + a[2][3] * s1,
+            ],
+            [
+                a[1][0] * c4 - a[1][1] * c2 + a[1][3] * c0,
+                -a[0][0] * c4 + a[0][1] * c2 - a[0][3] * c0,
+                a[3][0] * s4 - a[3][1] * s2 + a[3][3] * s0,
+                -a[2][0] * s4 + a[2][1] * s2 - a[2][3] * s0,
+            ],
+            [
+                -a[1][0] * c3 + a[1][1] * c1 - a[1][2] * c0,
+                a[0][0] * c3 - a[0][1] * c1 + a[0][2] * c0,
+                -a[3][0] * s3 + a[3][1] * s1 - a[3][2] * s0,
+                a[2][0] * s3 - a[2][1] * s1 + a[2][2] * s0,
+            ],
+        ])
+    }
+
+    /// Returns the inverse of self.
+    /// Singular matrices produce non finite elements,
+    /// use try_inverse() if self might not be invertible.
+    pub const fn inverse(&self) -> Self {
+        self.adjugate().divided(self.determinant())
+    }
+
+    /// Returns the inverse of self, or None if self is singular.
+    /// Self counts as singular if the absolute value of its determinant
+    /// is less than or equal to tolerance.
+    pub const fn try_inverse(&self, tolerance: f64) -> Option<Self> {
+        let det = self.determinant();
+        if det.abs() <= tolerance {
+            return None;
+        }
+        Some(self.adjugate().divided(det))
+    }
+}
+
+impl Index<usize> for Mat4f64 {
+    type Output = [f64; 4];
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.rows[index]
+    }
+}
+
+impl IndexMut<usize> for Mat4f64 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.rows[index]
+    }
+}
+
+impl Add<Mat4f64> for Mat4f64 {
+    type Output = Mat4f64;
+    fn add(self, rhs: Mat4f64) -> Self::Output {
+        self.sum(rhs)
+    }
+}
+
+impl AddAssign<Mat4f64> for Mat4f64 {
+    fn add_assign(&mut self, rhs: Mat4f64) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Mat4f64> for Mat4f64 {
+    type Output = Mat4f64;
+    fn sub(self, rhs: Mat4f64) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl SubAssign<Mat4f64> for Mat4f64 {
+    fn sub_assign(&mut self, rhs: Mat4f64) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f64> for Mat4f64 {
+    type Output = Mat4f64;
+    fn mul(self, scalar: f64) -> Self::Output {
+        self.scaled(scalar)
+    }
+}
+
+impl Mul<Mat4f64> for f64 {
+    type Output = Mat4f64;
+    fn mul(self, m: Mat4f64) -> Self::Output {
+        m * self
+    }
+}
+
+impl MulAssign<f64> for Mat4f64 {
+    fn mul_assign(&mut self, scalar: f64) {
+        *self = *self * scalar;
+    }
+}
+
+impl Div<f64> for Mat4f64 {
+    type Output = Mat4f64;
+    fn div(self, scalar: f64) -> Self::Output {
+        self.divided(scalar)
+    }
+}
+
+impl DivAssign<f64> for Mat4f64 {
+    fn div_assign(&mut self, scalar: f64) {
+        *self = *self / scalar;
+    }
+}
+
+impl Mul<Mat4f64> for Mat4f64 {
+    type Output = Mat4f64;
+    fn mul(self, rhs: Mat4f64) -> Self::Output {
+        self.product(rhs)
+    }
+}
+
+impl MulAssign<Mat4f64> for Mat4f64 {
+    fn mul_assign(&mut self, rhs: Mat4f64) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Vec4f64> for Mat4f64 {
+    type Output = Vec4f64;
+    fn mul(self, v: Vec4f64) -> Self::Output {
+        self.product_vec(v)
+    }
+}
+
+/// The parts of an affine transformation, see Mat4f64::decompose().
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bitcode", derive(bitcode::Encode, bitcode::Decode))]
+pub struct Mat4f64Decomposition {
+    pub translation: Vec3f64,
+    /// An orthonormal rotation matrix with a determinant of +1.
+    pub rotation: Mat3f64,
+    /// The scale along the x, y and z axis, x is negative for mirroring transformations.
+    pub scale: Vec3f64,
+    /// The xy, xz and yz shear factors, applied before scaling.
+    /// These are 0.0 for transformations composed only of translation, rotation and scale.
+    pub shear: Vec3f64,
+    /// The last row of the decomposed matrix, 0, 0, 0, 1 for affine transformations.
+    /// It is not part of to_mat4() and to_mat4_with_shear().
+    pub last_row: Vec4f64,
+}
+
+impl Mat4f64Decomposition {
+    /// Returns true if to_mat4() does not reproduce the decomposed matrix: it contains
+    /// a shear greater than tolerance, which from_trs() can not represent, or its last row
+    /// differs from 0, 0, 0, 1 by more than tolerance (a projective transformation).
+    pub fn is_lossy(&self, tolerance: f64) -> bool {
+        let affine = [0.0, 0.0, 0.0, 1.0];
+        let projective = self
+            .last_row
+            .coords
+            .iter()
+            .zip(affine)
+            .any(|(element, expected)| (element - expected).abs() > tolerance);
+        projective
+            || self.shear[0].abs() > tolerance
+            || self.shear[1].abs() > tolerance
+            || self.shear[2].abs() > tolerance
+    }
+
+    /// Returns true if an axis of the decomposed transformation has a scale of 0.0,
+    /// meaning the upper left 3x3 block of the decomposed matrix is singular.
+    pub fn is_degenerate(&self) -> bool {
+        self.scale[0] == 0.0 || self.scale[1] == 0.0 || self.scale[2] == 0.0
+    }
+
+    /// Returns the transformation without shear (translation * rotation * scale).
+    pub const fn to_mat4(&self) -> Mat4f64 {
+        Mat4f64::from_trs(self.translation, self.rotation, self.scale)
+    }
+
+    /// Returns the complete transformation including shear
+    /// (translation * rotation * scale * shear).
+    pub const fn to_mat4_with_shear(&self) -> Mat4f64 {
+        let s = &self.shear.coords;
+        let shear = Mat4f64::from_shear(s[0], s[1], 0.0, s[2], 0.0, 0.0);
+        self.to_mat4().product(shear)
+    }
+}
+
+/// The LU decomposition of a Mat4f64 with partial pivoting, see Mat4f64::lu().
+/// P * A = L * U, where P is a permutation matrix, L is lower triangular with ones
+/// on the diagonal and U is upper triangular.
+#[derive(Debug, Clone, Copy)]
+pub struct Mat4f64Lu {
+    /// L below the diagonal and U on and above the diagonal.
+    lu: [[f64; 4]; 4],
+    /// Row i of P * A is row permutation[i] of A.
+    permutation: [usize; 4],
+    /// The determinant of P (1.0 or -1.0).
+    sign: f64,
+}
+
+impl Mat4f64Lu {
+    /// Returns the lower triangular matrix L.
+    pub fn l(&self) -> Mat4f64 {
+        let mut l = Mat4f64::identity();
+        for i in 0..4 {
+            for j in 0..i {
+                l[i][j] = self.lu[i][j];
+            }
+        }
+        l
+    }
+
+    /// Returns the upper triangular matrix U.
+    pub fn u(&self) -> Mat4f64 {
+        let mut u = Mat4f64::zero();
+        for i in 0..4 {
+            for j in i..4 {
+                u[i][j] = self.lu[i][j];
+            }
+        }
+        u
+    }
+
+    /// Returns the permutation matrix P.
+    pub fn p(&self) -> Mat4f64 {
+        let mut p = Mat4f64::zero();
+        for i in 0..4 {
+            p[i][self.permutation[i]] = 1.0;
+        }
+        p
+    }
+
+    /// Returns the determinant of the decomposed matrix.
+    pub fn determinant(&self) -> f64 {
+        let mut det = self.sign;
+        for i in 0..4 {
+            det *= self.lu[i][i];
+        }
+        det
+    }
+
+    /// Solves the linear system A * x = b for x, where A is the decomposed matrix.
+    pub fn solve(&self, b: Vec4f64) -> Vec4f64 {
+        let lu = &self.lu;
+        let mut x = [0.0; 4];
+        // Forward substitution: L * y = P * b
+        for i in 0..4 {
+            let mut sum = b[self.permutation[i]];
+            for j in 0..i {
+                sum -= lu[i][j] * x[j];
+            }
+            x[i] = sum;
+        }
+        // Back substitution: U * x = y
+        for i in (0..4).rev() {
+            let mut sum = x[i];
+            for j in i + 1..4 {
+                sum -= lu[i][j] * x[j];
+            }
+            x[i] = sum / lu[i][i];
+        }
+        Vec4f64::new(x)
+    }
+}
+
+/// The Cholesky decomposition of a symmetric positive definite Mat4f64,
+/// see Mat4f64::cholesky(). A = L * L^T, where L is lower triangular
+/// with a positive diagonal.
+#[derive(Debug, Clone, Copy)]
+pub struct Mat4f64Cholesky {
+    l: Mat4f64,
+}
+
+impl Mat4f64Cholesky {
+    /// Returns the lower triangular matrix L.
+    pub fn l(&self) -> Mat4f64 {
+        self.l
+    }
+
+    /// Returns the determinant of the decomposed matrix.
+    pub fn determinant(&self) -> f64 {
+        let d = self.l.diagonal();
+        let mut det = 1.0;
+        for i in 0..4 {
+            det *= d[i] * d[i];
+        }
+        det
+    }
+
+    /// Solves the linear system A * x = b for x, where A is the decomposed matrix.
+    pub fn solve(&self, b: Vec4f64) -> Vec4f64 {
+        let l = &self.l;
+        let mut x = [0.0; 4];
+        // Forward substitution: L * y = b
+        for i in 0..4 {
+            let mut sum = b[i];
+            for j in 0..i {
+                sum -= l[i][j] * x[j];
+            }
+            x[i] = sum / l[i][i];
+        }
+        // Back substitution: L^T * x = y
+        for i in (0..4).rev() {
+            let mut sum = x[i];
+            for j in i + 1..4 {
+                sum -= l[j][i] * x[j];
+            }
+            x[i] = sum / l[i][i];
+        }
+        Vec4f64::new(x)
+    }
+
+    /// Returns the inverse of the decomposed matrix.
+    pub fn inverse(&self) -> Mat4f64 {
+        Mat4f64::from_cols([
+            self.solve(Vec4f64::new([1.0, 0.0, 0.0, 0.0])),
+            self.solve(Vec4f64::new([0.0, 1.0, 0.0, 0.0])),
+            self.solve(Vec4f64::new([0.0, 0.0, 1.0, 0.0])),
+            self.solve(Vec4f64::new([0.0, 0.0, 0.0, 1.0])),
+        ])
+    }
+}
+
+/// The LDL^T decomposition of a symmetric Mat4f64, see Mat4f64::ldlt().
+/// A = L * D * L^T, where L is lower triangular with ones on the diagonal
+/// and D is diagonal.
+#[derive(Debug, Clone, Copy)]
+pub struct Mat4f64Ldlt {
+    l: Mat4f64,
+    d: Vec4f64,
+}
+
+impl Mat4f64Ldlt {
+    /// Returns the lower triangular matrix L.
+    pub fn l(&self) -> Mat4f64 {
+        self.l
+    }
+
+    /// Returns the diagonal of D.
+    pub fn d(&self) -> Vec4f64 {
+        self.d
+    }
+
+    /// Returns the determinant of the decomposed matrix.
+    pub fn determinant(&self) -> f64 {
+        let mut det = 1.0;
+        for i in 0..4 {
+            det *= self.d[i];
+        }
+        det
+    }
+
+    /// Solves the linear system A * x = b for x, where A is the decomposed matrix.
+    pub fn solve(&self, b: Vec4f64) -> Vec4f64 {
+        let l = &self.l;
+        let mut x = [0.0; 4];
+        // Forward substitution: L * z = b
+        for i in 0..4 {
+            let mut sum = b[i];
+            for j in 0..i {
+                sum -= l[i][j] * x[j];
+            }
+            x[i] = sum;
+        }
+        // Diagonal: D * y = z
+        for (xi, di) in x.iter_mut().zip(self.d.coords) {
+            *xi /= di;
+        }
+        // Back substitution: L^T * x = y
+        for i in (0..4).rev() {
+            let mut sum = x[i];
+            for j in i + 1..4 {
+                sum -= l[j][i] * x[j];
+            }
+            x[i] = sum;
+        }
+        Vec4f64::new(x)
+    }
+
+    /// Returns the inverse of the decomposed matrix.
+    pub fn inverse(&self) -> Mat4f64 {
+        Mat4f64::from_cols([
+            self.solve(Vec4f64::new([1.0, 0.0, 0.0, 0.0])),
+            self.solve(Vec4f64::new([0.0, 1.0, 0.0, 0.0])),
+            self.solve(Vec4f64::new([0.0, 0.0, 1.0, 0.0])),
+            self.solve(Vec4f64::new([0.0, 0.0, 0.0, 1.0])),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mat::convention::{DepthRange, Handedness, ProjectionConvention};
+    use crate::mat::mat3f64::Mat3f64;
+    use crate::mat::testing::{assert_eigen, assert_mat_close, assert_orthonormal};
+    use crate::vec::vec3f64::Vec3f64;
+    use crate::vec::vec4f64::Vec4f64;
+
+    use super::Mat4f64;
+
+    #[test]
+    fn matrix_creation() {
+        let zero = Mat4f64::zero();
+        let id = Mat4f64::identity();
+        let m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let t = Mat4f64::new_from_cols([
+            [1.0, 5.0, 9.0, 13.0],
+            [2.0, 6.0, 10.0, 14.0],
+            [3.0, 7.0, 11.0, 15.0],
+            [4.0, 8.0, 12.0, 16.0],
+        ]);
+
+        assert_eq!(zero[0][0], 0.0);
+        assert_eq!(zero[0][1], 0.0);
+        assert_eq!(zero[0][2], 0.0);
+        assert_eq!(zero[0][3], 0.0);
+        assert_eq!(zero[1][0], 0.0);
+        assert_eq!(zero[1][1], 0.0);
+        assert_eq!(zero[1][2], 0.0);
+        assert_eq!(zero[1][3], 0.0);
+        assert_eq!(zero[2][0], 0.0);
+        assert_eq!(zero[2][1], 0.0);
+        assert_eq!(zero[2][2], 0.0);
+        assert_eq!(zero[2][3], 0.0);
+        assert_eq!(zero[3][0], 0.0);
+        assert_eq!(zero[3][1], 0.0);
+        assert_eq!(zero[3][2], 0.0);
+        assert_eq!(zero[3][3], 0.0);
+
+        assert_eq!(id[0][0], 1.0);
+        assert_eq!(id[0][1], 0.0);
+        assert_eq!(id[0][2], 0.0);
+        assert_eq!(id[0][3], 0.0);
+        assert_eq!(id[1][0], 0.0);
+        assert_eq!(id[1][1], 1.0);
+        assert_eq!(id[1][2], 0.0);
+        assert_eq!(id[1][3], 0.0);
+        assert_eq!(id[2][0], 0.0);
+        assert_eq!(id[2][1], 0.0);
+        assert_eq!(id[2][2], 1.0);
+        assert_eq!(id[2][3], 0.0);
+        assert_eq!(id[3][0], 0.0);
+        assert_eq!(id[3][1], 0.0);
+        assert_eq!(id[3][2], 0.0);
+        assert_eq!(id[3][3], 1.0);
+
+        assert_eq!(m[0][0], 1.0);
+        assert_eq!(m[0][1], 2.0);
+        assert_eq!(m[0][2], 3.0);
+        assert_eq!(m[0][3], 4.0);
+        assert_eq!(m[1][0], 5.0);
+        assert_eq!(m[1][1], 6.0);
+        assert_eq!(m[1][2], 7.0);
+        assert_eq!(m[1][3], 8.0);
+        assert_eq!(m[2][0], 9.0);
+        assert_eq!(m[2][1], 10.0);
+        assert_eq!(m[2][2], 11.0);
+        assert_eq!(m[2][3], 12.0);
+        assert_eq!(m[3][0], 13.0);
+        assert_eq!(m[3][1], 14.0);
+        assert_eq!(m[3][2], 15.0);
+        assert_eq!(m[3][3], 16.0);
+
+        assert_eq!(t[0][0], 1.0);
+        assert_eq!(t[0][1], 2.0);
+        assert_eq!(t[0][2], 3.0);
+        assert_eq!(t[0][3], 4.0);
+        assert_eq!(t[1][0], 5.0);
+        assert_eq!(t[1][1], 6.0);
+        assert_eq!(t[1][2], 7.0);
+        assert_eq!(t[1][3], 8.0);
+        assert_eq!(t[2][0], 9.0);
+        assert_eq!(t[2][1], 10.0);
+        assert_eq!(t[2][2], 11.0);
+        assert_eq!(t[2][3], 12.0);
+        assert_eq!(t[3][0], 13.0);
+        assert_eq!(t[3][1], 14.0);
+        assert_eq!(t[3][2], 15.0);
+        assert_eq!(t[3][3], 16.0);
+    }
+
+    #[test]
+    fn transpose() {
+        let mut m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let t = m.transposed();
+        m.transpose();
+
+        assert_eq!(t[0][0], 1.0);
+        assert_eq!(t[0][1], 5.0);
+        assert_eq!(t[0][2], 9.0);
+        assert_eq!(t[0][3], 13.0);
+        assert_eq!(t[1][0], 2.0);
+        assert_eq!(t[1][1], 6.0);
+        assert_eq!(t[1][2], 10.0);
+        assert_eq!(t[1][3], 14.0);
+        assert_eq!(t[2][0], 3.0);
+        assert_eq!(t[2][1], 7.0);
+        assert_eq!(t[2][2], 11.0);
+        assert_eq!(t[2][3], 15.0);
+        assert_eq!(t[3][0], 4.0);
+        assert_eq!(t[3][1], 8.0);
+        assert_eq!(t[3][2], 12.0);
+        assert_eq!(t[3][3], 16.0);
+
+        assert_eq!(m[0][0], 1.0);
+        assert_eq!(m[0][1], 5.0);
+        assert_eq!(m[0][2], 9.0);
+        assert_eq!(m[0][3], 13.0);
+        assert_eq!(m[1][0], 2.0);
+        assert_eq!(m[1][1], 6.0);
+        assert_eq!(m[1][2], 10.0);
+        assert_eq!(m[1][3], 14.0);
+        assert_eq!(m[2][0], 3.0);
+        assert_eq!(m[2][1], 7.0);
+        assert_eq!(m[2][2], 11.0);
+        assert_eq!(m[2][3], 15.0);
+        assert_eq!(m[3][0], 4.0);
+        assert_eq!(m[3][1], 8.0);
+        assert_eq!(m[3][2], 12.0);
+        assert_eq!(m[3][3], 16.0);
+    }
+
+    #[test]
+    fn as_row_and_col_major() {
+        let m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let cols = m.as_col_major();
+        let rows = m.as_row_major();
+
+        assert_eq!(cols[0][0], 1.0);
+        assert_eq!(cols[0][1], 5.0);
+        assert_eq!(cols[0][2], 9.0);
+        assert_eq!(cols[0][3], 13.0);
+        assert_eq!(cols[1][0], 2.0);
+        assert_eq!(cols[1][1], 6.0);
         assert_eq!(cols[1][2], 10.0);
         assert_eq!(cols[1][3], 14.0);
         assert_eq!(cols[2][0], 3.0);
@@ -431,302 +2171,2268 @@ mod tests {
         assert_eq!(cols[3][2], 12.0);
         assert_eq!(cols[3][3], 16.0);
 
-        assert_eq!(rows[0][0], 1.0);
-        assert_eq!(rows[0][1], 2.0);
-        assert_eq!(rows[0][2], 3.0);
-        assert_eq!(rows[0][3], 4.0);
-        assert_eq!(rows[1][0], 5.0);
-        assert_eq!(rows[1][1], 6.0);
-        assert_eq!(rows[1][2], 7.0);
-        assert_eq!(rows[1][3], 8.0);
-        assert_eq!(rows[2][0], 9.0);
-        assert_eq!(rows[2][1], 10.0);
-        assert_eq!(rows[2][2], 11.0);
-        assert_eq!(rows[2][3], 12.0);
-        assert_eq!(rows[3][0], 13.0);
-        assert_eq!(rows[3][1], 14.0);
-        assert_eq!(rows[3][2], 15.0);
-        assert_eq!(rows[3][3], 16.0);
+        assert_eq!(rows[0][0], 1.0);
+        assert_eq!(rows[0][1], 2.0);
+        assert_eq!(rows[0][2], 3.0);
+        assert_eq!(rows[0][3], 4.0);
+        assert_eq!(rows[1][0], 5.0);
+        assert_eq!(rows[1][1], 6.0);
+        assert_eq!(rows[1][2], 7.0);
+        assert_eq!(rows[1][3], 8.0);
+        assert_eq!(rows[2][0], 9.0);
+        assert_eq!(rows[2][1], 10.0);
+        assert_eq!(rows[2][2], 11.0);
+        assert_eq!(rows[2][3], 12.0);
+        assert_eq!(rows[3][0], 13.0);
+        assert_eq!(rows[3][1], 14.0);
+        assert_eq!(rows[3][2], 15.0);
+        assert_eq!(rows[3][3], 16.0);
+    }
+
+    #[test]
+    fn matrix_addition() {
+        let m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let mut n = Mat4f64::new([
+            [17.0, 18.0, 19.0, 20.0],
+            [21.0, 22.0, 23.0, 24.0],
+            [25.0, 26.0, 27.0, 28.0],
+            [29.0, 30.0, 31.0, 32.0],
+        ]);
+        let r = n + m;
+        n += m;
+
+        assert_eq!(r[0][0], 18.0);
+        assert_eq!(r[0][1], 20.0);
+        assert_eq!(r[0][2], 22.0);
+        assert_eq!(r[0][3], 24.0);
+        assert_eq!(r[1][0], 26.0);
+        assert_eq!(r[1][1], 28.0);
+        assert_eq!(r[1][2], 30.0);
+        assert_eq!(r[1][3], 32.0);
+        assert_eq!(r[2][0], 34.0);
+        assert_eq!(r[2][1], 36.0);
+        assert_eq!(r[2][2], 38.0);
+        assert_eq!(r[2][3], 40.0);
+        assert_eq!(r[3][0], 42.0);
+        assert_eq!(r[3][1], 44.0);
+        assert_eq!(r[3][2], 46.0);
+        assert_eq!(r[3][3], 48.0);
+
+        assert_eq!(n[0][0], 18.0);
+        assert_eq!(n[0][1], 20.0);
+        assert_eq!(n[0][2], 22.0);
+        assert_eq!(n[0][3], 24.0);
+        assert_eq!(n[1][0], 26.0);
+        assert_eq!(n[1][1], 28.0);
+        assert_eq!(n[1][2], 30.0);
+        assert_eq!(n[1][3], 32.0);
+        assert_eq!(n[2][0], 34.0);
+        assert_eq!(n[2][1], 36.0);
+        assert_eq!(n[2][2], 38.0);
+        assert_eq!(n[2][3], 40.0);
+        assert_eq!(n[3][0], 42.0);
+        assert_eq!(n[3][1], 44.0);
+        assert_eq!(n[3][2], 46.0);
+        assert_eq!(n[3][3], 48.0);
+    }
+
+    #[test]
+    fn matrix_subtraction() {
+        let m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let mut n = Mat4f64::new([
+            [17.0, 18.0, 19.0, 20.0],
+            [21.0, 22.0, 23.0, 24.0],
+            [25.0, 26.0, 27.0, 28.0],
+            [29.0, 30.0, 31.0, 32.0],
+        ]);
+        let r = m - n;
+        n -= m;
+
+        assert_eq!(r[0][0], -16.0);
+        assert_eq!(r[0][1], -16.0);
+        assert_eq!(r[0][2], -16.0);
+        assert_eq!(r[0][3], -16.0);
+        assert_eq!(r[1][0], -16.0);
+        assert_eq!(r[1][1], -16.0);
+        assert_eq!(r[1][2], -16.0);
+        assert_eq!(r[1][3], -16.0);
+        assert_eq!(r[2][0], -16.0);
+        assert_eq!(r[2][1], -16.0);
+        assert_eq!(r[2][2], -16.0);
+        assert_eq!(r[2][3], -16.0);
+        assert_eq!(r[3][0], -16.0);
+        assert_eq!(r[3][1], -16.0);
+        assert_eq!(r[3][2], -16.0);
+        assert_eq!(r[3][3], -16.0);
+
+        assert_eq!(n[0][0], 16.0);
+        assert_eq!(n[0][1], 16.0);
+        assert_eq!(n[0][2], 16.0);
+        assert_eq!(n[0][3], 16.0);
+        assert_eq!(n[1][0], 16.0);
+        assert_eq!(n[1][1], 16.0);
+        assert_eq!(n[1][2], 16.0);
+        assert_eq!(n[1][3], 16.0);
+        assert_eq!(n[2][0], 16.0);
+        assert_eq!(n[2][1], 16.0);
+        assert_eq!(n[2][2], 16.0);
+        assert_eq!(n[2][3], 16.0);
+        assert_eq!(n[3][0], 16.0);
+        assert_eq!(n[3][1], 16.0);
+        assert_eq!(n[3][2], 16.0);
+        assert_eq!(n[3][3], 16.0);
+    }
+
+    #[test]
+    fn scalar_multiplication() {
+        let mut m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let n = m * 3.0;
+        assert_eq!(n[0][0], 3.0);
+        assert_eq!(n[0][1], 6.0);
+        assert_eq!(n[0][2], 9.0);
+        assert_eq!(n[0][3], 12.0);
+        assert_eq!(n[1][0], 15.0);
+        assert_eq!(n[1][1], 18.0);
+        assert_eq!(n[1][2], 21.0);
+        assert_eq!(n[1][3], 24.0);
+        assert_eq!(n[2][0], 27.0);
+        assert_eq!(n[2][1], 30.0);
+        assert_eq!(n[2][2], 33.0);
+        assert_eq!(n[2][3], 36.0);
+        assert_eq!(n[3][0], 39.0);
+        assert_eq!(n[3][1], 42.0);
+        assert_eq!(n[3][2], 45.0);
+        assert_eq!(n[3][3], 48.0);
+
+        let n = 3.0 * m;
+        assert_eq!(n[0][0], 3.0);
+        assert_eq!(n[0][1], 6.0);
+        assert_eq!(n[0][2], 9.0);
+        assert_eq!(n[0][3], 12.0);
+        assert_eq!(n[1][0], 15.0);
+        assert_eq!(n[1][1], 18.0);
+        assert_eq!(n[1][2], 21.0);
+        assert_eq!(n[1][3], 24.0);
+        assert_eq!(n[2][0], 27.0);
+        assert_eq!(n[2][1], 30.0);
+        assert_eq!(n[2][2], 33.0);
+        assert_eq!(n[2][3], 36.0);
+        assert_eq!(n[3][0], 39.0);
+        assert_eq!(n[3][1], 42.0);
+        assert_eq!(n[3][2], 45.0);
+        assert_eq!(n[3][3], 48.0);
+
+        m *= 3.0;
+        assert_eq!(m[0][0], 3.0);
+        assert_eq!(m[0][1], 6.0);
+        assert_eq!(m[0][2], 9.0);
+        assert_eq!(m[0][3], 12.0);
+        assert_eq!(m[1][0], 15.0);
+        assert_eq!(m[1][1], 18.0);
+        assert_eq!(m[1][2], 21.0);
+        assert_eq!(m[1][3], 24.0);
+        assert_eq!(m[2][0], 27.0);
+        assert_eq!(m[2][1], 30.0);
+        assert_eq!(m[2][2], 33.0);
+        assert_eq!(m[2][3], 36.0);
+        assert_eq!(m[3][0], 39.0);
+        assert_eq!(m[3][1], 42.0);
+        assert_eq!(m[3][2], 45.0);
+        assert_eq!(m[3][3], 48.0);
+    }
+
+    #[test]
+    fn scalar_division() {
+        let mut m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let n = m / 3.0;
+        assert_eq!(n[0][0], 1.0 / 3.0);
+        assert_eq!(n[0][1], 2.0 / 3.0);
+        assert_eq!(n[0][2], 3.0 / 3.0);
+        assert_eq!(n[0][3], 4.0 / 3.0);
+        assert_eq!(n[1][0], 5.0 / 3.0);
+        assert_eq!(n[1][1], 6.0 / 3.0);
+        assert_eq!(n[1][2], 7.0 / 3.0);
+        assert_eq!(n[1][3], 8.0 / 3.0);
+        assert_eq!(n[2][0], 9.0 / 3.0);
+        assert_eq!(n[2][1], 10.0 / 3.0);
+        assert_eq!(n[2][2], 11.0 / 3.0);
+        assert_eq!(n[2][3], 12.0 / 3.0);
+        assert_eq!(n[3][0], 13.0 / 3.0);
+        assert_eq!(n[3][1], 14.0 / 3.0);
+        assert_eq!(n[3][2], 15.0 / 3.0);
+        assert_eq!(n[3][3], 16.0 / 3.0);
+
+        m /= 3.0;
+        assert_eq!(m[0][0], 1.0 / 3.0);
+        assert_eq!(m[0][1], 2.0 / 3.0);
+        assert_eq!(m[0][2], 3.0 / 3.0);
+        assert_eq!(m[0][3], 4.0 / 3.0);
+        assert_eq!(m[1][0], 5.0 / 3.0);
+        assert_eq!(m[1][1], 6.0 / 3.0);
+        assert_eq!(m[1][2], 7.0 / 3.0);
+        assert_eq!(m[1][3], 8.0 / 3.0);
+        assert_eq!(m[2][0], 9.0 / 3.0);
+        assert_eq!(m[2][1], 10.0 / 3.0);
+        assert_eq!(m[2][2], 11.0 / 3.0);
+        assert_eq!(m[2][3], 12.0 / 3.0);
+        assert_eq!(m[3][0], 13.0 / 3.0);
+        assert_eq!(m[3][1], 14.0 / 3.0);
+        assert_eq!(m[3][2], 15.0 / 3.0);
+        assert_eq!(m[3][3], 16.0 / 3.0);
+    }
+
+    #[test]
+    fn matrix_multiplication() {
+        let mut m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let n = Mat4f64::new([
+            [17.0, 18.0, 19.0, 20.0],
+            [21.0, 22.0, 23.0, 24.0],
+            [25.0, 26.0, 27.0, 28.0],
+            [29.0, 30.0, 31.0, 32.0],
+        ]);
+        let r = m * n;
+        m *= n;
+
+        assert_eq!(r[0][0], 250.0);
+        assert_eq!(r[0][1], 260.0);
+        assert_eq!(r[0][2], 270.0);
+        assert_eq!(r[0][3], 280.0);
+        assert_eq!(r[1][0], 618.0);
+        assert_eq!(r[1][1], 644.0);
+        assert_eq!(r[1][2], 670.0);
+        assert_eq!(r[1][3], 696.0);
+        assert_eq!(r[2][0], 986.0);
+        assert_eq!(r[2][1], 1028.0);
+        assert_eq!(r[2][2], 1070.0);
+        assert_eq!(r[2][3], 1112.0);
+        assert_eq!(r[3][0], 1354.0);
+        assert_eq!(r[3][1], 1412.0);
+        assert_eq!(r[3][2], 1470.0);
+        assert_eq!(r[3][3], 1528.0);
+
+        assert_eq!(m[0][0], 250.0);
+        assert_eq!(m[0][1], 260.0);
+        assert_eq!(m[0][2], 270.0);
+        assert_eq!(m[0][3], 280.0);
+        assert_eq!(m[1][0], 618.0);
+        assert_eq!(m[1][1], 644.0);
+        assert_eq!(m[1][2], 670.0);
+        assert_eq!(m[1][3], 696.0);
+        assert_eq!(m[2][0], 986.0);
+        assert_eq!(m[2][1], 1028.0);
+        assert_eq!(m[2][2], 1070.0);
+        assert_eq!(m[2][3], 1112.0);
+        assert_eq!(m[3][0], 1354.0);
+        assert_eq!(m[3][1], 1412.0);
+        assert_eq!(m[3][2], 1470.0);
+        assert_eq!(m[3][3], 1528.0);
+    }
+
+    #[test]
+    fn vector_multiplication() {
+        let m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let v = Vec4f64::new([2.0, 3.0, 4.0, 5.0]);
+        let w = m * v;
+
+        assert_eq!(w[0], 40.0);
+        assert_eq!(w[1], 96.0);
+        assert_eq!(w[2], 152.0);
+        assert_eq!(w[3], 208.0);
+    }
+
+    #[test]
+    fn const_evaluation() {
+        const M: Mat4f64 = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        const T: Mat4f64 = M.transposed();
+        const P: Mat4f64 = M.product(M);
+        const S: Mat4f64 = M.sum(Mat4f64::identity()).difference(Mat4f64::identity());
+        const H: Mat4f64 = M.scaled(4.0).divided(2.0);
+        const W: Vec4f64 = M.product_vec(Vec4f64::new([2.0, 3.0, 4.0, 5.0]));
+        const Z: Mat4f64 = Mat4f64::zero();
+
+        assert_eq!(T[0][0], 1.0);
+        assert_eq!(T[0][1], 5.0);
+        assert_eq!(T[0][2], 9.0);
+        assert_eq!(T[0][3], 13.0);
+        assert_eq!(T[1][0], 2.0);
+        assert_eq!(T[1][1], 6.0);
+        assert_eq!(T[1][2], 10.0);
+        assert_eq!(T[1][3], 14.0);
+        assert_eq!(T[2][0], 3.0);
+        assert_eq!(T[2][1], 7.0);
+        assert_eq!(T[2][2], 11.0);
+        assert_eq!(T[2][3], 15.0);
+        assert_eq!(T[3][0], 4.0);
+        assert_eq!(T[3][1], 8.0);
+        assert_eq!(T[3][2], 12.0);
+        assert_eq!(T[3][3], 16.0);
+
+        assert_eq!(P[0][0], 90.0);
+        assert_eq!(P[0][1], 100.0);
+        assert_eq!(P[0][2], 110.0);
+        assert_eq!(P[0][3], 120.0);
+        assert_eq!(P[1][0], 202.0);
+        assert_eq!(P[1][1], 228.0);
+        assert_eq!(P[1][2], 254.0);
+        assert_eq!(P[1][3], 280.0);
+        assert_eq!(P[2][0], 314.0);
+        assert_eq!(P[2][1], 356.0);
+        assert_eq!(P[2][2], 398.0);
+        assert_eq!(P[2][3], 440.0);
+        assert_eq!(P[3][0], 426.0);
+        assert_eq!(P[3][1], 484.0);
+        assert_eq!(P[3][2], 542.0);
+        assert_eq!(P[3][3], 600.0);
+
+        assert_eq!(S[0][0], 1.0);
+        assert_eq!(S[0][1], 2.0);
+        assert_eq!(S[0][2], 3.0);
+        assert_eq!(S[0][3], 4.0);
+        assert_eq!(S[1][0], 5.0);
+        assert_eq!(S[1][1], 6.0);
+        assert_eq!(S[1][2], 7.0);
+        assert_eq!(S[1][3], 8.0);
+        assert_eq!(S[2][0], 9.0);
+        assert_eq!(S[2][1], 10.0);
+        assert_eq!(S[2][2], 11.0);
+        assert_eq!(S[2][3], 12.0);
+        assert_eq!(S[3][0], 13.0);
+        assert_eq!(S[3][1], 14.0);
+        assert_eq!(S[3][2], 15.0);
+        assert_eq!(S[3][3], 16.0);
+
+        assert_eq!(H[0][0], 2.0);
+        assert_eq!(H[0][1], 4.0);
+        assert_eq!(H[0][2], 6.0);
+        assert_eq!(H[0][3], 8.0);
+        assert_eq!(H[1][0], 10.0);
+        assert_eq!(H[1][1], 12.0);
+        assert_eq!(H[1][2], 14.0);
+        assert_eq!(H[1][3], 16.0);
+        assert_eq!(H[2][0], 18.0);
+        assert_eq!(H[2][1], 20.0);
+        assert_eq!(H[2][2], 22.0);
+        assert_eq!(H[2][3], 24.0);
+        assert_eq!(H[3][0], 26.0);
+        assert_eq!(H[3][1], 28.0);
+        assert_eq!(H[3][2], 30.0);
+        assert_eq!(H[3][3], 32.0);
+
+        assert_eq!(W[0], 40.0);
+        assert_eq!(W[1], 96.0);
+        assert_eq!(W[2], 152.0);
+        assert_eq!(W[3], 208.0);
+
+        assert_eq!(Z[0][0], 0.0);
+        assert_eq!(Z[0][1], 0.0);
+        assert_eq!(Z[0][2], 0.0);
+        assert_eq!(Z[0][3], 0.0);
+        assert_eq!(Z[1][0], 0.0);
+        assert_eq!(Z[1][1], 0.0);
+        assert_eq!(Z[1][2], 0.0);
+        assert_eq!(Z[1][3], 0.0);
+        assert_eq!(Z[2][0], 0.0);
+        assert_eq!(Z[2][1], 0.0);
+        assert_eq!(Z[2][2], 0.0);
+        assert_eq!(Z[2][3], 0.0);
+        assert_eq!(Z[3][0], 0.0);
+        assert_eq!(Z[3][1], 0.0);
+        assert_eq!(Z[3][2], 0.0);
+        assert_eq!(Z[3][3], 0.0);
+    }
+
+    #[test]
+    fn determinant_and_adjugate() {
+        let m = Mat4f64::new([
+            [1.0, 1.0, 1.0, -1.0],
+            [1.0, 1.0, -1.0, 1.0],
+            [1.0, -1.0, 1.0, 1.0],
+            [-1.0, 1.0, 1.0, 1.0],
+        ]);
+        let adj = m.adjugate();
+
+        assert_eq!(m.determinant(), -16.0);
+        assert_eq!(Mat4f64::identity().determinant(), 1.0);
+        assert_eq!(
+            Mat4f64::new([
+                [1.0, 2.0, 3.0, 4.0],
+                [5.0, 6.0, 7.0, 8.0],
+                [9.0, 10.0, 11.0, 12.0],
+                [13.0, 14.0, 15.0, 16.0],
+            ])
+            .determinant(),
+            0.0
+        );
+
+        // The adjugate of m is -4 * m
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(adj[i][j], -4.0 * m[i][j]);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let m = Mat4f64::new([
+            [1.0, 1.0, 1.0, -1.0],
+            [1.0, 1.0, -1.0, 1.0],
+            [1.0, -1.0, 1.0, 1.0],
+            [-1.0, 1.0, 1.0, 1.0],
+        ]);
+        let inv = m.inverse();
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(inv[i][j], m[i][j] / 4.0);
+            }
+        }
+
+        let m = Mat4f64::new([
+            [2.0, 0.0, 1.0, 3.0],
+            [0.0, 1.0, 0.0, 2.0],
+            [1.0, 0.0, 1.0, 0.0],
+            [0.0, 3.0, 0.0, 1.0],
+        ]);
+        let id = m * m.try_inverse(1e-12).unwrap();
+        for i in 0..4 {
+            for j in 0..4 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((id[i][j] - expected).abs() < 1e-12);
+            }
+        }
+
+        // Singular matrices
+        let singular = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        assert!(singular.try_inverse(1e-12).is_none());
+        assert!(Mat4f64::zero().try_inverse(0.0).is_none());
+
+        // Configurable tolerance
+        let almost_singular = Mat4f64::identity() * 0.1;
+        assert!(almost_singular.try_inverse(0.001).is_none());
+        assert!(almost_singular.try_inverse(0.00001).is_some());
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let from_rows = Mat4f64::from_rows([
+            Vec4f64::new([1.0, 2.0, 3.0, 4.0]),
+            Vec4f64::new([5.0, 6.0, 7.0, 8.0]),
+            Vec4f64::new([9.0, 10.0, 11.0, 12.0]),
+            Vec4f64::new([13.0, 14.0, 15.0, 16.0]),
+        ]);
+        let from_cols = Mat4f64::from_cols([
+            Vec4f64::new([1.0, 2.0, 3.0, 4.0]),
+            Vec4f64::new([5.0, 6.0, 7.0, 8.0]),
+            Vec4f64::new([9.0, 10.0, 11.0, 12.0]),
+            Vec4f64::new([13.0, 14.0, 15.0, 16.0]),
+        ]);
+
+        assert_eq!(from_rows[0][0], 1.0);
+        assert_eq!(from_rows[0][1], 2.0);
+        assert_eq!(from_rows[0][2], 3.0);
+        assert_eq!(from_rows[0][3], 4.0);
+        assert_eq!(from_rows[1][0], 5.0);
+        assert_eq!(from_rows[1][1], 6.0);
+        assert_eq!(from_rows[1][2], 7.0);
+        assert_eq!(from_rows[1][3], 8.0);
+        assert_eq!(from_rows[2][0], 9.0);
+        assert_eq!(from_rows[2][1], 10.0);
+        assert_eq!(from_rows[2][2], 11.0);
+        assert_eq!(from_rows[2][3], 12.0);
+        assert_eq!(from_rows[3][0], 13.0);
+        assert_eq!(from_rows[3][1], 14.0);
+        assert_eq!(from_rows[3][2], 15.0);
+        assert_eq!(from_rows[3][3], 16.0);
+
+        assert_eq!(from_cols[0][0], 1.0);
+        assert_eq!(from_cols[0][1], 5.0);
+        assert_eq!(from_cols[0][2], 9.0);
+        assert_eq!(from_cols[0][3], 13.0);
+        assert_eq!(from_cols[1][0], 2.0);
+        assert_eq!(from_cols[1][1], 6.0);
+        assert_eq!(from_cols[1][2], 10.0);
+        assert_eq!(from_cols[1][3], 14.0);
+        assert_eq!(from_cols[2][0], 3.0);
+        assert_eq!(from_cols[2][1], 7.0);
+        assert_eq!(from_cols[2][2], 11.0);
+        assert_eq!(from_cols[2][3], 15.0);
+        assert_eq!(from_cols[3][0], 4.0);
+        assert_eq!(from_cols[3][1], 8.0);
+        assert_eq!(from_cols[3][2], 12.0);
+        assert_eq!(from_cols[3][3], 16.0);
+
+        assert_eq!(m.row(0)[0], 1.0);
+        assert_eq!(m.row(0)[1], 2.0);
+        assert_eq!(m.row(0)[2], 3.0);
+        assert_eq!(m.row(0)[3], 4.0);
+        assert_eq!(m.row(1)[0], 5.0);
+        assert_eq!(m.row(1)[1], 6.0);
+        assert_eq!(m.row(1)[2], 7.0);
+        assert_eq!(m.row(1)[3], 8.0);
+        assert_eq!(m.row(2)[0], 9.0);
+        assert_eq!(m.row(2)[1], 10.0);
+        assert_eq!(m.row(2)[2], 11.0);
+        assert_eq!(m.row(2)[3], 12.0);
+        assert_eq!(m.row(3)[0], 13.0);
+        assert_eq!(m.row(3)[1], 14.0);
+        assert_eq!(m.row(3)[2], 15.0);
+        assert_eq!(m.row(3)[3], 16.0);
+
+        assert_eq!(m.col(0)[0], 1.0);
+        assert_eq!(m.col(0)[1], 5.0);
+        assert_eq!(m.col(0)[2], 9.0);
+        assert_eq!(m.col(0)[3], 13.0);
+        assert_eq!(m.col(1)[0], 2.0);
+        assert_eq!(m.col(1)[1], 6.0);
+        assert_eq!(m.col(1)[2], 10.0);
+        assert_eq!(m.col(1)[3], 14.0);
+        assert_eq!(m.col(2)[0], 3.0);
+        assert_eq!(m.col(2)[1], 7.0);
+        assert_eq!(m.col(2)[2], 11.0);
+        assert_eq!(m.col(2)[3], 15.0);
+        assert_eq!(m.col(3)[0], 4.0);
+        assert_eq!(m.col(3)[1], 8.0);
+        assert_eq!(m.col(3)[2], 12.0);
+        assert_eq!(m.col(3)[3], 16.0);
+
+        let mut n = Mat4f64::zero();
+        n.set_row(1, Vec4f64::ones());
+        n.set_col(0, Vec4f64::new([-1.0, -2.0, -3.0, -4.0]));
+        assert_eq!(n[0][0], -1.0);
+        assert_eq!(n[0][1], 0.0);
+        assert_eq!(n[0][2], 0.0);
+        assert_eq!(n[0][3], 0.0);
+        assert_eq!(n[1][0], -2.0);
+        assert_eq!(n[1][1], 1.0);
+        assert_eq!(n[1][2], 1.0);
+        assert_eq!(n[1][3], 1.0);
+        assert_eq!(n[2][0], -3.0);
+        assert_eq!(n[2][1], 0.0);
+        assert_eq!(n[2][2], 0.0);
+        assert_eq!(n[2][3], 0.0);
+        assert_eq!(n[3][0], -4.0);
+        assert_eq!(n[3][1], 0.0);
+        assert_eq!(n[3][2], 0.0);
+        assert_eq!(n[3][3], 0.0);
+    }
+
+    #[test]
+    fn diagonal_and_trace() {
+        let m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let d = Mat4f64::from_diagonal(Vec4f64::new([2.0, 3.0, 4.0, 5.0]));
+
+        assert_eq!(m.diagonal()[0], 1.0);
+        assert_eq!(m.diagonal()[1], 6.0);
+        assert_eq!(m.diagonal()[2], 11.0);
+        assert_eq!(m.diagonal()[3], 16.0);
+
+        assert_eq!(d[0][0], 2.0);
+        assert_eq!(d[0][1], 0.0);
+        assert_eq!(d[0][2], 0.0);
+        assert_eq!(d[0][3], 0.0);
+        assert_eq!(d[1][0], 0.0);
+        assert_eq!(d[1][1], 3.0);
+        assert_eq!(d[1][2], 0.0);
+        assert_eq!(d[1][3], 0.0);
+        assert_eq!(d[2][0], 0.0);
+        assert_eq!(d[2][1], 0.0);
+        assert_eq!(d[2][2], 4.0);
+        assert_eq!(d[2][3], 0.0);
+        assert_eq!(d[3][0], 0.0);
+        assert_eq!(d[3][1], 0.0);
+        assert_eq!(d[3][2], 0.0);
+        assert_eq!(d[3][3], 5.0);
+
+        assert_eq!(m.trace(), 34.0);
+        assert_eq!(d.trace(), 14.0);
+        assert_eq!(Mat4f64::identity().trace(), 4.0);
+    }
+
+    #[test]
+    fn frobenius_norm() {
+        let m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+
+        assert_eq!(m.frobenius_norm_squared(), 1496.0);
+        assert_eq!(m.frobenius_norm(), 1496.0f64.sqrt());
+        assert_eq!(Mat4f64::identity().frobenius_norm(), 4.0f64.sqrt());
+        assert_eq!(Mat4f64::zero().frobenius_norm(), 0.0);
+    }
+
+    #[test]
+    fn outer_product() {
+        let a = Vec4f64::new([1.0, 2.0, 3.0, 4.0]);
+        let b = Vec4f64::new([2.0, -1.0, 3.0, -2.0]);
+        let m = Mat4f64::outer_product(a, b);
+
+        assert_eq!(m[0][0], 2.0);
+        assert_eq!(m[0][1], -1.0);
+        assert_eq!(m[0][2], 3.0);
+        assert_eq!(m[0][3], -2.0);
+        assert_eq!(m[1][0], 4.0);
+        assert_eq!(m[1][1], -2.0);
+        assert_eq!(m[1][2], 6.0);
+        assert_eq!(m[1][3], -4.0);
+        assert_eq!(m[2][0], 6.0);
+        assert_eq!(m[2][1], -3.0);
+        assert_eq!(m[2][2], 9.0);
+        assert_eq!(m[2][3], -6.0);
+        assert_eq!(m[3][0], 8.0);
+        assert_eq!(m[3][1], -4.0);
+        assert_eq!(m[3][2], 12.0);
+        assert_eq!(m[3][3], -8.0);
+    }
+
+    #[test]
+    fn affine_constructors() {
+        let p = Vec4f64::new([1.0, 2.0, 3.0, 1.0]);
+
+        let t = Mat4f64::from_translation(Vec3f64::new([4.0, -1.0, 2.0])) * p;
+        assert_eq!(t[0], 5.0);
+        assert_eq!(t[1], 1.0);
+        assert_eq!(t[2], 5.0);
+        assert_eq!(t[3], 1.0);
+
+        let s = Mat4f64::from_scale(Vec3f64::new([2.0, -3.0, 0.5])) * p;
+        assert_eq!(s[0], 2.0);
+        assert_eq!(s[1], -6.0);
+        assert_eq!(s[2], 1.5);
+        assert_eq!(s[3], 1.0);
+
+        let sh = Mat4f64::from_shear(1.0, 0.0, 0.0, 2.0, 0.0, 0.0) * p;
+        assert_eq!(sh[0], 3.0);
+        assert_eq!(sh[1], 8.0);
+        assert_eq!(sh[2], 3.0);
+        assert_eq!(sh[3], 1.0);
+
+        // Directions (w = 0) are not affected by translations
+        let d = Mat4f64::from_translation(Vec3f64::new([4.0, -1.0, 2.0]))
+            * Vec4f64::new([1.0, 2.0, 3.0, 0.0]);
+        assert_eq!(d[0], 1.0);
+        assert_eq!(d[1], 2.0);
+        assert_eq!(d[2], 3.0);
+        assert_eq!(d[3], 0.0);
+    }
+
+    #[test]
+    fn rotations() {
+        let angle = std::f64::consts::FRAC_PI_2;
+        let x = Vec4f64::new([1.0, 0.0, 0.0, 0.0]);
+        let y = Vec4f64::new([0.0, 1.0, 0.0, 0.0]);
+        let z = Vec4f64::new([0.0, 0.0, 1.0, 0.0]);
+        let close = |a: Vec4f64, b: Vec4f64| (a - b).mag() < 1e-12;
+
+        // Right hand rule: x -> y -> z -> x
+        assert!(close(Mat4f64::from_rotation_x(angle) * y, z));
+        assert!(close(Mat4f64::from_rotation_y(angle) * z, x));
+        assert!(close(Mat4f64::from_rotation_z(angle) * x, y));
+
+        // Axis angle matches the axis aligned rotations
+        let axes = [
+            (Vec3f64::new([1.0, 0.0, 0.0]), Mat4f64::from_rotation_x(0.7)),
+            (Vec3f64::new([0.0, 1.0, 0.0]), Mat4f64::from_rotation_y(0.7)),
+            (Vec3f64::new([0.0, 0.0, 1.0]), Mat4f64::from_rotation_z(0.7)),
+        ];
+        for (axis, expected) in axes {
+            let m = Mat4f64::from_axis_angle(axis, 0.7);
+            for i in 0..4 {
+                for j in 0..4 {
+                    assert!((m[i][j] - expected[i][j]).abs() < 1e-12);
+                }
+            }
+        }
+
+        // A rotation around the diagonal by 120 degrees permutes the axes
+        let diagonal = Vec3f64::new([1.0, 1.0, 1.0]).normed();
+        let m = Mat4f64::from_axis_angle(diagonal, 2.0 * std::f64::consts::FRAC_PI_3);
+        assert!(close(m * x, y));
+        assert!(close(m * y, z));
+        assert!(close(m * z, x));
+    }
+
+    #[test]
+    fn affine_builders() {
+        let p = Vec4f64::new([1.0, 0.0, 0.0, 1.0]);
+        let m = Mat4f64::identity()
+            .scale(Vec3f64::new([2.0, 2.0, 2.0]))
+            .rotate_z(std::f64::consts::FRAC_PI_2)
+            .translate(Vec3f64::new([10.0, 20.0, 30.0]));
+        let r = m * p;
+        assert!((r[0] - 10.0).abs() < 1e-12);
+        assert!((r[1] - 22.0).abs() < 1e-12);
+        assert!((r[2] - 30.0).abs() < 1e-12);
+        assert_eq!(r[3], 1.0);
+
+        let m = Mat4f64::identity()
+            .rotate_x(0.3)
+            .rotate_y(0.2)
+            .rotate(Vec3f64::new([0.0, 0.0, 1.0]), 0.1);
+        let expected = Mat4f64::from_rotation_z(0.1)
+            * Mat4f64::from_rotation_y(0.2)
+            * Mat4f64::from_rotation_x(0.3);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((m[i][j] - expected[i][j]).abs() < 1e-12);
+            }
+        }
+
+        let sh = Mat4f64::identity().shear(0.0, 1.0, 0.0, 0.0, 0.0, 0.0) * Vec4f64::ones();
+        assert_eq!(sh[0], 2.0);
+        assert_eq!(sh[1], 1.0);
+        assert_eq!(sh[2], 1.0);
+        assert_eq!(sh[3], 1.0);
+    }
+
+    /// Projects a view space point and returns its normalized device coordinates.
+    fn project(m: Mat4f64, x: f64, y: f64, z: f64) -> [f64; 3] {
+        let c = m * Vec4f64::new([x, y, z, 1.0]);
+        [c[0] / c[3], c[1] / c[3], c[2] / c[3]]
+    }
+
+    fn assert_close(a: [f64; 3], b: [f64; 3]) {
+        for i in 0..3 {
+            assert!((a[i] - b[i]).abs() < 1e-12, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn perspective_projection() {
+        let conventions = [
+            (Handedness::Right, DepthRange::NegativeOneToOne, -1.0, 1.0),
+            (Handedness::Right, DepthRange::ZeroToOne, 0.0, 1.0),
+            (Handedness::Left, DepthRange::NegativeOneToOne, -1.0, 1.0),
+            (Handedness::Left, DepthRange::ZeroToOne, 0.0, 1.0),
+        ];
+        for (handedness, depth_range, ndc_near, ndc_far) in conventions {
+            let forward = match handedness {
+                Handedness::Right => -1.0,
+                Handedness::Left => 1.0,
+            };
+            let convention = ProjectionConvention::new(handedness, depth_range);
+            let m = Mat4f64::perspective(std::f64::consts::FRAC_PI_2, 2.0, 0.5, 100.0, convention);
+
+            // Center of the near and far plane
+            assert_close(project(m, 0.0, 0.0, forward * 0.5), [0.0, 0.0, ndc_near]);
+            assert_close(project(m, 0.0, 0.0, forward * 100.0), [0.0, 0.0, ndc_far]);
+            // Corners of the near plane (fov of 90 degrees, aspect ratio 2)
+            assert_close(project(m, 1.0, 0.5, forward * 0.5), [1.0, 1.0, ndc_near]);
+            assert_close(
+                project(m, -1.0, -0.5, forward * 0.5),
+                [-1.0, -1.0, ndc_near],
+            );
+            // Corner of the far plane
+            assert_close(
+                project(m, 200.0, 100.0, forward * 100.0),
+                [1.0, 1.0, ndc_far],
+            );
+
+            // Reversed z
+            let reversed = Mat4f64::perspective(
+                std::f64::consts::FRAC_PI_2,
+                2.0,
+                0.5,
+                100.0,
+                convention.with_reversed_z(),
+            );
+            assert_close(
+                project(reversed, 0.0, 0.0, forward * 0.5),
+                [0.0, 0.0, ndc_far],
+            );
+            assert_close(
+                project(reversed, 0.0, 0.0, forward * 100.0),
+                [0.0, 0.0, ndc_near],
+            );
+
+            // Infinite far plane
+            let infinite = Mat4f64::perspective(
+                std::f64::consts::FRAC_PI_2,
+                2.0,
+                0.5,
+                f64::INFINITY,
+                convention,
+            );
+            assert_close(
+                project(infinite, 0.0, 0.0, forward * 0.5),
+                [0.0, 0.0, ndc_near],
+            );
+            let distant = project(infinite, 0.0, 0.0, forward * 1e14);
+            assert!((distant[2] - ndc_far).abs() < 1e-12);
+            assert!(distant[2] < ndc_far);
+
+            // Reversed z with infinite far plane
+            let infinite = Mat4f64::perspective(
+                std::f64::consts::FRAC_PI_2,
+                2.0,
+                0.5,
+                f64::INFINITY,
+                convention.with_reversed_z(),
+            );
+            assert_close(
+                project(infinite, 0.0, 0.0, forward * 0.5),
+                [0.0, 0.0, ndc_far],
+            );
+            let distant = project(infinite, 0.0, 0.0, forward * 1e14);
+            assert!((distant[2] - ndc_near).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn frustum_projection() {
+        let convention = ProjectionConvention::new(Handedness::Right, DepthRange::NegativeOneToOne);
+        let m = Mat4f64::frustum(-1.0, 3.0, -2.0, 1.0, 1.0, 10.0, convention);
+
+        // Known OpenGL glFrustum matrix
+        let expected = [
+            [0.5, 0.0, 0.5, 0.0],
+            [0.0, 2.0 / 3.0, -1.0 / 3.0, 0.0],
+            [0.0, 0.0, -11.0 / 9.0, -20.0 / 9.0],
+            [0.0, 0.0, -1.0, 0.0],
+        ];
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((m[i][j] - expected[i][j]).abs() < 1e-12);
+            }
+        }
+
+        // Off center bounds map to the edges of clip space
+        assert_close(project(m, -1.0, -2.0, -1.0), [-1.0, -1.0, -1.0]);
+        assert_close(project(m, 3.0, 1.0, -1.0), [1.0, 1.0, -1.0]);
+        assert_close(project(m, 30.0, 10.0, -10.0), [1.0, 1.0, 1.0]);
+
+        let convention = ProjectionConvention::new(Handedness::Left, DepthRange::ZeroToOne);
+        let m = Mat4f64::frustum(-1.0, 3.0, -2.0, 1.0, 1.0, 10.0, convention);
+        assert_close(project(m, -1.0, -2.0, 1.0), [-1.0, -1.0, 0.0]);
+        assert_close(project(m, 3.0, 1.0, 1.0), [1.0, 1.0, 0.0]);
+        assert_close(project(m, 30.0, 10.0, 10.0), [1.0, 1.0, 1.0]);
     }
 
     #[test]
-    fn matrix_addition() {
+    fn orthographic_projection() {
+        let convention = ProjectionConvention::new(Handedness::Right, DepthRange::NegativeOneToOne);
+        let m = Mat4f64::orthographic(-2.0, 2.0, -1.0, 3.0, 0.5, 10.0, convention);
+        assert_close(project(m, -2.0, -1.0, -0.5), [-1.0, -1.0, -1.0]);
+        assert_close(project(m, 2.0, 3.0, -10.0), [1.0, 1.0, 1.0]);
+        assert_close(project(m, 0.0, 1.0, -5.25), [0.0, 0.0, 0.0]);
+
+        let convention = ProjectionConvention::new(Handedness::Left, DepthRange::ZeroToOne);
+        let m = Mat4f64::orthographic(-2.0, 2.0, -1.0, 3.0, 0.5, 10.0, convention);
+        assert_close(project(m, -2.0, -1.0, 0.5), [-1.0, -1.0, 0.0]);
+        assert_close(project(m, 2.0, 3.0, 10.0), [1.0, 1.0, 1.0]);
+
+        let m = Mat4f64::orthographic(
+            -2.0,
+            2.0,
+            -1.0,
+            3.0,
+            0.5,
+            10.0,
+            convention.with_reversed_z(),
+        );
+        assert_close(project(m, -2.0, -1.0, 0.5), [-1.0, -1.0, 1.0]);
+        assert_close(project(m, 2.0, 3.0, 10.0), [1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn look_at_and_look_to() {
+        let eye = Vec3f64::new([0.0, 0.0, 5.0]);
+        let up = Vec3f64::new([0.0, 1.0, 0.0]);
+
+        // Right handed: the camera looks down the negative z axis
+        let m = Mat4f64::look_at(eye, Vec3f64::zero(), up, Handedness::Right);
+        let expected = Mat4f64::from_translation(Vec3f64::new([0.0, 0.0, -5.0]));
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((m[i][j] - expected[i][j]).abs() < 1e-12);
+            }
+        }
+
+        // Left handed: the camera looks down the positive z axis
+        let m = Mat4f64::look_at(eye, Vec3f64::zero(), up, Handedness::Left);
+        let target = m * Vec4f64::new([0.0, 0.0, 0.0, 1.0]);
+        let right = m * Vec4f64::new([-1.0, 0.0, 0.0, 0.0]);
+        assert!((target - Vec4f64::new([0.0, 0.0, 5.0, 1.0])).mag() < 1e-12);
+        assert!((right - Vec4f64::new([1.0, 0.0, 0.0, 0.0])).mag() < 1e-12);
+
+        // Look to is look at with a direction
+        let eye = Vec3f64::new([1.0, 2.0, 3.0]);
+        let dir = Vec3f64::new([2.0, -1.0, -4.0]);
+        for handedness in [Handedness::Right, Handedness::Left] {
+            let a = Mat4f64::look_to(eye, dir, up, handedness);
+            let b = Mat4f64::look_at(eye, eye + dir, up, handedness);
+            for i in 0..4 {
+                for j in 0..4 {
+                    assert!((a[i][j] - b[i][j]).abs() < 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn camera_basis() {
+        let eye = Vec3f64::new([1.0, 2.0, 3.0]);
+        let target = Vec3f64::new([-3.0, 0.5, -2.0]);
+        let up = Vec3f64::new([0.0, 1.0, 0.0]);
+        let forward = (target - eye).normed();
+        let close = |a: Vec3f64, b: Vec3f64| (a - b).mag() < 1e-12;
+
+        for handedness in [Handedness::Right, Handedness::Left] {
+            let m = Mat4f64::look_at(eye, target, up, handedness);
+            assert!(close(m.camera_position(), eye));
+            assert!(close(m.camera_forward(handedness), forward));
+
+            let right = m.camera_right();
+            let cam_up = m.camera_up();
+            assert!((right.mag() - 1.0).abs() < 1e-12);
+            assert!((cam_up.mag() - 1.0).abs() < 1e-12);
+            assert!(right.dot(forward).abs() < 1e-12);
+            assert!(cam_up.dot(forward).abs() < 1e-12);
+            assert!(right.dot(cam_up).abs() < 1e-12);
+            assert!(cam_up.dot(up) > 0.0);
+        }
+
+        // The up direction is the same in both conventions, right is mirrored
+        let r = Mat4f64::look_at(eye, target, up, Handedness::Right);
+        let l = Mat4f64::look_at(eye, target, up, Handedness::Left);
+        assert!(close(r.camera_up(), l.camera_up()));
+        assert!(close(r.camera_right(), forward.cross(up).normed()));
+        assert!(close(l.camera_right(), up.cross(forward).normed()));
+    }
+
+    #[test]
+    fn transform_points_and_vectors() {
+        let m = Mat4f64::from_translation(Vec3f64::new([1.0, 2.0, 3.0]))
+            * Mat4f64::from_scale(Vec3f64::new([2.0, 3.0, 4.0]));
+        let v = Vec3f64::new([1.0, -1.0, 0.5]);
+
+        let p = m.transform_point3(v);
+        assert_eq!(p[0], 3.0);
+        assert_eq!(p[1], -1.0);
+        assert_eq!(p[2], 5.0);
+
+        let d = m.transform_vector3(v);
+        assert_eq!(d[0], 2.0);
+        assert_eq!(d[1], -3.0);
+        assert_eq!(d[2], 2.0);
+
+        // Affine matrices have w = 1, so projecting equals transforming
+        let p = m.project_point3(v);
+        assert_eq!(p[0], 3.0);
+        assert_eq!(p[1], -1.0);
+        assert_eq!(p[2], 5.0);
+
+        // Projective matrices need the perspective divide
+        let m = Mat4f64::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ]);
+        let p = m.project_point3(Vec3f64::new([4.0, 2.0, 2.0]));
+        assert_eq!(p[0], 2.0);
+        assert_eq!(p[1], 1.0);
+        assert_eq!(p[2], 1.0);
+    }
+
+    #[test]
+    fn flat_arrays() {
         let m = Mat4f64::new([
             [1.0, 2.0, 3.0, 4.0],
             [5.0, 6.0, 7.0, 8.0],
             [9.0, 10.0, 11.0, 12.0],
             [13.0, 14.0, 15.0, 16.0],
         ]);
-        let mut n = Mat4f64::new([
-            [17.0, 18.0, 19.0, 20.0],
-            [21.0, 22.0, 23.0, 24.0],
-            [25.0, 26.0, 27.0, 28.0],
-            [29.0, 30.0, 31.0, 32.0],
+        let rows = m.as_row_major_flat();
+        let cols = m.as_col_major_flat();
+        let from_rows = Mat4f64::from_row_major_flat([
+            1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+        ]);
+        let from_cols = Mat4f64::from_col_major_flat([
+            1.0, 5.0, 9.0, 13.0, 2.0, 6.0, 10.0, 14.0, 3.0, 7.0, 11.0, 15.0, 4.0, 8.0, 12.0, 16.0,
         ]);
-        let r = n + m;
-        n += m;
 
-        assert_eq!(r[0][0], 18.0);
-        assert_eq!(r[0][1], 20.0);
-        assert_eq!(r[0][2], 22.0);
-        assert_eq!(r[0][3], 24.0);
-        assert_eq!(r[1][0], 26.0);
-        assert_eq!(r[1][1], 28.0);
-        assert_eq!(r[1][2], 30.0);
-        assert_eq!(r[1][3], 32.0);
-        assert_eq!(r[2][0], 34.0);
-        assert_eq!(r[2][1], 36.0);
-        assert_eq!(r[2][2], 38.0);
-        assert_eq!(r[2][3], 40.0);
-        assert_eq!(r[3][0], 42.0);
-        assert_eq!(r[3][1], 44.0);
-        assert_eq!(r[3][2], 46.0);
-        assert_eq!(r[3][3], 48.0);
+        assert_eq!(rows[0], 1.0);
+        assert_eq!(rows[1], 2.0);
+        assert_eq!(rows[2], 3.0);
+        assert_eq!(rows[3], 4.0);
+        assert_eq!(rows[4], 5.0);
+        assert_eq!(rows[5], 6.0);
+        assert_eq!(rows[6], 7.0);
+        assert_eq!(rows[7], 8.0);
+        assert_eq!(rows[8], 9.0);
+        assert_eq!(rows[9], 10.0);
+        assert_eq!(rows[10], 11.0);
+        assert_eq!(rows[11], 12.0);
+        assert_eq!(rows[12], 13.0);
+        assert_eq!(rows[13], 14.0);
+        assert_eq!(rows[14], 15.0);
+        assert_eq!(rows[15], 16.0);
 
-        assert_eq!(n[0][0], 18.0);
-        assert_eq!(n[0][1], 20.0);
-        assert_eq!(n[0][2], 22.0);
-        assert_eq!(n[0][3], 24.0);
-        assert_eq!(n[1][0], 26.0);
-        assert_eq!(n[1][1], 28.0);
-        assert_eq!(n[1][2], 30.0);
-        assert_eq!(n[1][3], 32.0);
-        assert_eq!(n[2][0], 34.0);
-        assert_eq!(n[2][1], 36.0);
-        assert_eq!(n[2][2], 38.0);
-        assert_eq!(n[2][3], 40.0);
-        assert_eq!(n[3][0], 42.0);
-        assert_eq!(n[3][1], 44.0);
-        assert_eq!(n[3][2], 46.0);
-        assert_eq!(n[3][3], 48.0);
+        assert_eq!(cols[0], 1.0);
+        assert_eq!(cols[1], 5.0);
+        assert_eq!(cols[2], 9.0);
+        assert_eq!(cols[3], 13.0);
+        assert_eq!(cols[4], 2.0);
+        assert_eq!(cols[5], 6.0);
+        assert_eq!(cols[6], 10.0);
+        assert_eq!(cols[7], 14.0);
+        assert_eq!(cols[8], 3.0);
+        assert_eq!(cols[9], 7.0);
+        assert_eq!(cols[10], 11.0);
+        assert_eq!(cols[11], 15.0);
+        assert_eq!(cols[12], 4.0);
+        assert_eq!(cols[13], 8.0);
+        assert_eq!(cols[14], 12.0);
+        assert_eq!(cols[15], 16.0);
+
+        assert_eq!(from_rows[0][0], 1.0);
+        assert_eq!(from_rows[0][1], 2.0);
+        assert_eq!(from_rows[0][2], 3.0);
+        assert_eq!(from_rows[0][3], 4.0);
+        assert_eq!(from_rows[1][0], 5.0);
+        assert_eq!(from_rows[1][1], 6.0);
+        assert_eq!(from_rows[1][2], 7.0);
+        assert_eq!(from_rows[1][3], 8.0);
+        assert_eq!(from_rows[2][0], 9.0);
+        assert_eq!(from_rows[2][1], 10.0);
+        assert_eq!(from_rows[2][2], 11.0);
+        assert_eq!(from_rows[2][3], 12.0);
+        assert_eq!(from_rows[3][0], 13.0);
+        assert_eq!(from_rows[3][1], 14.0);
+        assert_eq!(from_rows[3][2], 15.0);
+        assert_eq!(from_rows[3][3], 16.0);
+
+        assert_eq!(from_cols[0][0], 1.0);
+        assert_eq!(from_cols[0][1], 2.0);
+        assert_eq!(from_cols[0][2], 3.0);
+        assert_eq!(from_cols[0][3], 4.0);
+        assert_eq!(from_cols[1][0], 5.0);
+        assert_eq!(from_cols[1][1], 6.0);
+        assert_eq!(from_cols[1][2], 7.0);
+        assert_eq!(from_cols[1][3], 8.0);
+        assert_eq!(from_cols[2][0], 9.0);
+        assert_eq!(from_cols[2][1], 10.0);
+        assert_eq!(from_cols[2][2], 11.0);
+        assert_eq!(from_cols[2][3], 12.0);
+        assert_eq!(from_cols[3][0], 13.0);
+        assert_eq!(from_cols[3][1], 14.0);
+        assert_eq!(from_cols[3][2], 15.0);
+        assert_eq!(from_cols[3][3], 16.0);
+    }
+
+    #[test]
+    fn precision_conversion() {
+        let m = Mat4f64::new([
+            [1.0, 2.0 / 3.0, 3.0, 4.0 / 3.0],
+            [5.0 / 3.0, 6.0, 7.0 / 3.0, 8.0],
+            [9.0, 10.0 / 3.0, 11.0, 12.0 / 3.0],
+            [13.0 / 3.0, 14.0, 15.0 / 3.0, 16.0],
+        ]);
+        let c = m.to_f32();
+
+        assert_eq!(c[0][0], m[0][0] as f32);
+        assert_eq!(c[0][1], m[0][1] as f32);
+        assert_eq!(c[0][2], m[0][2] as f32);
+        assert_eq!(c[0][3], m[0][3] as f32);
+        assert_eq!(c[1][0], m[1][0] as f32);
+        assert_eq!(c[1][1], m[1][1] as f32);
+        assert_eq!(c[1][2], m[1][2] as f32);
+        assert_eq!(c[1][3], m[1][3] as f32);
+        assert_eq!(c[2][0], m[2][0] as f32);
+        assert_eq!(c[2][1], m[2][1] as f32);
+        assert_eq!(c[2][2], m[2][2] as f32);
+        assert_eq!(c[2][3], m[2][3] as f32);
+        assert_eq!(c[3][0], m[3][0] as f32);
+        assert_eq!(c[3][1], m[3][1] as f32);
+        assert_eq!(c[3][2], m[3][2] as f32);
+        assert_eq!(c[3][3], m[3][3] as f32);
+    }
+
+    #[test]
+    fn embedding_and_extraction() {
+        let s = Mat3f64::new([[11.0, 12.0, 13.0], [21.0, 22.0, 23.0], [31.0, 32.0, 33.0]]);
+        let e = Mat4f64::from_mat3(s);
+        let m = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        let u = m.upper_left();
+
+        assert_eq!(e[0][0], 11.0);
+        assert_eq!(e[0][1], 12.0);
+        assert_eq!(e[0][2], 13.0);
+        assert_eq!(e[0][3], 0.0);
+        assert_eq!(e[1][0], 21.0);
+        assert_eq!(e[1][1], 22.0);
+        assert_eq!(e[1][2], 23.0);
+        assert_eq!(e[1][3], 0.0);
+        assert_eq!(e[2][0], 31.0);
+        assert_eq!(e[2][1], 32.0);
+        assert_eq!(e[2][2], 33.0);
+        assert_eq!(e[2][3], 0.0);
+        assert_eq!(e[3][0], 0.0);
+        assert_eq!(e[3][1], 0.0);
+        assert_eq!(e[3][2], 0.0);
+        assert_eq!(e[3][3], 1.0);
+
+        assert_eq!(u[0][0], 1.0);
+        assert_eq!(u[0][1], 2.0);
+        assert_eq!(u[0][2], 3.0);
+        assert_eq!(u[1][0], 5.0);
+        assert_eq!(u[1][1], 6.0);
+        assert_eq!(u[1][2], 7.0);
+        assert_eq!(u[2][0], 9.0);
+        assert_eq!(u[2][1], 10.0);
+        assert_eq!(u[2][2], 11.0);
+    }
+
+    #[test]
+    fn decompose() {
+        let translation = Vec3f64::new([1.0, -2.0, 3.0]);
+        let rotation = Mat4f64::from_axis_angle(Vec3f64::new([1.0, 2.0, 3.0]).normed(), 0.8);
+        let scale = Vec3f64::new([2.0, 0.5, 3.0]);
+        let m = Mat4f64::from_translation(translation) * rotation * Mat4f64::from_scale(scale);
+        let d = m.decompose();
+
+        assert!((d.translation - translation).mag() < 1e-12);
+        assert!((d.scale - scale).mag() < 1e-12);
+        assert!(d.shear.mag() < 1e-12);
+        assert!(!d.is_lossy(1e-12));
+        assert!((d.rotation.determinant() - 1.0).abs() < 1e-12);
+        assert_mat_close(Mat4f64::from_mat3(d.rotation), rotation, 1e-12);
+        assert_mat_close(d.to_mat4(), m, 1e-12);
+
+        let trs = Mat4f64::from_trs(translation, rotation.upper_left(), scale);
+        assert_mat_close(trs, m, 1e-12);
+    }
+
+    #[test]
+    fn decompose_mirrored() {
+        let rotation = Mat4f64::from_rotation_z(0.5);
+        let m = Mat4f64::from_translation(Vec3f64::new([4.0, 5.0, 6.0]))
+            * rotation
+            * Mat4f64::from_scale(Vec3f64::new([2.0, -3.0, 4.0]));
+        let d = m.decompose();
+
+        assert!(d.scale[0] < 0.0);
+        assert!((d.scale[0] * d.scale[1] * d.scale[2] + 24.0).abs() < 1e-12);
+        assert!((d.rotation.determinant() - 1.0).abs() < 1e-12);
+        assert!(!d.is_lossy(1e-12));
+        assert_mat_close(d.to_mat4(), m, 1e-12);
+    }
+
+    #[test]
+    fn decompose_sheared() {
+        let m = Mat4f64::from_translation(Vec3f64::new([1.0, 2.0, 3.0]))
+            * Mat4f64::from_rotation_y(0.3)
+            * Mat4f64::from_scale(Vec3f64::new([2.0, 3.0, 4.0]))
+            * Mat4f64::from_shear(0.5, 0.25, 0.0, 0.75, 0.0, 0.0);
+        let d = m.decompose();
+
+        assert!((d.shear - Vec3f64::new([0.5, 0.25, 0.75])).mag() < 1e-12);
+        assert!(d.is_lossy(1e-12));
+        assert!((d.scale - Vec3f64::new([2.0, 3.0, 4.0])).mag() < 1e-12);
+        assert_mat_close(d.to_mat4_with_shear(), m, 1e-12);
+
+        // Mirrored and sheared
+        let m = m * Mat4f64::from_scale(Vec3f64::new([1.0, 1.0, -1.0]));
+        let d = m.decompose();
+        assert!(d.scale[0] < 0.0);
+        assert!((d.rotation.determinant() - 1.0).abs() < 1e-12);
+        assert_mat_close(d.to_mat4_with_shear(), m, 1e-12);
+    }
+
+    #[test]
+    fn decompose_projective() {
+        let convention = ProjectionConvention::new(Handedness::Right, DepthRange::ZeroToOne);
+        let m = Mat4f64::perspective(1.0, 1.5, 0.1, 10.0, convention);
+        let d = m.decompose();
+        assert_eq!(d.last_row.coords, m.rows[3]);
+        assert!(d.is_lossy(1e-12));
+        assert!(!d.is_degenerate());
+
+        let affine = Mat4f64::from_translation(Vec3f64::new([1.0, 2.0, 3.0]));
+        assert!(!affine.decompose().is_lossy(1e-12));
+    }
+
+    #[test]
+    fn decompose_degenerate() {
+        let translation = Vec3f64::new([1.0, -2.0, 3.0]);
+        let rotation = Mat4f64::from_axis_angle(Vec3f64::new([1.0, 2.0, 3.0]).normed(), 0.8);
+        let flattened = [
+            Vec3f64::new([0.0, 2.0, 3.0]),
+            Vec3f64::new([2.0, 0.0, 3.0]),
+            Vec3f64::new([2.0, 3.0, 0.0]),
+            Vec3f64::new([0.0, 0.0, 3.0]),
+            Vec3f64::new([0.0, -2.0, 0.0]),
+            Vec3f64::new([0.0, 0.0, 0.0]),
+        ];
+        for scale in flattened {
+            let m = Mat4f64::from_translation(translation) * rotation * Mat4f64::from_scale(scale);
+            let d = m.decompose();
+            assert!(d.is_degenerate());
+            assert_mat_close(
+                Mat4f64::from_mat3(d.rotation.transposed() * d.rotation),
+                Mat4f64::identity(),
+                1e-12,
+            );
+            assert!((d.rotation.determinant() - 1.0).abs() < 1e-12);
+            assert!(!d.is_lossy(1e-12));
+            assert_mat_close(d.to_mat4(), m, 1e-12);
+        }
+
+        // A y axis parallel to the x axis is expressed as shear
+        let m = Mat4f64::new([
+            [1.0, 2.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        let d = m.decompose();
+        assert!(d.is_degenerate());
+        assert_eq!(d.scale[1], 0.0);
+        assert!((d.shear[0] - 2.0).abs() < 1e-12);
+        assert!((d.rotation.determinant() - 1.0).abs() < 1e-12);
+        assert_mat_close(d.to_mat4_with_shear(), m, 1e-12);
+    }
+
+    #[test]
+    fn symmetric_eigen() {
+        let m = Mat4f64::new([
+            [2.0, -1.0, 0.0, 0.0],
+            [-1.0, 2.0, -1.0, 0.0],
+            [0.0, -1.0, 2.0, -1.0],
+            [0.0, 0.0, -1.0, 2.0],
+        ]);
+        let (values, vectors) = m.symmetric_eigen();
+        // The eigenvalues of this matrix are 2 - 2 cos(k pi / 5)
+        for (i, k) in [4.0, 3.0, 2.0, 1.0].into_iter().enumerate() {
+            let expected = 2.0 - 2.0 * (k * std::f64::consts::PI / 5.0).cos();
+            assert!((values[i] - expected).abs() < 1e-12);
+        }
+        assert_eigen(m, values, vectors);
+
+        // Already diagonal
+        let m = Mat4f64::from_diagonal(Vec4f64::new([1.0, 4.0, 2.0, 3.0]));
+        let (values, vectors) = m.symmetric_eigen();
+        assert_eq!(values.coords, [4.0, 3.0, 2.0, 1.0]);
+        assert_eigen(m, values, vectors);
+
+        // Reconstructed from its eigendecomposition
+        let m = Mat4f64::new([
+            [4.0, -2.0, 1.0, 0.5],
+            [-2.0, 6.0, 0.5, 1.0],
+            [1.0, 0.5, 3.0, -1.0],
+            [0.5, 1.0, -1.0, 5.0],
+        ]);
+        let (values, vectors) = m.symmetric_eigen();
+        assert_eigen(m, values, vectors);
+        assert_mat_close(
+            vectors * Mat4f64::from_diagonal(values) * vectors.transposed(),
+            m,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn lu_decomposition() {
+        let m = Mat4f64::new([
+            [0.0, 2.0, 0.0, 1.0],
+            [2.0, 2.0, 3.0, 2.0],
+            [4.0, -3.0, 0.0, 1.0],
+            [6.0, 1.0, -6.0, -5.0],
+        ]);
+        let lu = m.lu(1e-12).unwrap();
+        let (l, u, p) = (lu.l(), lu.u(), lu.p());
+        let pa = p * m;
+        let product = l * u;
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((pa[i][j] - product[i][j]).abs() < 1e-12);
+                if j > i {
+                    assert_eq!(l[i][j], 0.0);
+                }
+                if j < i {
+                    assert_eq!(u[i][j], 0.0);
+                }
+            }
+            assert_eq!(l[i][i], 1.0);
+        }
+        // The first pivot is 0.0, so rows have to be swapped
+        assert_eq!(p[0][0], 0.0);
+        assert!((lu.determinant() - m.determinant()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn solve() {
+        let m = Mat4f64::new([
+            [0.0, 2.0, 0.0, 1.0],
+            [2.0, 2.0, 3.0, 2.0],
+            [4.0, -3.0, 0.0, 1.0],
+            [6.0, 1.0, -6.0, -5.0],
+        ]);
+        let b = Vec4f64::new([-3.5, 8.0, 10.5, -16.5]);
+        let x = m.solve(b, 1e-12).unwrap();
+        let expected = [1.0, -2.0, 3.0, 0.5];
+        for i in 0..4 {
+            assert!((x[i] - expected[i]).abs() < 1e-12);
+        }
+
+        // Singular matrices
+        let singular = Mat4f64::new([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 10.0, 11.0, 12.0],
+            [13.0, 14.0, 15.0, 16.0],
+        ]);
+        assert!(singular.lu(1e-12).is_none());
+        assert!(singular.solve(b, 1e-12).is_none());
+        assert!(Mat4f64::zero().lu(0.0).is_none());
+        assert!(Mat4f64::identity().lu(0.0).is_some());
+    }
+
+    #[test]
+    fn qr_decomposition() {
+        let m = Mat4f64::new([
+            [2.0, -1.0, 0.0, 3.0],
+            [1.0, 3.0, -2.0, 1.0],
+            [0.0, 1.0, 4.0, -1.0],
+            [-2.0, 1.0, 1.0, 2.0],
+        ]);
+        let (q, r) = m.qr();
+        let product = q * r;
+
+        assert_orthonormal(q);
+        for i in 0..4 {
+            assert!(r[i][i] >= 0.0);
+            for j in 0..4 {
+                if j < i {
+                    assert_eq!(r[i][j], 0.0);
+                }
+                assert!((product[i][j] - m[i][j]).abs() < 1e-12);
+            }
+        }
+
+        let (q, r) = Mat4f64::identity().qr();
+        for i in 0..4 {
+            for j in 0..4 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((q[i][j] - expected).abs() < 1e-12);
+                assert!((r[i][j] - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn orthonormalize() {
+        let rotation =
+            Mat4f64::from_axis_angle(crate::vec::vec3f64::Vec3f64::new([0.0, 0.6, 0.8]), 1.0);
+        let mut drifted = rotation;
+        for i in 0..4 {
+            for j in 0..4 {
+                drifted[i][j] += 0.01 * ((i * 4 + j) % 3) as f64 - 0.01;
+            }
+        }
+        let original = drifted.col(0).normed();
+        let orthonormalized = drifted.orthonormalized();
+        drifted.orthonormalize();
+
+        assert_orthonormal(orthonormalized);
+        assert_orthonormal(drifted);
+        assert!((orthonormalized.determinant() - 1.0).abs() < 1e-12);
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((orthonormalized[i][j] - rotation[i][j]).abs() < 0.05);
+            }
+        }
+
+        // The first column keeps its direction
+        assert!((orthonormalized.col(0) - original).mag() < 1e-12);
+    }
+
+    #[test]
+    fn cholesky() {
+        let m = Mat4f64::new([
+            [4.0, 1.0, 0.0, 1.0],
+            [1.0, 3.0, 1.0, 0.0],
+            [0.0, 1.0, 2.0, 1.0],
+            [1.0, 0.0, 1.0, 5.0],
+        ]);
+        let cholesky = m.cholesky(1e-12).unwrap();
+        let l = cholesky.l();
+        assert_mat_close(l * l.transposed(), m, 1e-12);
+        for i in 0..4 {
+            assert!(l[i][i] > 0.0);
+            for j in i + 1..4 {
+                assert_eq!(l[i][j], 0.0);
+            }
+        }
+
+        let x = cholesky.solve(Vec4f64::new([2.5, -2.0, 4.5, 6.5]));
+        assert!((x - Vec4f64::new([1.0, -2.0, 3.0, 0.5])).mag() < 1e-12);
+        assert!((cholesky.determinant() - m.determinant()).abs() < 1e-12);
+        assert_mat_close(cholesky.inverse() * m, Mat4f64::identity(), 1e-12);
+
+        // Not positive definite
+        assert!(Mat4f64::new([
+            [1.0, 2.0, 0.0, 0.0],
+            [2.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 2.0, 1.0],
+            [0.0, 0.0, 1.0, -2.0]
+        ])
+        .cholesky(1e-12)
+        .is_none());
+        assert!((-1.0 * Mat4f64::identity()).cholesky(1e-12).is_none());
+        assert!(Mat4f64::zero().cholesky(0.0).is_none());
+    }
+
+    #[test]
+    fn ldlt() {
+        for m in [
+            Mat4f64::new([
+                [4.0, 1.0, 0.0, 1.0],
+                [1.0, 3.0, 1.0, 0.0],
+                [0.0, 1.0, 2.0, 1.0],
+                [1.0, 0.0, 1.0, 5.0],
+            ]),
+            Mat4f64::new([
+                [1.0, 2.0, 0.0, 0.0],
+                [2.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 2.0, 1.0],
+                [0.0, 0.0, 1.0, -2.0],
+            ]),
+        ] {
+            let ldlt = m.ldlt(1e-12).unwrap();
+            let l = ldlt.l();
+            let d = Mat4f64::from_diagonal(ldlt.d());
+            assert_mat_close(l * d * l.transposed(), m, 1e-12);
+            for i in 0..4 {
+                assert_eq!(l[i][i], 1.0);
+                for j in i + 1..4 {
+                    assert_eq!(l[i][j], 0.0);
+                }
+            }
+            assert!((ldlt.determinant() - m.determinant()).abs() < 1e-12);
+        }
+
+        let m = Mat4f64::new([
+            [1.0, 2.0, 0.0, 0.0],
+            [2.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 2.0, 1.0],
+            [0.0, 0.0, 1.0, -2.0],
+        ]);
+        let x = m
+            .ldlt(1e-12)
+            .unwrap()
+            .solve(Vec4f64::new([-3.0, 0.0, 6.5, 2.0]));
+        assert!((x - Vec4f64::new([1.0, -2.0, 3.0, 0.5])).mag() < 1e-12);
+
+        assert!(Mat4f64::zero().ldlt(0.0).is_none());
+    }
+
+    #[test]
+    fn symmetric_inverse() {
+        for m in [
+            Mat4f64::new([
+                [4.0, 1.0, 0.0, 1.0],
+                [1.0, 3.0, 1.0, 0.0],
+                [0.0, 1.0, 2.0, 1.0],
+                [1.0, 0.0, 1.0, 5.0],
+            ]),
+            Mat4f64::new([
+                [1.0, 2.0, 0.0, 0.0],
+                [2.0, 1.0, 0.0, 0.0],
+                [0.0, 0.0, 2.0, 1.0],
+                [0.0, 0.0, 1.0, -2.0],
+            ]),
+        ] {
+            let inverse = m.symmetric_inverse(1e-12).unwrap();
+            assert_mat_close(inverse * m, Mat4f64::identity(), 1e-12);
+            assert_mat_close(inverse, m.inverse(), 1e-12);
+        }
+        assert!(Mat4f64::zero().symmetric_inverse(1e-12).is_none());
+    }
+
+    #[test]
+    fn powi() {
+        let m = Mat4f64::new([
+            [1.0, 2.0, 0.0, 1.0],
+            [0.0, 1.0, -1.0, 2.0],
+            [2.0, 0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0, 3.0],
+        ]);
+        assert_eq!(m.powi(0).as_row_major(), Mat4f64::identity().as_row_major());
+        assert_eq!(m.powi(1).as_row_major(), m.as_row_major());
+        assert_mat_close(m.powi(3), m * m * m, 1e-12);
+        assert_mat_close(m.powi(-2), m.inverse() * m.inverse(), 1e-12);
+        assert_mat_close(m.powi(5) * m.powi(-5), Mat4f64::identity(), 1e-12);
+    }
+
+    #[test]
+    fn powf() {
+        let rotation = Mat4f64::new([
+            [0.7648421872844885, -0.644217687237691, 0.0, 0.0],
+            [0.644217687237691, 0.7648421872844885, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert_mat_close(
+            rotation.powf(0.5).unwrap(),
+            Mat4f64::new([
+                [0.9393727128473789, -0.34289780745545134, 0.0, 0.0],
+                [0.34289780745545134, 0.9393727128473789, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(rotation.powf(0.0).unwrap(), Mat4f64::identity(), 1e-12);
+
+        let m = Mat4f64::new([
+            [4.0, 1.0, 0.0, 1.0],
+            [1.0, 3.0, 1.0, 0.0],
+            [0.0, 1.0, 2.0, 1.0],
+            [1.0, 0.0, 1.0, 5.0],
+        ]);
+        assert_mat_close(m.powf(2.0).unwrap(), m * m, 1e-12);
+        assert_mat_close(m.powf(-1.0).unwrap(), m.inverse(), 1e-12);
+        assert!((-1.0 * Mat4f64::identity()).powf(0.5).is_none());
+    }
+
+    #[test]
+    fn exp() {
+        assert_eq!(
+            Mat4f64::zero().exp().as_row_major(),
+            Mat4f64::identity().as_row_major()
+        );
+        assert_mat_close(
+            Mat4f64::new([
+                [0.0, -0.7, 0.0, 0.0],
+                [0.7, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0],
+            ])
+            .exp(),
+            Mat4f64::new([
+                [0.7648421872844885, -0.644217687237691, 0.0, 0.0],
+                [0.644217687237691, 0.7648421872844885, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat4f64::new([
+                [0.5, 0.0, 0.0, 0.0],
+                [0.0, 2.0, 0.0, 0.0],
+                [0.0, 0.0, -1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.5],
+            ])
+            .exp(),
+            Mat4f64::new([
+                [1.6487212707001282, 0.0, 0.0, 0.0],
+                [0.0, 7.38905609893065, 0.0, 0.0],
+                [0.0, 0.0, 0.36787944117144233, 0.0],
+                [0.0, 0.0, 0.0, 4.4816890703380645],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat4f64::new([
+                [0.0, 0.5, 0.0, 0.0],
+                [0.0, 0.0, 0.5, 0.0],
+                [0.0, 0.0, 0.0, 0.5],
+                [0.0, 0.0, 0.0, 0.0],
+            ])
+            .exp(),
+            Mat4f64::new([
+                [1.0, 0.5, 0.125, 0.020833333333333332],
+                [0.0, 1.0, 0.5, 0.125],
+                [0.0, 0.0, 1.0, 0.5],
+                [0.0, 0.0, 0.0, 1.0],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat4f64::new([
+                [0.1, -0.3, 0.2, 0.05],
+                [0.25, 0.2, -0.1, 0.0],
+                [-0.05, 0.15, -0.2, 0.1],
+                [0.3, 0.0, 0.1, 0.1],
+            ])
+            .exp(),
+            Mat4f64::new([
+                [
+                    1.0679480820826202,
+                    -0.3284335098543491,
+                    0.20641705056836485,
+                    0.06520280110588672,
+                ],
+                [
+                    0.28874531232918227,
+                    1.1705210820152583,
+                    -0.07312759573233157,
+                    0.002863340608328017,
+                ],
+                [
+                    -0.012841007193563073,
+                    0.15484355595017138,
+                    0.8136993602401515,
+                    0.09462528294078523,
+                ],
+                [
+                    0.32646810954455574,
+                    -0.041552092208460005,
+                    0.1269996314861675,
+                    1.119461095106177,
+                ],
+            ]),
+            1e-12,
+        );
+        let large = Mat4f64::new([
+            [1.0, 2.0, 0.0, 1.0],
+            [0.0, 1.0, -1.0, 2.0],
+            [2.0, 0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0, 3.0],
+        ]);
+        let expected = Mat4f64::new([
+            [
+                7.874766627553315,
+                15.634650600035878,
+                -4.970636778796116,
+                29.49485527966433,
+            ],
+            [
+                3.8828018608276698,
+                11.81613571476175,
+                -4.875940291206804,
+                25.517356931247345,
+            ],
+            [
+                7.810479651999773,
+                9.941273557592233,
+                0.05059567951721109,
+                15.648341896072209,
+            ],
+            [
+                11.787978000416755,
+                21.61211710524746,
+                -5.882770017622269,
+                47.09906452445903,
+            ],
+        ]);
+        assert_mat_close(large.exp(), expected, 1e-12 * large.exp().frobenius_norm());
+    }
+
+    #[test]
+    fn log() {
+        assert_eq!(
+            Mat4f64::identity().log().unwrap().as_row_major(),
+            Mat4f64::zero().as_row_major()
+        );
+        assert_mat_close(
+            Mat4f64::new([
+                [0.7648421872844885, -0.644217687237691, 0.0, 0.0],
+                [0.644217687237691, 0.7648421872844885, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ])
+            .log()
+            .unwrap(),
+            Mat4f64::new([
+                [0.0, -0.7, 0.0, 0.0],
+                [0.7, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat4f64::new([
+                [0.1, -0.3, 0.2, 0.05],
+                [0.25, 0.2, -0.1, 0.0],
+                [-0.05, 0.15, -0.2, 0.1],
+                [0.3, 0.0, 0.1, 0.1],
+            ])
+            .exp()
+            .log()
+            .unwrap(),
+            Mat4f64::new([
+                [0.1, -0.3, 0.2, 0.05],
+                [0.25, 0.2, -0.1, 0.0],
+                [-0.05, 0.15, -0.2, 0.1],
+                [0.3, 0.0, 0.1, 0.1],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat4f64::new([
+                [4.0, 1.0, 0.0, 1.0],
+                [1.0, 3.0, 1.0, 0.0],
+                [0.0, 1.0, 2.0, 1.0],
+                [1.0, 0.0, 1.0, 5.0],
+            ])
+            .log()
+            .unwrap(),
+            Mat4f64::new([
+                [
+                    1.3061047417234815,
+                    0.3347952867143343,
+                    -0.12730283365896256,
+                    0.2546056673179251,
+                ],
+                [
+                    0.3347952867143343,
+                    0.9477528478778704,
+                    0.48565472750457356,
+                    -0.10374622652768588,
+                ],
+                [
+                    -0.12730283365896256,
+                    0.48565472750457356,
+                    0.48565472750457356,
+                    0.358351893845611,
+                ],
+                [
+                    0.2546056673179251,
+                    -0.10374622652768588,
+                    0.358351893845611,
+                    1.5371538019101298,
+                ],
+            ]),
+            1e-12,
+        );
+        assert!((-1.0 * Mat4f64::identity()).log().is_none());
+        assert!(Mat4f64::zero().log().is_none());
+    }
+
+    #[test]
+    fn sqrt() {
+        let m = Mat4f64::new([
+            [4.0, 1.0, 0.0, 1.0],
+            [1.0, 3.0, 1.0, 0.0],
+            [0.0, 1.0, 2.0, 1.0],
+            [1.0, 0.0, 1.0, 5.0],
+        ]);
+        let root = m.sqrt().unwrap();
+        assert_mat_close(root * root, m, 1e-12);
+        assert_mat_close(
+            root,
+            Mat4f64::new([
+                [
+                    1.96388086726514,
+                    0.282581696514798,
+                    -0.05075163681853535,
+                    0.24646256377993792,
+                ],
+                [
+                    0.282581696514798,
+                    1.6739829187085042,
+                    0.340649585375171,
+                    -0.043435384776697714,
+                ],
+                [
+                    -0.05075163681853535,
+                    0.340649585375171,
+                    1.340649585375171,
+                    0.28989794855663564,
+                ],
+                [
+                    0.24646256377993792,
+                    -0.043435384776697714,
+                    0.28989794855663564,
+                    2.2030271790032403,
+                ],
+            ]),
+            1e-12,
+        );
+        assert_mat_close(
+            Mat4f64::new([
+                [0.7648421872844885, -0.644217687237691, 0.0, 0.0],
+                [0.644217687237691, 0.7648421872844885, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ])
+            .sqrt()
+            .unwrap(),
+            Mat4f64::new([
+                [0.9393727128473789, -0.34289780745545134, 0.0, 0.0],
+                [0.34289780745545134, 0.9393727128473789, 0.0, 0.0],
+                [0.0, 0.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0, 1.0],
+            ]),
+            1e-12,
+        );
+        assert_eq!(
+            Mat4f64::identity().sqrt().unwrap().as_row_major(),
+            Mat4f64::identity().as_row_major()
+        );
+        assert!((-1.0 * Mat4f64::identity()).sqrt().is_none());
+        assert_eq!(
+            Mat4f64::zero().sqrt().unwrap().as_row_major(),
+            Mat4f64::zero().as_row_major()
+        );
+
+        // Singular positive semidefinite matrices have a square root
+        assert_mat_close(
+            Mat4f64::from_diagonal(Vec4f64::new([4.0, 0.0, 9.0, 1.0]))
+                .sqrt()
+                .unwrap(),
+            Mat4f64::from_diagonal(Vec4f64::new([2.0, 0.0, 3.0, 1.0])),
+            1e-12,
+        );
+        // A rank one matrix v * v^T has the square root v * v^T / |v|
+        let v = Vec4f64::new([1.0, 2.0, 0.0, 2.0]);
+        let m = Mat4f64::outer_product(v, v);
+        assert_mat_close(m.sqrt().unwrap(), m / 9.0f64.sqrt(), 1e-12);
+        // Singular matrices that are not symmetric need not have a square root
+        assert!(Mat4f64::new([
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+        .sqrt()
+        .is_none());
+    }
+
+    #[test]
+    fn rank() {
+        assert_eq!(
+            Mat4f64::new([
+                [2.5, 0.5, 0.0, -0.5],
+                [0.0, 2.5, 0.5, 0.0],
+                [0.5, 0.0, 2.5, 0.5],
+                [-0.5, 0.5, 0.0, 2.5]
+            ])
+            .rank(1e-12),
+            4
+        );
+        assert_eq!(
+            Mat4f64::new([
+                [2.0, 1.0, -1.0, 0.5],
+                [-4.0, -2.0, 2.0, -1.0],
+                [1.0, 0.5, -0.5, 0.25],
+                [6.0, 3.0, -3.0, 1.5]
+            ])
+            .rank(1e-12),
+            1
+        );
+        assert_eq!(Mat4f64::zero().rank(1e-12), 0);
+    }
+
+    #[test]
+    fn null_space() {
+        let m = Mat4f64::new([
+            [2.0, 1.0, -1.0, 0.5],
+            [-4.0, -2.0, 2.0, -1.0],
+            [1.0, 0.5, -0.5, 0.25],
+            [6.0, 3.0, -3.0, 1.5],
+        ]);
+        let basis = m.null_space(1e-12);
+        assert_eq!(basis.len(), 3);
+        for (i, v) in basis.iter().enumerate() {
+            assert!((m * *v).mag() < 1e-12);
+            assert!((v.mag() - 1.0).abs() < 1e-12);
+            for w in &basis[..i] {
+                assert!(v.dot(*w).abs() < 1e-12);
+            }
+        }
+
+        let m = Mat4f64::new([
+            [2.5, 0.5, 0.0, -0.5],
+            [0.0, 2.5, 0.5, 0.0],
+            [0.5, 0.0, 2.5, 0.5],
+            [-0.5, 0.5, 0.0, 2.5],
+        ]);
+        let basis = m.null_space(1e-12);
+        assert_eq!(basis.len(), 0);
+        for v in basis {
+            assert!((m * v).mag() < 1e-12);
+        }
+        assert_eq!(Mat4f64::zero().null_space(1e-12).len(), 4);
     }
 
     #[test]
-    fn matrix_subtraction() {
+    fn condition_number() {
         let m = Mat4f64::new([
-            [1.0, 2.0, 3.0, 4.0],
-            [5.0, 6.0, 7.0, 8.0],
-            [9.0, 10.0, 11.0, 12.0],
-            [13.0, 14.0, 15.0, 16.0],
-        ]);
-        let mut n = Mat4f64::new([
-            [17.0, 18.0, 19.0, 20.0],
-            [21.0, 22.0, 23.0, 24.0],
-            [25.0, 26.0, 27.0, 28.0],
-            [29.0, 30.0, 31.0, 32.0],
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0, 0.0],
+            [0.0, 0.0, 4.0, 0.0],
+            [0.0, 0.0, 0.0, 8.0],
         ]);
-        let r = m - n;
-        n -= m;
+        assert!((Mat4f64::identity().condition_number() - 4.0).abs() < 1e-12);
+        assert!((m.condition_number() - 10.625).abs() < 1e-12);
+        assert!(
+            Mat4f64::new([
+                [2.0, 1.0, -1.0, 0.5],
+                [-4.0, -2.0, 2.0, -1.0],
+                [1.0, 0.5, -0.5, 0.25],
+                [6.0, 3.0, -3.0, 1.5]
+            ])
+            .condition_number()
+                > 1e6
+        );
+        assert_eq!(Mat4f64::zero().condition_number(), f64::INFINITY);
+    }
 
-        assert_eq!(r[0][0], -16.0);
-        assert_eq!(r[0][1], -16.0);
-        assert_eq!(r[0][2], -16.0);
-        assert_eq!(r[0][3], -16.0);
-        assert_eq!(r[1][0], -16.0);
-        assert_eq!(r[1][1], -16.0);
-        assert_eq!(r[1][2], -16.0);
-        assert_eq!(r[1][3], -16.0);
-        assert_eq!(r[2][0], -16.0);
-        assert_eq!(r[2][1], -16.0);
-        assert_eq!(r[2][2], -16.0);
-        assert_eq!(r[2][3], -16.0);
-        assert_eq!(r[3][0], -16.0);
-        assert_eq!(r[3][1], -16.0);
-        assert_eq!(r[3][2], -16.0);
-        assert_eq!(r[3][3], -16.0);
+    #[test]
+    fn is_invertible() {
+        assert!(Mat4f64::new([
+            [2.5, 0.5, 0.0, -0.5],
+            [0.0, 2.5, 0.5, 0.0],
+            [0.5, 0.0, 2.5, 0.5],
+            [-0.5, 0.5, 0.0, 2.5]
+        ])
+        .is_invertible(1e-12));
+        assert!(!Mat4f64::new([
+            [2.0, 1.0, -1.0, 0.5],
+            [-4.0, -2.0, 2.0, -1.0],
+            [1.0, 0.5, -0.5, 0.25],
+            [6.0, 3.0, -3.0, 1.5]
+        ])
+        .is_invertible(1e-12));
+        assert!(!Mat4f64::zero().is_invertible(0.0));
+    }
 
-        assert_eq!(n[0][0], 16.0);
-        assert_eq!(n[0][1], 16.0);
-        assert_eq!(n[0][2], 16.0);
-        assert_eq!(n[0][3], 16.0);
-        assert_eq!(n[1][0], 16.0);
-        assert_eq!(n[1][1], 16.0);
-        assert_eq!(n[1][2], 16.0);
-        assert_eq!(n[1][3], 16.0);
-        assert_eq!(n[2][0], 16.0);
-        assert_eq!(n[2][1], 16.0);
-        assert_eq!(n[2][2], 16.0);
-        assert_eq!(n[2][3], 16.0);
-        assert_eq!(n[3][0], 16.0);
-        assert_eq!(n[3][1], 16.0);
-        assert_eq!(n[3][2], 16.0);
-        assert_eq!(n[3][3], 16.0);
+    #[test]
+    fn is_orthogonal() {
+        let rotation = Mat4f64::new([
+            [0.6, -0.8, 0.0, 0.0],
+            [0.8, 0.6, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert!(rotation.is_orthogonal(1e-12));
+        assert!((-1.0 * rotation).is_orthogonal(1e-12));
+        assert!(!(2.0 * rotation).is_orthogonal(1e-12));
+        assert!(!Mat4f64::new([
+            [2.5, 0.5, 0.0, -0.5],
+            [0.0, 2.5, 0.5, 0.0],
+            [0.5, 0.0, 2.5, 0.5],
+            [-0.5, 0.5, 0.0, 2.5]
+        ])
+        .is_orthogonal(1e-12));
     }
 
     #[test]
-    fn scalar_multiplication() {
-        let mut m = Mat4f64::new([
-            [1.0, 2.0, 3.0, 4.0],
-            [5.0, 6.0, 7.0, 8.0],
-            [9.0, 10.0, 11.0, 12.0],
-            [13.0, 14.0, 15.0, 16.0],
+    fn is_symmetric() {
+        let m = Mat4f64::new([
+            [2.5, 0.5, 0.0, -0.5],
+            [0.0, 2.5, 0.5, 0.0],
+            [0.5, 0.0, 2.5, 0.5],
+            [-0.5, 0.5, 0.0, 2.5],
         ]);
-        let n = m * 3.0;
-        assert_eq!(n[0][0], 3.0);
-        assert_eq!(n[0][1], 6.0);
-        assert_eq!(n[0][2], 9.0);
-        assert_eq!(n[0][3], 12.0);
-        assert_eq!(n[1][0], 15.0);
-        assert_eq!(n[1][1], 18.0);
-        assert_eq!(n[1][2], 21.0);
-        assert_eq!(n[1][3], 24.0);
-        assert_eq!(n[2][0], 27.0);
-        assert_eq!(n[2][1], 30.0);
-        assert_eq!(n[2][2], 33.0);
-        assert_eq!(n[2][3], 36.0);
-        assert_eq!(n[3][0], 39.0);
-        assert_eq!(n[3][1], 42.0);
-        assert_eq!(n[3][2], 45.0);
-        assert_eq!(n[3][3], 48.0);
+        assert!(!m.is_symmetric(1e-12));
+        assert!((m + m.transposed()).is_symmetric(1e-12));
+        let v = Vec4f64::new([1.0, -2.0, 0.5, 3.0]);
+        assert!(Mat4f64::outer_product(v, v).is_symmetric(1e-12));
+        assert!(!Mat4f64::outer_product(v, 2.0 * v + Vec4f64::ones()).is_symmetric(1e-12));
+    }
 
-        let n = 3.0 * m;
-        assert_eq!(n[0][0], 3.0);
-        assert_eq!(n[0][1], 6.0);
-        assert_eq!(n[0][2], 9.0);
-        assert_eq!(n[0][3], 12.0);
-        assert_eq!(n[1][0], 15.0);
-        assert_eq!(n[1][1], 18.0);
-        assert_eq!(n[1][2], 21.0);
-        assert_eq!(n[1][3], 24.0);
-        assert_eq!(n[2][0], 27.0);
-        assert_eq!(n[2][1], 30.0);
-        assert_eq!(n[2][2], 33.0);
-        assert_eq!(n[2][3], 36.0);
-        assert_eq!(n[3][0], 39.0);
-        assert_eq!(n[3][1], 42.0);
-        assert_eq!(n[3][2], 45.0);
-        assert_eq!(n[3][3], 48.0);
+    #[test]
+    fn inverse_affine() {
+        let m = Mat4f64::from_translation(Vec3f64::new([1.0, -2.0, 3.0]))
+            .rotate(Vec3f64::new([1.0, 2.0, -1.0]).normed(), 0.8)
+            .scale(Vec3f64::new([2.0, 0.5, -3.0]))
+            .shear(0.3, 0.0, -0.2, 0.0, 0.1, 0.0);
+        let inverse = m.inverse_affine();
+        assert_mat_close(inverse, m.inverse(), 1e-12);
+        assert_mat_close(inverse * m, Mat4f64::identity(), 1e-12);
+        assert_eq!(inverse.rows[3], [0.0, 0.0, 0.0, 1.0]);
+    }
 
-        m *= 3.0;
-        assert_eq!(m[0][0], 3.0);
-        assert_eq!(m[0][1], 6.0);
-        assert_eq!(m[0][2], 9.0);
-        assert_eq!(m[0][3], 12.0);
-        assert_eq!(m[1][0], 15.0);
-        assert_eq!(m[1][1], 18.0);
-        assert_eq!(m[1][2], 21.0);
-        assert_eq!(m[1][3], 24.0);
-        assert_eq!(m[2][0], 27.0);
-        assert_eq!(m[2][1], 30.0);
-        assert_eq!(m[2][2], 33.0);
-        assert_eq!(m[2][3], 36.0);
-        assert_eq!(m[3][0], 39.0);
-        assert_eq!(m[3][1], 42.0);
-        assert_eq!(m[3][2], 45.0);
-        assert_eq!(m[3][3], 48.0);
+    #[test]
+    fn inverse_rigid() {
+        let m = Mat4f64::from_axis_angle(Vec3f64::new([-1.0, 2.0, 2.0]).normed(), 2.1)
+            .translate(Vec3f64::new([4.0, 0.5, -1.0]));
+        let inverse = m.inverse_rigid();
+        assert_mat_close(inverse, m.inverse(), 1e-12);
+        assert_mat_close(inverse * m, Mat4f64::identity(), 1e-12);
+        assert_eq!(inverse.rows[3], [0.0, 0.0, 0.0, 1.0]);
     }
 
     #[test]
-    fn scalar_division() {
-        let mut m = Mat4f64::new([
-            [1.0, 2.0, 3.0, 4.0],
-            [5.0, 6.0, 7.0, 8.0],
-            [9.0, 10.0, 11.0, 12.0],
-            [13.0, 14.0, 15.0, 16.0],
-        ]);
-        let n = m / 3.0;
-        assert_eq!(n[0][0], 1.0 / 3.0);
-        assert_eq!(n[0][1], 2.0 / 3.0);
-        assert_eq!(n[0][2], 3.0 / 3.0);
-        assert_eq!(n[0][3], 4.0 / 3.0);
-        assert_eq!(n[1][0], 5.0 / 3.0);
-        assert_eq!(n[1][1], 6.0 / 3.0);
-        assert_eq!(n[1][2], 7.0 / 3.0);
-        assert_eq!(n[1][3], 8.0 / 3.0);
-        assert_eq!(n[2][0], 9.0 / 3.0);
-        assert_eq!(n[2][1], 10.0 / 3.0);
-        assert_eq!(n[2][2], 11.0 / 3.0);
-        assert_eq!(n[2][3], 12.0 / 3.0);
-        assert_eq!(n[3][0], 13.0 / 3.0);
-        assert_eq!(n[3][1], 14.0 / 3.0);
-        assert_eq!(n[3][2], 15.0 / 3.0);
-        assert_eq!(n[3][3], 16.0 / 3.0);
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn inverse_affine_projective() {
+        let convention = ProjectionConvention::new(Handedness::Right, DepthRange::NegativeOneToOne);
+        Mat4f64::perspective(1.0, 1.0, 0.1, 10.0, convention).inverse_affine();
+    }
 
-        m /= 3.0;
-        assert_eq!(m[0][0], 1.0 / 3.0);
-        assert_eq!(m[0][1], 2.0 / 3.0);
-        assert_eq!(m[0][2], 3.0 / 3.0);
-        assert_eq!(m[0][3], 4.0 / 3.0);
-        assert_eq!(m[1][0], 5.0 / 3.0);
-        assert_eq!(m[1][1], 6.0 / 3.0);
-        assert_eq!(m[1][2], 7.0 / 3.0);
-        assert_eq!(m[1][3], 8.0 / 3.0);
-        assert_eq!(m[2][0], 9.0 / 3.0);
-        assert_eq!(m[2][1], 10.0 / 3.0);
-        assert_eq!(m[2][2], 11.0 / 3.0);
-        assert_eq!(m[2][3], 12.0 / 3.0);
-        assert_eq!(m[3][0], 13.0 / 3.0);
-        assert_eq!(m[3][1], 14.0 / 3.0);
-        assert_eq!(m[3][2], 15.0 / 3.0);
-        assert_eq!(m[3][3], 16.0 / 3.0);
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn inverse_rigid_scaled() {
+        Mat4f64::from_scale(Vec3f64::new([2.0, 1.0, 1.0])).inverse_rigid();
     }
 
     #[test]
-    fn matrix_multiplication() {
-        let mut m = Mat4f64::new([
-            [1.0, 2.0, 3.0, 4.0],
-            [5.0, 6.0, 7.0, 8.0],
-            [9.0, 10.0, 11.0, 12.0],
-            [13.0, 14.0, 15.0, 16.0],
-        ]);
-        let n = Mat4f64::new([
-            [17.0, 18.0, 19.0, 20.0],
-            [21.0, 22.0, 23.0, 24.0],
-            [25.0, 26.0, 27.0, 28.0],
-            [29.0, 30.0, 31.0, 32.0],
-        ]);
-        let r = m * n;
-        m *= n;
+    fn normal_matrix() {
+        let m = Mat4f64::from_axis_angle(Vec3f64::new([1.0, -1.0, 2.0]).normed(), 0.7)
+            .scale(Vec3f64::new([3.0, 0.5, 1.0]))
+            .translate(Vec3f64::new([1.0, 2.0, 3.0]));
+        // A plane spanned by two tangents stays perpendicular to its transformed normal
+        let tangent_a = Vec3f64::new([1.0, 1.0, 0.0]);
+        let tangent_b = Vec3f64::new([0.0, 1.0, -1.0]);
+        let normal = tangent_a.cross(tangent_b);
+        let a = m.transform_vector3(tangent_a);
+        let b = m.transform_vector3(tangent_b);
+        for normal_matrix in [m.normal_matrix(), m.normal_matrix_cofactor()] {
+            let transformed = (normal_matrix * normal).normed();
+            assert!(transformed.dot(a).abs() < 1e-12);
+            assert!(transformed.dot(b).abs() < 1e-12);
+            assert!((transformed - a.cross(b).normed()).mag() < 1e-12);
+        }
 
-        assert_eq!(r[0][0], 250.0);
-        assert_eq!(r[0][1], 260.0);
-        assert_eq!(r[0][2], 270.0);
-        assert_eq!(r[0][3], 280.0);
-        assert_eq!(r[1][0], 618.0);
-        assert_eq!(r[1][1], 644.0);
-        assert_eq!(r[1][2], 670.0);
-        assert_eq!(r[1][3], 696.0);
-        assert_eq!(r[2][0], 986.0);
-        assert_eq!(r[2][1], 1028.0);
-        assert_eq!(r[2][2], 1070.0);
-        assert_eq!(r[2][3], 1112.0);
-        assert_eq!(r[3][0], 1354.0);
-        assert_eq!(r[3][1], 1412.0);
-        assert_eq!(r[3][2], 1470.0);
-        assert_eq!(r[3][3], 1528.0);
+        // Rotations are their own normal matrix
+        let rotation = Mat4f64::from_rotation_y(1.2);
+        assert!((rotation.normal_matrix() - rotation.upper_left()).frobenius_norm() < 1e-12);
+        assert!(
+            (rotation.normal_matrix_cofactor() - rotation.upper_left()).frobenius_norm() < 1e-12
+        );
+    }
 
-        assert_eq!(m[0][0], 250.0);
-        assert_eq!(m[0][1], 260.0);
-        assert_eq!(m[0][2], 270.0);
-        assert_eq!(m[0][3], 280.0);
-        assert_eq!(m[1][0], 618.0);
-        assert_eq!(m[1][1], 644.0);
-        assert_eq!(m[1][2], 670.0);
-        assert_eq!(m[1][3], 696.0);
-        assert_eq!(m[2][0], 986.0);
-        assert_eq!(m[2][1], 1028.0);
-        assert_eq!(m[2][2], 1070.0);
-        assert_eq!(m[2][3], 1112.0);
-        assert_eq!(m[3][0], 1354.0);
-        assert_eq!(m[3][1], 1412.0);
-        assert_eq!(m[3][2], 1470.0);
-        assert_eq!(m[3][3], 1528.0);
+    #[test]
+    fn normal_matrix_cofactor_singular() {
+        // Flattening onto the xy plane keeps normals pointing along z
+        let m = Mat4f64::from_scale(Vec3f64::new([2.0, 3.0, 0.0]));
+        let normal = m.normal_matrix_cofactor() * Vec3f64::new([0.0, 0.0, 1.0]);
+        assert!((normal.normed() - Vec3f64::new([0.0, 0.0, 1.0])).mag() < 1e-12);
+        assert!(!m.normal_matrix()[2][2].is_finite());
     }
 
     #[test]
-    fn vector_multiplication() {
-        let m = Mat4f64::new([
-            [1.0, 2.0, 3.0, 4.0],
-            [5.0, 6.0, 7.0, 8.0],
-            [9.0, 10.0, 11.0, 12.0],
-            [13.0, 14.0, 15.0, 16.0],
-        ]);
-        let v = Vec4f64::new([2.0, 3.0, 4.0, 5.0]);
-        let w = m * v;
+    fn from_reflection() {
+        // The plane y = 2, with and without a normalized normal
+        for plane in [
+            Vec4f64::new([0.0, 1.0, 0.0, -2.0]),
+            Vec4f64::new([0.0, 2.0, 0.0, -4.0]),
+        ] {
+            let m = Mat4f64::from_reflection(plane);
+            let p = m.transform_point3(Vec3f64::new([1.0, 5.0, 3.0]));
+            assert!((p - Vec3f64::new([1.0, -1.0, 3.0])).mag() < 1e-12);
+        }
 
-        assert_eq!(w[0], 40.0);
-        assert_eq!(w[1], 96.0);
-        assert_eq!(w[2], 152.0);
-        assert_eq!(w[3], 208.0);
+        let plane = Vec4f64::new([1.0, -2.0, 2.0, 3.0]);
+        let m = Mat4f64::from_reflection(plane);
+        assert!((m.determinant() + 1.0).abs() < 1e-12);
+        assert_mat_close(m * m, Mat4f64::identity(), 1e-12);
+        let p = Vec3f64::new([0.5, 4.0, -1.0]);
+        let q = m.transform_point3(p);
+        let midpoint = 0.5 * (p + q);
+        assert!(
+            Vec4f64::new([midpoint[0], midpoint[1], midpoint[2], 1.0])
+                .dot(plane)
+                .abs()
+                < 1e-12
+        );
+        assert!((p - q).cross(Vec3f64::new([1.0, -2.0, 2.0])).mag() < 1e-12);
+    }
+
+    #[test]
+    fn from_plane_projection() {
+        let m = Mat4f64::from_plane_projection(Vec4f64::new([0.0, 1.0, 0.0, -2.0]));
+        let p = m.transform_point3(Vec3f64::new([1.0, 5.0, 3.0]));
+        assert!((p - Vec3f64::new([1.0, 2.0, 3.0])).mag() < 1e-12);
+
+        let plane = Vec4f64::new([1.0, -2.0, 2.0, 3.0]);
+        let m = Mat4f64::from_plane_projection(plane);
+        assert_mat_close(m * m, m, 1e-12);
+        let p = m.transform_point3(Vec3f64::new([0.5, 4.0, -1.0]));
+        assert!(Vec4f64::new([p[0], p[1], p[2], 1.0]).dot(plane).abs() < 1e-12);
+    }
+
+    #[test]
+    fn from_shadow() {
+        let ground = Vec4f64::new([0.0, 1.0, 0.0, 0.0]);
+        let m = Mat4f64::from_point_shadow(ground, Vec3f64::new([0.0, 10.0, 0.0]));
+        let p = m.project_point3(Vec3f64::new([1.0, 5.0, 0.0]));
+        assert!((p - Vec3f64::new([2.0, 0.0, 0.0])).mag() < 1e-12);
+
+        let m = Mat4f64::from_directional_shadow(ground, Vec3f64::new([1.0, -1.0, 0.0]));
+        let p = m.project_point3(Vec3f64::new([0.0, 2.0, 3.0]));
+        assert!((p - Vec3f64::new([2.0, 0.0, 3.0])).mag() < 1e-12);
+
+        // Points on the plane cast their shadow onto themselves
+        let plane = Vec4f64::new([1.0, 1.0, 1.0, -3.0]);
+        let m = Mat4f64::from_point_shadow(plane, Vec3f64::new([5.0, 6.0, 7.0]));
+        let p = m.project_point3(Vec3f64::new([1.0, 1.0, 1.0]));
+        assert!((p - Vec3f64::new([1.0, 1.0, 1.0])).mag() < 1e-12);
+    }
+
+    #[test]
+    fn with_oblique_near_plane() {
+        let conventions = [
+            (Handedness::Right, DepthRange::NegativeOneToOne),
+            (Handedness::Right, DepthRange::ZeroToOne),
+            (Handedness::Left, DepthRange::NegativeOneToOne),
+            (Handedness::Left, DepthRange::ZeroToOne),
+        ];
+        for (handedness, depth_range) in conventions {
+            let forward = match handedness {
+                Handedness::Right => -1.0,
+                Handedness::Left => 1.0,
+            };
+            let base = ProjectionConvention::new(handedness, depth_range);
+            for convention in [base, base.with_reversed_z()] {
+                let (ndc_near, ndc_far) = match (depth_range, convention.reversed_z) {
+                    (DepthRange::NegativeOneToOne, false) => (-1.0, 1.0),
+                    (DepthRange::NegativeOneToOne, true) => (1.0, -1.0),
+                    (DepthRange::ZeroToOne, false) => (0.0, 1.0),
+                    (DepthRange::ZeroToOne, true) => (1.0, 0.0),
+                };
+                for far in [100.0, f64::INFINITY] {
+                    let m = Mat4f64::perspective(1.2, 1.5, 0.5, far, convention);
+                    // A tilted plane two units in front of the camera
+                    let plane = Vec4f64::new([0.3, -0.2, forward, -2.0]);
+                    let oblique = m.with_oblique_near_plane(plane, convention);
+
+                    for (x, y) in [(0.0, 0.0), (1.0, -0.5), (-0.8, 0.7)] {
+                        let z = forward * (2.0 - 0.3 * x + 0.2 * y);
+                        let on_plane = Vec3f64::new([x, y, z]);
+                        let projected = oblique.project_point3(on_plane);
+                        assert!((projected[2] - ndc_near).abs() < 1e-12);
+                        let original = m.project_point3(on_plane);
+                        assert!((projected[0] - original[0]).abs() < 1e-12);
+                        assert!((projected[1] - original[1]).abs() < 1e-12);
+
+                        let beyond = oblique.project_point3(Vec3f64::new([x, y, z + forward]));
+                        assert!((beyond[2] - ndc_near) * (ndc_far - ndc_near) > 0.0);
+                        assert!((beyond[2] - ndc_far) * (ndc_near - ndc_far) > 0.0);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn transform_plane() {
+        let ground = Vec4f64::new([0.0, 1.0, 0.0, 0.0]);
+        let moved =
+            Mat4f64::from_translation(Vec3f64::new([1.0, 3.0, 2.0])).transform_plane(ground);
+        assert!((moved - Vec4f64::new([0.0, 1.0, 0.0, -3.0])).mag() < 1e-12);
+
+        let m = Mat4f64::from_axis_angle(Vec3f64::new([1.0, 1.0, 0.0]).normed(), 0.9)
+            .scale(Vec3f64::new([2.0, 0.5, 1.5]))
+            .translate(Vec3f64::new([-1.0, 2.0, 0.5]));
+        let plane = Vec4f64::new([1.0, -2.0, 2.0, 3.0]);
+        let transformed = m.transform_plane(plane);
+        for p in [[-3.0, 0.0, 0.0], [1.0, 2.0, 0.0], [0.0, 0.5, -1.0]] {
+            let p = m.transform_point3(Vec3f64::new(p));
+            assert!(Vec4f64::new([p[0], p[1], p[2], 1.0]).dot(transformed).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn perspective_off_axis() {
+        let convention = ProjectionConvention::new(Handedness::Right, DepthRange::ZeroToOne);
+        // Symmetric tangents match perspective()
+        let fov_y: f64 = 1.1;
+        let tan_up = (fov_y / 2.0).tan();
+        let symmetric = Mat4f64::perspective_off_axis(
+            -1.5 * tan_up,
+            1.5 * tan_up,
+            -tan_up,
+            tan_up,
+            0.1,
+            50.0,
+            convention,
+        );
+        let expected = Mat4f64::perspective(fov_y, 1.5, 0.1, 50.0, convention);
+        assert_mat_close(symmetric, expected, 1e-12);
+
+        // A frustum reaching further to the left, as for the right eye of a headset
+        let m = Mat4f64::perspective_off_axis(-1.2, 0.8, -1.0, 0.9, 0.1, 50.0, convention);
+        assert_close(project(m, -1.2 * 0.1, 0.9 * 0.1, -0.1), [-1.0, 1.0, 0.0]);
+        assert_close(project(m, 0.8 * 50.0, -50.0, -50.0), [1.0, -1.0, 1.0]);
+        // The viewing direction is off center
+        let center = project(m, 0.0, 0.0, -5.0);
+        assert!((center[0] - 0.2).abs() < 1e-12);
+        assert!((center[1] - 0.1 / 1.9).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tile() {
+        let convention = ProjectionConvention::new(Handedness::Right, DepthRange::NegativeOneToOne);
+        let m = Mat4f64::perspective(1.0, 1.5, 0.5, 20.0, convention);
+        let (columns, rows) = (3, 2);
+        for column in 0..columns {
+            for row in 0..rows {
+                let tile = m.tile(columns, rows, column, row);
+                // The corners of the tile in the full image become the corners of the tile image
+                let left = -1.0 + 2.0 * column as f64 / columns as f64;
+                let bottom = -1.0 + 2.0 * row as f64 / rows as f64;
+                let right = left + 2.0 / columns as f64;
+                let top = bottom + 2.0 / rows as f64;
+                let inverse = m.inverse();
+                for (x, y, expected_x, expected_y) in [
+                    (left, bottom, -1.0, -1.0),
+                    (right, top, 1.0, 1.0),
+                    (left, top, -1.0, 1.0),
+                ] {
+                    let p = inverse.project_point3(Vec3f64::new([x, y, 0.3]));
+                    let projected = tile.project_point3(p);
+                    assert_close(projected.coords, [expected_x, expected_y, 0.3]);
+                }
+            }
+        }
+        assert_mat_close(m.tile(1, 1, 0, 0), m, 1e-12);
     }
 }
@@ -0,0 +1,1195 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
+
+use crate::mat::mat3f32::Mat3f32;
+use crate::mat::mat3x2f32::Mat3x2f32;
+use crate::mat::mat3x4f32::Mat3x4f32;
+use crate::mat::mat4f32::Mat4f32;
+use crate::mat::mat4x2f32::Mat4x2f32;
+use crate::mat::mat4x3f64::Mat4x3f64;
+use crate::vec::vec3f32::Vec3f32;
+use crate::vec::vec4f32::Vec4f32;
+
+/// A 4x3 floating point matrix (4 rows and 3 columns).
+/// Indexing follows row major order, like in most mathematical texts.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bitcode", derive(bitcode::Encode, bitcode::Decode))]
+pub struct Mat4x3f32 {
+    pub rows: [[f32; 3]; 4],
+}
+
+impl Mat4x3f32 {
+    /// Creates a new matrix with user defined elements.
+    /// The user defined elements are in row major order.
+    pub const fn new(rows: [[f32; 3]; 4]) -> Self {
+        Self { rows }
+    }
+
+    /// Creates a new matrix with user defined elements.
+    /// The user defined elements are in column major order.
+    pub const fn new_from_cols(cols: [[f32; 4]; 3]) -> Self {
+        Self::new([
+            [cols[0][0], cols[1][0], cols[2][0]],
+            [cols[0][1], cols[1][1], cols[2][1]],
+            [cols[0][2], cols[1][2], cols[2][2]],
+            [cols[0][3], cols[1][3], cols[2][3]],
+        ])
+    }
+
+    /// Creates a new matrix with all elements equal to 0.0.
+    pub const fn zero() -> Self {
+        Self {
+            rows: [
+                [0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0],
+            ],
+        }
+    }
+
+    /// Creates a new matrix from row vectors.
+    pub const fn from_rows(rows: [Vec3f32; 4]) -> Self {
+        Self::new([
+            rows[0].coords,
+            rows[1].coords,
+            rows[2].coords,
+            rows[3].coords,
+        ])
+    }
+
+    /// Creates a new matrix from column vectors.
+    pub const fn from_cols(cols: [Vec4f32; 3]) -> Self {
+        Self::new_from_cols([cols[0].coords, cols[1].coords, cols[2].coords])
+    }
+
+    /// Returns self but transposed
+    /// (Rows are now columns and columns are now rows).
+    pub const fn transposed(&self) -> Mat3x4f32 {
+        let m = &self.rows;
+        Mat3x4f32::new([
+            [m[0][0], m[1][0], m[2][0], m[3][0]],
+            [m[0][1], m[1][1], m[2][1], m[3][1]],
+            [m[0][2], m[1][2], m[2][2], m[3][2]],
+        ])
+    }
+
+    /// Returns the matrix rows as arrays in row major order.
+    pub const fn as_row_major(&self) -> [[f32; 3]; 4] {
+        self.rows
+    }
+
+    /// Returns the matrix columns as arrays in column major order.
+    pub const fn as_col_major(&self) -> [[f32; 4]; 3] {
+        self.transposed().rows
+    }
+
+    /// Returns the row at index as a vector.
+    pub const fn row(&self, index: usize) -> Vec3f32 {
+        Vec3f32::new(self.rows[index])
+    }
+
+    /// Returns the column at index as a vector.
+    pub const fn col(&self, index: usize) -> Vec4f32 {
+        let m = &self.rows;
+        Vec4f32::new([m[0][index], m[1][index], m[2][index], m[3][index]])
+    }
+
+    /// Replaces the row at index with row.
+    pub const fn set_row(&mut self, index: usize, row: Vec3f32) {
+        self.rows[index] = row.coords;
+    }
+
+    /// Replaces the column at index with col.
+    pub const fn set_col(&mut self, index: usize, col: Vec4f32) {
+        self.rows[0][index] = col.coords[0];
+        self.rows[1][index] = col.coords[1];
+        self.rows[2][index] = col.coords[2];
+        self.rows[3][index] = col.coords[3];
+    }
+
+    /// Creates a new matrix from a flat array of elements in row major order.
+    pub const fn from_row_major_flat(elements: [f32; 12]) -> Self {
+        let e = &elements;
+        Self::new([
+            [e[0], e[1], e[2]],
+            [e[3], e[4], e[5]],
+            [e[6], e[7], e[8]],
+            [e[9], e[10], e[11]],
+        ])
+    }
+
+    /// Creates a new matrix from a flat array of elements in column major order.
+    pub const fn from_col_major_flat(elements: [f32; 12]) -> Self {
+        let e = &elements;
+        Self::new([
+            [e[0], e[4], e[8]],
+            [e[1], e[5], e[9]],
+            [e[2], e[6], e[10]],
+            [e[3], e[7], e[11]],
+        ])
+    }
+
+    /// Returns all elements as a flat array in row major order.
+    pub const fn as_row_major_flat(&self) -> [f32; 12] {
+        let m = &self.rows;
+        [
+            m[0][0], m[0][1], m[0][2], m[1][0], m[1][1], m[1][2], m[2][0], m[2][1], m[2][2],
+            m[3][0], m[3][1], m[3][2],
+        ]
+    }
+
+    /// Returns all elements as a flat array in column major order.
+    /// This is the layout most graphics APIs expect for uniform uploads.
+    pub const fn as_col_major_flat(&self) -> [f32; 12] {
+        let m = &self.rows;
+        [
+            m[0][0], m[1][0], m[2][0], m[3][0], m[0][1], m[1][1], m[2][1], m[3][1], m[0][2],
+            m[1][2], m[2][2], m[3][2],
+        ]
+    }
+
+    /// Returns self converted to double precision.
+    pub const fn to_f64(&self) -> Mat4x3f64 {
+        let m = &self.rows;
+        Mat4x3f64::new([
+            [m[0][0] as f64, m[0][1] as f64, m[0][2] as f64],
+            [m[1][0] as f64, m[1][1] as f64, m[1][2] as f64],
+            [m[2][0] as f64, m[2][1] as f64, m[2][2] as f64],
+            [m[3][0] as f64, m[3][1] as f64, m[3][2] as f64],
+        ])
+    }
+
+    /// Reduces self to reduced row echelon form with Gaussian elimination and partial pivoting.
+    /// Returns the reduced rows and which columns contain a pivot. Pivots with an absolute
+    /// value less than or equal to tolerance are treated as zero.
+    fn row_echelon(&self, tolerance: f32) -> ([[f32; 3]; 4], [bool; 3]) {
+        let mut a = self.rows;
+        let mut pivots = [false; 3];
+        let mut rank = 0;
+        for col in 0..3 {
+            if rank == 4 {
+                break;
+            }
+            let mut pivot = rank;
+            for (row, values) in a.iter().enumerate().skip(rank + 1) {
+                if values[col].abs() > a[pivot][col].abs() {
+                    pivot = row;
+                }
+            }
+            if a[pivot][col].abs() <= tolerance {
+                continue;
+            }
+            a.swap(rank, pivot);
+            let pivot_value = a[rank][col];
+            for x in a[rank].iter_mut() {
+                *x /= pivot_value;
+            }
+            let pivot_row = a[rank];
+            for (row, values) in a.iter_mut().enumerate() {
+                if row != rank {
+                    let factor = values[col];
+                    for (x, p) in values.iter_mut().zip(pivot_row) {
+                        *x -= factor * p;
+                    }
+                }
+            }
+            pivots[col] = true;
+            rank += 1;
+        }
+        (a, pivots)
+    }
+
+    /// Returns the rank of self, computed with Gaussian elimination and partial pivoting.
+    /// Pivots with an absolute value less than or equal to tolerance are treated as zero.
+    pub fn rank(&self, tolerance: f32) -> usize {
+        let (_, pivots) = self.row_echelon(tolerance);
+        pivots.iter().filter(|&&pivot| pivot).count()
+    }
+
+    /// Returns an orthonormal basis of the null space of self, the vectors v with self * v = 0.
+    /// The basis is empty if self has full column rank.
+    /// Pivots with an absolute value less than or equal to tolerance are treated as zero (see rank()).
+    pub fn null_space(&self, tolerance: f32) -> Vec<Vec3f32> {
+        let (a, pivots) = self.row_echelon(tolerance);
+        let mut basis = Vec::new();
+        for free in 0..3 {
+            if pivots[free] {
+                continue;
+            }
+            let mut v = Vec3f32::zero();
+            v[free] = 1.0;
+            // The reduced rows have their pivots in the pivot columns in order
+            let mut row = 0;
+            for col in 0..3 {
+                if pivots[col] {
+                    v[col] = -a[row][free];
+                    row += 1;
+                }
+            }
+            basis.push(v);
+        }
+        Vec3f32::gram_schmidt(&mut basis);
+        basis
+    }
+
+    /// Returns the condition number of self in the Frobenius norm,
+    /// ||self|| * ||self^+||, where self^+ is the pseudo inverse. It is at least 3
+    /// and grows as self gets closer to losing rank.
+    /// Returns infinity if self does not have full rank.
+    pub fn condition_number(&self) -> f32 {
+        // ||self^+||^2 is the trace of the inverse of self^T * self
+        let gram = self.transposed() * *self;
+        match gram.try_inverse(0.0) {
+            Some(inverse) => gram.trace().sqrt() * inverse.trace().sqrt(),
+            None => f32::INFINITY,
+        }
+    }
+
+    /// Returns true if the columns of self are orthonormal, meaning self^T * self
+    /// differs from the identity by at most tolerance in every element.
+    pub fn is_orthogonal(&self, tolerance: f32) -> bool {
+        let gram = self.transposed() * *self;
+        let identity = Mat3f32::identity();
+        for i in 0..3 {
+            for j in 0..3 {
+                if (gram[i][j] - identity[i][j]).abs() > tolerance {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Returns the sum of self and rhs.
+    /// Same as the + operator, but usable in const contexts.
+    pub const fn sum(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [a[0][0] + b[0][0], a[0][1] + b[0][1], a[0][2] + b[0][2]],
+            [a[1][0] + b[1][0], a[1][1] + b[1][1], a[1][2] + b[1][2]],
+            [a[2][0] + b[2][0], a[2][1] + b[2][1], a[2][2] + b[2][2]],
+            [a[3][0] + b[3][0], a[3][1] + b[3][1], a[3][2] + b[3][2]],
+        ])
+    }
+
+    /// Returns the difference of self and rhs.
+    /// Same as the - operator, but usable in const contexts.
+    pub const fn difference(&self, rhs: Self) -> Self {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Self::new([
+            [a[0][0] - b[0][0], a[0][1] - b[0][1], a[0][2] - b[0][2]],
+            [a[1][0] - b[1][0], a[1][1] - b[1][1], a[1][2] - b[1][2]],
+            [a[2][0] - b[2][0], a[2][1] - b[2][1], a[2][2] - b[2][2]],
+            [a[3][0] - b[3][0], a[3][1] - b[3][1], a[3][2] - b[3][2]],
+        ])
+    }
+
+    /// Returns self with every element multiplied by scalar.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn scaled(&self, scalar: f32) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [a[0][0] * scalar, a[0][1] * scalar, a[0][2] * scalar],
+            [a[1][0] * scalar, a[1][1] * scalar, a[1][2] * scalar],
+            [a[2][0] * scalar, a[2][1] * scalar, a[2][2] * scalar],
+            [a[3][0] * scalar, a[3][1] * scalar, a[3][2] * scalar],
+        ])
+    }
+
+    /// Returns self with every element divided by scalar.
+    /// Same as the / operator, but usable in const contexts.
+    pub const fn divided(&self, scalar: f32) -> Self {
+        let a = &self.rows;
+        Self::new([
+            [a[0][0] / scalar, a[0][1] / scalar, a[0][2] / scalar],
+            [a[1][0] / scalar, a[1][1] / scalar, a[1][2] / scalar],
+            [a[2][0] / scalar, a[2][1] / scalar, a[2][2] / scalar],
+            [a[3][0] / scalar, a[3][1] / scalar, a[3][2] / scalar],
+        ])
+    }
+
+    /// Returns the product of self and the column vector v.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product_vec(&self, v: Vec3f32) -> Vec4f32 {
+        let a = &self.rows;
+        let b = &v.coords;
+        Vec4f32::new([
+            a[0][0] * b[0] + a[0][1] * b[1] + a[0][2] * b[2],
+            a[1][0] * b[0] + a[1][1] * b[1] + a[1][2] * b[2],
+            a[2][0] * b[0] + a[2][1] * b[1] + a[2][2] * b[2],
+            a[3][0] * b[0] + a[3][1] * b[1] + a[3][2] * b[2],
+        ])
+    }
+}
+
+impl Index<usize> for Mat4x3f32 {
+    type Output = [f32; 3];
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.rows[index]
+    }
+}
+
+impl IndexMut<usize> for Mat4x3f32 {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.rows[index]
+    }
+}
+
+impl Add<Mat4x3f32> for Mat4x3f32 {
+    type Output = Mat4x3f32;
+    fn add(self, rhs: Mat4x3f32) -> Self::Output {
+        self.sum(rhs)
+    }
+}
+
+impl AddAssign<Mat4x3f32> for Mat4x3f32 {
+    fn add_assign(&mut self, rhs: Mat4x3f32) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Mat4x3f32> for Mat4x3f32 {
+    type Output = Mat4x3f32;
+    fn sub(self, rhs: Mat4x3f32) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl SubAssign<Mat4x3f32> for Mat4x3f32 {
+    fn sub_assign(&mut self, rhs: Mat4x3f32) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f32> for Mat4x3f32 {
+    type Output = Mat4x3f32;
+    fn mul(self, scalar: f32) -> Self::Output {
+        self.scaled(scalar)
+    }
+}
+
+impl Mul<Mat4x3f32> for f32 {
+    type Output = Mat4x3f32;
+    fn mul(self, m: Mat4x3f32) -> Self::Output {
+        m * self
+    }
+}
+
+impl MulAssign<f32> for Mat4x3f32 {
+    fn mul_assign(&mut self, scalar: f32) {
+        *self = *self * scalar;
+    }
+}
+
+impl Div<f32> for Mat4x3f32 {
+    type Output = Mat4x3f32;
+    fn div(self, scalar: f32) -> Self::Output {
+        self.divided(scalar)
+    }
+}
+
+impl DivAssign<f32> for Mat4x3f32 {
+    fn div_assign(&mut self, scalar: f32) {
+        *self = *self / scalar;
+    }
+}
+
+impl Mul<Mat3x2f32> for Mat4x3f32 {
+    type Output = Mat4x2f32;
+    fn mul(self, rhs: Mat3x2f32) -> Self::Output {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Mat4x2f32::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1],
+            ],
+            [
+                a[2][0] * b[0][0] + a[2][1] * b[1][0] + a[2][2] * b[2][0],
+                a[2][0] * b[0][1] + a[2][1] * b[1][1] + a[2][2] * b[2][1],
+            ],
+            [
+                a[3][0] * b[0][0] + a[3][1] * b[1][0] + a[3][2] * b[2][0],
+                a[3][0] * b[0][1] + a[3][1] * b[1][1] + a[3][2] * b[2][1],
+            ],
+        ])
+    }
+}
+
+impl Mul<Mat3f32> for Mat4x3f32 {
+    type Output = Mat4x3f32;
+    fn mul(self, rhs: Mat3f32) -> Self::Output {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Mat4x3f32::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1],
+                a[0][0] * b[0][2] + a[0][1] * b[1][2] + a[0][2] * b[2][2],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1],
+                a[1][0] * b[0][2] + a[1][1] * b[1][2] + a[1][2] * b[2][2],
+            ],
+            [
+                a[2][0] * b[0][0] + a[2][1] * b[1][0] + a[2][2] * b[2][0],
+                a[2][0] * b[0][1] + a[2][1] * b[1][1] + a[2][2] * b[2][1],
+                a[2][0] * b[0][2] + a[2][1] * b[1][2] + a[2][2] * b[2][2],
+            ],
+            [
+                a[3][0] * b[0][0] + a[3][1] * b[1][0] + a[3][2] * b[2][0],
+                a[3][0] * b[0][1] + a[3][1] * b[1][1] + a[3][2] * b[2][1],
+                a[3][0] * b[0][2] + a[3][1] * b[1][2] + a[3][2] * b[2][2],
+            ],
+        ])
+    }
+}
+
+impl MulAssign<Mat3f32> for Mat4x3f32 {
+    fn mul_assign(&mut self, rhs: Mat3f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Mat3x4f32> for Mat4x3f32 {
+    type Output = Mat4f32;
+    fn mul(self, rhs: Mat3x4f32) -> Self::Output {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Mat4f32::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1],
+                a[0][0] * b[0][2] + a[0][1] * b[1][2] + a[0][2] * b[2][2],
+                a[0][0] * b[0][3] + a[0][1] * b[1][3] + a[0][2] * b[2][3],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1],
+                a[1][0] * b[0][2] + a[1][1] * b[1][2] + a[1][2] * b[2][2],
+                a[1][0] * b[0][3] + a[1][1] * b[1][3] + a[1][2] * b[2][3],
+            ],
+            [
+                a[2][0] * b[0][0] + a[2][1] * b[1][0] + a[2][2] * b[2][0],
+                a[2][0] * b[0][1] + a[2][1] * b[1][1] + a[2][2] * b[2][1],
+                a[2][0] * b[0][2] + a[2][1] * b[1][2] + a[2][2] * b[2][2],
+                a[2][0] * b[0][3] + a[2][1] * b[1][3] + a[2][2] * b[2][3],
+            ],
+            [
+                a[3][0] * b[0][0] + a[3][1] * b[1][0] + a[3][2] * b[2][0],
+                a[3][0] * b[0][1] + a[3][1] * b[1][1] + a[3][2] * b[2][1],
+                a[3][0] * b[0][2] + a[3][1] * b[1][2] + a[3][2] * b[2][2],
+                a[3][0] * b[0][3] + a[3][1] * b[1][3] + a[3][2] * b[2][3],
+            ],
+        ])
+    }
+}
+
+impl Mul<Mat4x3f32> for Mat4f32 {
+    type Output = Mat4x3f32;
+    fn mul(self, rhs: Mat4x3f32) -> Self::Output {
+        let a = &self.rows;
+        let b = &rhs.rows;
+        Mat4x3f32::new([
+            [
+                a[0][0] * b[0][0] + a[0][1] * b[1][0] + a[0][2] * b[2][0] + a[0][3] * b[3][0],
+                a[0][0] * b[0][1] + a[0][1] * b[1][1] + a[0][2] * b[2][1] + a[0][3] * b[3][1],
+                a[0][0] * b[0][2] + a[0][1] * b[1][2] + a[0][2] * b[2][2] + a[0][3] * b[3][2],
+            ],
+            [
+                a[1][0] * b[0][0] + a[1][1] * b[1][0] + a[1][2] * b[2][0] + a[1][3] * b[3][0],
+                a[1][0] * b[0][1] + a[1][1] * b[1][1] + a[1][2] * b[2][1] + a[1][3] * b[3][1],
+                a[1][0] * b[0][2] + a[1][1] * b[1][2] + a[1][2] * b[2][2] + a[1][3] * b[3][2],
+            ],
+            [
+                a[2][0] * b[0][0] + a[2][1] * b[1][0] + a[2][2] * b[2][0] + a[2][3] * b[3][0],
+                a[2][0] * b[0][1] + a[2][1] * b[1][1] + a[2][2] * b[2][1] + a[2][3] * b[3][1],
+                a[2][0] * b[0][2] + a[2][1] * b[1][2] + a[2][2] * b[2][2] + a[2][3] * b[3][2],
+            ],
+            [
+                a[3][0] * b[0][0] + a[3][1] * b[1][0] + a[3][2] * b[2][0] + a[3][3] * b[3][0],
+                a[3][0] * b[0][1] + a[3][1] * b[1][1] + a[3][2] * b[2][1] + a[3][3] * b[3][1],
+                a[3][0] * b[0][2] + a[3][1] * b[1][2] + a[3][2] * b[2][2] + a[3][3] * b[3][2],
+            ],
+        ])
+    }
+}
+
+impl Mul<Vec3f32> for Mat4x3f32 {
+    type Output = Vec4f32;
+    fn mul(self, v: Vec3f32) -> Self::Output {
+        self.product_vec(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mat::mat3f32::Mat3f32;
+    use crate::mat::mat3x2f32::Mat3x2f32;
+    use crate::mat::mat3x4f32::Mat3x4f32;
+    use crate::mat::mat4f32::Mat4f32;
+    use crate::vec::vec3f32::Vec3f32;
+    use crate::vec::vec4f32::Vec4f32;
+
+    use super::Mat4x3f32;
+
+    #[test]
+    fn matrix_creation() {
+        let zero = Mat4x3f32::zero();
+        let m = Mat4x3f32::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+            [10.0, 11.0, 12.0],
+        ]);
+        let c = Mat4x3f32::new_from_cols([
+            [1.0, 4.0, 7.0, 10.0],
+            [2.0, 5.0, 8.0, 11.0],
+            [3.0, 6.0, 9.0, 12.0],
+        ]);
+        let r = Mat4x3f32::from_rows([
+            Vec3f32::new([1.0, 2.0, 3.0]),
+            Vec3f32::new([4.0, 5.0, 6.0]),
+            Vec3f32::new([7.0, 8.0, 9.0]),
+            Vec3f32::new([10.0, 11.0, 12.0]),
+        ]);
+
+        assert_eq!(zero[0][0], 0.0);
+        assert_eq!(zero[0][1], 0.0);
+        assert_eq!(zero[0][2], 0.0);
+        assert_eq!(zero[1][0], 0.0);
+        assert_eq!(zero[1][1], 0.0);
+        assert_eq!(zero[1][2], 0.0);
+        assert_eq!(zero[2][0], 0.0);
+        assert_eq!(zero[2][1], 0.0);
+        assert_eq!(zero[2][2], 0.0);
+        assert_eq!(zero[3][0], 0.0);
+        assert_eq!(zero[3][1], 0.0);
+        assert_eq!(zero[3][2], 0.0);
+
+        assert_eq!(m[0][0], 1.0);
+        assert_eq!(m[0][1], 2.0);
+        assert_eq!(m[0][2], 3.0);
+        assert_eq!(m[1][0], 4.0);
+        assert_eq!(m[1][1], 5.0);
+        assert_eq!(m[1][2], 6.0);
+        assert_eq!(m[2][0], 7.0);
+        assert_eq!(m[2][1], 8.0);
+        assert_eq!(m[2][2], 9.0);
+        assert_eq!(m[3][0], 10.0);
+        assert_eq!(m[3][1], 11.0);
+        assert_eq!(m[3][2], 12.0);
+
+        assert_eq!(c[0][0], 1.0);
+        assert_eq!(c[0][1], 2.0);
+        assert_eq!(c[0][2], 3.0);
+        assert_eq!(c[1][0], 4.0);
+        assert_eq!(c[1][1], 5.0);
+        assert_eq!(c[1][2], 6.0);
+        assert_eq!(c[2][0], 7.0);
+        assert_eq!(c[2][1], 8.0);
+        assert_eq!(c[2][2], 9.0);
+        assert_eq!(c[3][0], 10.0);
+        assert_eq!(c[3][1], 11.0);
+        assert_eq!(c[3][2], 12.0);
+
+        assert_eq!(r[0][0], 1.0);
+        assert_eq!(r[0][1], 2.0);
+        assert_eq!(r[0][2], 3.0);
+        assert_eq!(r[1][0], 4.0);
+        assert_eq!(r[1][1], 5.0);
+        assert_eq!(r[1][2], 6.0);
+        assert_eq!(r[2][0], 7.0);
+        assert_eq!(r[2][1], 8.0);
+        assert_eq!(r[2][2], 9.0);
+        assert_eq!(r[3][0], 10.0);
+        assert_eq!(r[3][1], 11.0);
+        assert_eq!(r[3][2], 12.0);
+    }
+
+    #[test]
+    fn transpose() {
+        let m = Mat4x3f32::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+            [10.0, 11.0, 12.0],
+        ]);
+        let t = m.transposed();
+        let cols = m.as_col_major();
+        let back = t.transposed();
+
+        assert_eq!(t[0][0], 1.0);
+        assert_eq!(t[0][1], 4.0);
+        assert_eq!(t[0][2], 7.0);
+        assert_eq!(t[0][3], 10.0);
+        assert_eq!(t[1][0], 2.0);
+        assert_eq!(t[1][1], 5.0);
+        assert_eq!(t[1][2], 8.0);
+        assert_eq!(t[1][3], 11.0);
+        assert_eq!(t[2][0], 3.0);
+        assert_eq!(t[2][1], 6.0);
+        assert_eq!(t[2][2], 9.0);
+        assert_eq!(t[2][3], 12.0);
+
+        assert_eq!(cols[0][0], 1.0);
+        assert_eq!(cols[0][1], 4.0);
+        assert_eq!(cols[0][2], 7.0);
+        assert_eq!(cols[0][3], 10.0);
+        assert_eq!(cols[1][0], 2.0);
+        assert_eq!(cols[1][1], 5.0);
+        assert_eq!(cols[1][2], 8.0);
+        assert_eq!(cols[1][3], 11.0);
+        assert_eq!(cols[2][0], 3.0);
+        assert_eq!(cols[2][1], 6.0);
+        assert_eq!(cols[2][2], 9.0);
+        assert_eq!(cols[2][3], 12.0);
+
+        assert_eq!(back[0][0], 1.0);
+        assert_eq!(back[0][1], 2.0);
+        assert_eq!(back[0][2], 3.0);
+        assert_eq!(back[1][0], 4.0);
+        assert_eq!(back[1][1], 5.0);
+        assert_eq!(back[1][2], 6.0);
+        assert_eq!(back[2][0], 7.0);
+        assert_eq!(back[2][1], 8.0);
+        assert_eq!(back[2][2], 9.0);
+        assert_eq!(back[3][0], 10.0);
+        assert_eq!(back[3][1], 11.0);
+        assert_eq!(back[3][2], 12.0);
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let m = Mat4x3f32::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+            [10.0, 11.0, 12.0],
+        ]);
+        let mut n = Mat4x3f32::zero();
+        n.set_row(0, m.row(3));
+        n.set_col(2, m.col(0));
+
+        assert_eq!(m.row(0)[0], 1.0);
+        assert_eq!(m.row(0)[1], 2.0);
+        assert_eq!(m.row(0)[2], 3.0);
+        assert_eq!(m.row(1)[0], 4.0);
+        assert_eq!(m.row(1)[1], 5.0);
+        assert_eq!(m.row(1)[2], 6.0);
+        assert_eq!(m.row(2)[0], 7.0);
+        assert_eq!(m.row(2)[1], 8.0);
+        assert_eq!(m.row(2)[2], 9.0);
+        assert_eq!(m.row(3)[0], 10.0);
+        assert_eq!(m.row(3)[1], 11.0);
+        assert_eq!(m.row(3)[2], 12.0);
+
+        assert_eq!(m.col(0)[0], 1.0);
+        assert_eq!(m.col(0)[1], 4.0);
+        assert_eq!(m.col(0)[2], 7.0);
+        assert_eq!(m.col(0)[3], 10.0);
+        assert_eq!(m.col(1)[0], 2.0);
+        assert_eq!(m.col(1)[1], 5.0);
+        assert_eq!(m.col(1)[2], 8.0);
+        assert_eq!(m.col(1)[3], 11.0);
+        assert_eq!(m.col(2)[0], 3.0);
+        assert_eq!(m.col(2)[1], 6.0);
+        assert_eq!(m.col(2)[2], 9.0);
+        assert_eq!(m.col(2)[3], 12.0);
+
+        assert_eq!(n[0][0], 10.0);
+        assert_eq!(n[0][1], 11.0);
+        assert_eq!(n[0][2], 1.0);
+        assert_eq!(n[1][0], 0.0);
+        assert_eq!(n[1][1], 0.0);
+        assert_eq!(n[1][2], 4.0);
+        assert_eq!(n[2][0], 0.0);
+        assert_eq!(n[2][1], 0.0);
+        assert_eq!(n[2][2], 7.0);
+        assert_eq!(n[3][0], 0.0);
+        assert_eq!(n[3][1], 0.0);
+        assert_eq!(n[3][2], 10.0);
+    }
+
+    #[test]
+    fn matrix_addition_and_subtraction() {
+        let m = Mat4x3f32::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+            [10.0, 11.0, 12.0],
+        ]);
+        let mut n = Mat4x3f32::new([
+            [11.0, 12.0, 13.0],
+            [14.0, 15.0, 16.0],
+            [17.0, 18.0, 19.0],
+            [20.0, 21.0, 22.0],
+        ]);
+        let r = n + m;
+        let s = n - m;
+
+        assert_eq!(r[0][0], 12.0);
+        assert_eq!(r[0][1], 14.0);
+        assert_eq!(r[0][2], 16.0);
+        assert_eq!(r[1][0], 18.0);
+        assert_eq!(r[1][1], 20.0);
+        assert_eq!(r[1][2], 22.0);
+        assert_eq!(r[2][0], 24.0);
+        assert_eq!(r[2][1], 26.0);
+        assert_eq!(r[2][2], 28.0);
+        assert_eq!(r[3][0], 30.0);
+        assert_eq!(r[3][1], 32.0);
+        assert_eq!(r[3][2], 34.0);
+
+        assert_eq!(s[0][0], 10.0);
+        assert_eq!(s[0][1], 10.0);
+        assert_eq!(s[0][2], 10.0);
+        assert_eq!(s[1][0], 10.0);
+        assert_eq!(s[1][1], 10.0);
+        assert_eq!(s[1][2], 10.0);
+        assert_eq!(s[2][0], 10.0);
+        assert_eq!(s[2][1], 10.0);
+        assert_eq!(s[2][2], 10.0);
+        assert_eq!(s[3][0], 10.0);
+        assert_eq!(s[3][1], 10.0);
+        assert_eq!(s[3][2], 10.0);
+
+        n += m;
+        n -= m;
+        n -= m;
+        assert_eq!(n[0][0], 10.0);
+        assert_eq!(n[0][1], 10.0);
+        assert_eq!(n[0][2], 10.0);
+        assert_eq!(n[1][0], 10.0);
+        assert_eq!(n[1][1], 10.0);
+        assert_eq!(n[1][2], 10.0);
+        assert_eq!(n[2][0], 10.0);
+        assert_eq!(n[2][1], 10.0);
+        assert_eq!(n[2][2], 10.0);
+        assert_eq!(n[3][0], 10.0);
+        assert_eq!(n[3][1], 10.0);
+        assert_eq!(n[3][2], 10.0);
+    }
+
+    #[test]
+    fn scalar_multiplication_and_division() {
+        let mut m = Mat4x3f32::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+            [10.0, 11.0, 12.0],
+        ]);
+        let n = m * 3.0;
+        let o = 3.0 * m;
+        let p = m / 2.0;
+
+        assert_eq!(n[0][0], 3.0);
+        assert_eq!(n[0][1], 6.0);
+        assert_eq!(n[0][2], 9.0);
+        assert_eq!(n[1][0], 12.0);
+        assert_eq!(n[1][1], 15.0);
+        assert_eq!(n[1][2], 18.0);
+        assert_eq!(n[2][0], 21.0);
+        assert_eq!(n[2][1], 24.0);
+        assert_eq!(n[2][2], 27.0);
+        assert_eq!(n[3][0], 30.0);
+        assert_eq!(n[3][1], 33.0);
+        assert_eq!(n[3][2], 36.0);
+
+        assert_eq!(o[0][0], 3.0);
+        assert_eq!(o[0][1], 6.0);
+        assert_eq!(o[0][2], 9.0);
+        assert_eq!(o[1][0], 12.0);
+        assert_eq!(o[1][1], 15.0);
+        assert_eq!(o[1][2], 18.0);
+        assert_eq!(o[2][0], 21.0);
+        assert_eq!(o[2][1], 24.0);
+        assert_eq!(o[2][2], 27.0);
+        assert_eq!(o[3][0], 30.0);
+        assert_eq!(o[3][1], 33.0);
+        assert_eq!(o[3][2], 36.0);
+
+        assert_eq!(p[0][0], 0.5);
+        assert_eq!(p[0][1], 1.0);
+        assert_eq!(p[0][2], 1.5);
+        assert_eq!(p[1][0], 2.0);
+        assert_eq!(p[1][1], 2.5);
+        assert_eq!(p[1][2], 3.0);
+        assert_eq!(p[2][0], 3.5);
+        assert_eq!(p[2][1], 4.0);
+        assert_eq!(p[2][2], 4.5);
+        assert_eq!(p[3][0], 5.0);
+        assert_eq!(p[3][1], 5.5);
+        assert_eq!(p[3][2], 6.0);
+
+        m *= 4.0;
+        m /= 2.0;
+        assert_eq!(m[0][0], 2.0);
+        assert_eq!(m[0][1], 4.0);
+        assert_eq!(m[0][2], 6.0);
+        assert_eq!(m[1][0], 8.0);
+        assert_eq!(m[1][1], 10.0);
+        assert_eq!(m[1][2], 12.0);
+        assert_eq!(m[2][0], 14.0);
+        assert_eq!(m[2][1], 16.0);
+        assert_eq!(m[2][2], 18.0);
+        assert_eq!(m[3][0], 20.0);
+        assert_eq!(m[3][1], 22.0);
+        assert_eq!(m[3][2], 24.0);
+    }
+
+    #[test]
+    fn matrix_multiplication() {
+        let m = Mat4x3f32::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+            [10.0, 11.0, 12.0],
+        ]);
+
+        let b = Mat3x2f32::new([[0.0, 1.0], [2.0, 3.0], [4.0, 0.0]]);
+        let r = m * b;
+        assert_eq!(r[0][0], 16.0);
+        assert_eq!(r[0][1], 7.0);
+        assert_eq!(r[1][0], 34.0);
+        assert_eq!(r[1][1], 19.0);
+        assert_eq!(r[2][0], 52.0);
+        assert_eq!(r[2][1], 31.0);
+        assert_eq!(r[3][0], 70.0);
+        assert_eq!(r[3][1], 43.0);
+
+        let b = Mat3f32::new([[1.0, 2.0, 3.0], [4.0, 5.0, 1.0], [2.0, 3.0, 4.0]]);
+        let r = m * b;
+        assert_eq!(r[0][0], 15.0);
+        assert_eq!(r[0][1], 21.0);
+        assert_eq!(r[0][2], 17.0);
+        assert_eq!(r[1][0], 36.0);
+        assert_eq!(r[1][1], 51.0);
+        assert_eq!(r[1][2], 41.0);
+        assert_eq!(r[2][0], 57.0);
+        assert_eq!(r[2][1], 81.0);
+        assert_eq!(r[2][2], 65.0);
+        assert_eq!(r[3][0], 78.0);
+        assert_eq!(r[3][1], 111.0);
+        assert_eq!(r[3][2], 89.0);
+
+        let b = Mat3x4f32::new([
+            [2.0, 3.0, 4.0, 5.0],
+            [6.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 2.0, 3.0],
+        ]);
+        let r = m * b;
+        assert_eq!(r[0][0], 29.0);
+        assert_eq!(r[0][1], 25.0);
+        assert_eq!(r[0][2], 16.0);
+        assert_eq!(r[0][3], 22.0);
+        assert_eq!(r[1][0], 68.0);
+        assert_eq!(r[1][1], 58.0);
+        assert_eq!(r[1][2], 43.0);
+        assert_eq!(r[1][3], 58.0);
+        assert_eq!(r[2][0], 107.0);
+        assert_eq!(r[2][1], 91.0);
+        assert_eq!(r[2][2], 70.0);
+        assert_eq!(r[2][3], 94.0);
+        assert_eq!(r[3][0], 146.0);
+        assert_eq!(r[3][1], 124.0);
+        assert_eq!(r[3][2], 97.0);
+        assert_eq!(r[3][3], 130.0);
+
+        let s = Mat4f32::new([
+            [-1.0, 0.0, 1.0, 2.0],
+            [3.0, -1.0, 0.0, 1.0],
+            [2.0, 3.0, -1.0, 0.0],
+            [1.0, 2.0, 3.0, -1.0],
+        ]);
+        let r = s * m;
+        assert_eq!(r[0][0], 26.0);
+        assert_eq!(r[0][1], 28.0);
+        assert_eq!(r[0][2], 30.0);
+        assert_eq!(r[1][0], 9.0);
+        assert_eq!(r[1][1], 12.0);
+        assert_eq!(r[1][2], 15.0);
+        assert_eq!(r[2][0], 7.0);
+        assert_eq!(r[2][1], 11.0);
+        assert_eq!(r[2][2], 15.0);
+        assert_eq!(r[3][0], 20.0);
+        assert_eq!(r[3][1], 25.0);
+        assert_eq!(r[3][2], 30.0);
+
+        let mut n = m;
+        n *= Mat3f32::identity();
+        assert_eq!(n[0][0], 1.0);
+        assert_eq!(n[0][1], 2.0);
+        assert_eq!(n[0][2], 3.0);
+        assert_eq!(n[1][0], 4.0);
+        assert_eq!(n[1][1], 5.0);
+        assert_eq!(n[1][2], 6.0);
+        assert_eq!(n[2][0], 7.0);
+        assert_eq!(n[2][1], 8.0);
+        assert_eq!(n[2][2], 9.0);
+        assert_eq!(n[3][0], 10.0);
+        assert_eq!(n[3][1], 11.0);
+        assert_eq!(n[3][2], 12.0);
+    }
+
+    #[test]
+    fn vector_multiplication() {
+        let m = Mat4x3f32::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+            [10.0, 11.0, 12.0],
+        ]);
+        let v = Vec3f32::new([2.0, 3.0, 4.0]);
+        let w = m * v;
+
+        assert_eq!(w[0], 20.0);
+        assert_eq!(w[1], 47.0);
+        assert_eq!(w[2], 74.0);
+        assert_eq!(w[3], 101.0);
+    }
+
+    #[test]
+    fn const_evaluation() {
+        const M: Mat4x3f32 = Mat4x3f32::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+            [10.0, 11.0, 12.0],
+        ]);
+        const T: Mat3x4f32 = M.transposed();
+        const S: Mat4x3f32 = M.sum(M).difference(M).scaled(4.0).divided(2.0);
+        const W: Vec4f32 = M.product_vec(Vec3f32::new([2.0, 3.0, 4.0]));
+
+        assert_eq!(T[0][0], 1.0);
+        assert_eq!(T[0][1], 4.0);
+        assert_eq!(T[0][2], 7.0);
+        assert_eq!(T[0][3], 10.0);
+        assert_eq!(T[1][0], 2.0);
+        assert_eq!(T[1][1], 5.0);
+        assert_eq!(T[1][2], 8.0);
+        assert_eq!(T[1][3], 11.0);
+        assert_eq!(T[2][0], 3.0);
+        assert_eq!(T[2][1], 6.0);
+        assert_eq!(T[2][2], 9.0);
+        assert_eq!(T[2][3], 12.0);
+
+        assert_eq!(S[0][0], 2.0);
+        assert_eq!(S[0][1], 4.0);
+        assert_eq!(S[0][2], 6.0);
+        assert_eq!(S[1][0], 8.0);
+        assert_eq!(S[1][1], 10.0);
+        assert_eq!(S[1][2], 12.0);
+        assert_eq!(S[2][0], 14.0);
+        assert_eq!(S[2][1], 16.0);
+        assert_eq!(S[2][2], 18.0);
+        assert_eq!(S[3][0], 20.0);
+        assert_eq!(S[3][1], 22.0);
+        assert_eq!(S[3][2], 24.0);
+
+        assert_eq!(W[0], 20.0);
+        assert_eq!(W[1], 47.0);
+        assert_eq!(W[2], 74.0);
+        assert_eq!(W[3], 101.0);
+    }
+
+    #[test]
+    fn flat_arrays() {
+        let m = Mat4x3f32::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+            [10.0, 11.0, 12.0],
+        ]);
+        let rows = m.as_row_major_flat();
+        let cols = m.as_col_major_flat();
+        let from_rows = Mat4x3f32::from_row_major_flat([
+            1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0,
+        ]);
+        let from_cols = Mat4x3f32::from_col_major_flat([
+            1.0, 4.0, 7.0, 10.0, 2.0, 5.0, 8.0, 11.0, 3.0, 6.0, 9.0, 12.0,
+        ]);
+
+        assert_eq!(rows[0], 1.0);
+        assert_eq!(rows[1], 2.0);
+        assert_eq!(rows[2], 3.0);
+        assert_eq!(rows[3], 4.0);
+        assert_eq!(rows[4], 5.0);
+        assert_eq!(rows[5], 6.0);
+        assert_eq!(rows[6], 7.0);
+        assert_eq!(rows[7], 8.0);
+        assert_eq!(rows[8], 9.0);
+        assert_eq!(rows[9], 10.0);
+        assert_eq!(rows[10], 11.0);
+        assert_eq!(rows[11], 12.0);
+
+        assert_eq!(cols[0], 1.0);
+        assert_eq!(cols[1], 4.0);
+        assert_eq!(cols[2], 7.0);
+        assert_eq!(cols[3], 10.0);
+        assert_eq!(cols[4], 2.0);
+        assert_eq!(cols[5], 5.0);
+        assert_eq!(cols[6], 8.0);
+        assert_eq!(cols[7], 11.0);
+        assert_eq!(cols[8], 3.0);
+        assert_eq!(cols[9], 6.0);
+        assert_eq!(cols[10], 9.0);
+        assert_eq!(cols[11], 12.0);
+
+        assert_eq!(from_rows[0][0], 1.0);
+        assert_eq!(from_rows[0][1], 2.0);
+        assert_eq!(from_rows[0][2], 3.0);
+        assert_eq!(from_rows[1][0], 4.0);
+        assert_eq!(from_rows[1][1], 5.0);
+        assert_eq!(from_rows[1][2], 6.0);
+        assert_eq!(from_rows[2][0], 7.0);
+        assert_eq!(from_rows[2][1], 8.0);
+        assert_eq!(from_rows[2][2], 9.0);
+        assert_eq!(from_rows[3][0], 10.0);
+        assert_eq!(from_rows[3][1], 11.0);
+        assert_eq!(from_rows[3][2], 12.0);
+
+        assert_eq!(from_cols[0][0], 1.0);
+        assert_eq!(from_cols[0][1], 2.0);
+        assert_eq!(from_cols[0][2], 3.0);
+        assert_eq!(from_cols[1][0], 4.0);
+        assert_eq!(from_cols[1][1], 5.0);
+        assert_eq!(from_cols[1][2], 6.0);
+        assert_eq!(from_cols[2][0], 7.0);
+        assert_eq!(from_cols[2][1], 8.0);
+        assert_eq!(from_cols[2][2], 9.0);
+        assert_eq!(from_cols[3][0], 10.0);
+        assert_eq!(from_cols[3][1], 11.0);
+        assert_eq!(from_cols[3][2], 12.0);
+    }
+
+    #[test]
+    fn precision_conversion() {
+        let m = Mat4x3f32::new([
+            [1.0, 2.0 / 3.0, 3.0],
+            [4.0 / 3.0, 5.0, 6.0 / 3.0],
+            [7.0, 8.0 / 3.0, 9.0],
+            [10.0 / 3.0, 11.0, 12.0 / 3.0],
+        ]);
+        let c = m.to_f64();
+
+        assert_eq!(c[0][0], m[0][0] as f64);
+        assert_eq!(c[0][1], m[0][1] as f64);
+        assert_eq!(c[0][2], m[0][2] as f64);
+        assert_eq!(c[1][0], m[1][0] as f64);
+        assert_eq!(c[1][1], m[1][1] as f64);
+        assert_eq!(c[1][2], m[1][2] as f64);
+        assert_eq!(c[2][0], m[2][0] as f64);
+        assert_eq!(c[2][1], m[2][1] as f64);
+        assert_eq!(c[2][2], m[2][2] as f64);
+        assert_eq!(c[3][0], m[3][0] as f64);
+        assert_eq!(c[3][1], m[3][1] as f64);
+        assert_eq!(c[3][2], m[3][2] as f64);
+    }
+
+    #[test]
+    fn rank() {
+        assert_eq!(
+            Mat4x3f32::new([
+                [2.5, 0.5, 0.0],
+                [0.0, 2.5, 0.5],
+                [0.5, 0.0, 2.5],
+                [-0.5, 0.5, 0.0]
+            ])
+            .rank(1e-6),
+            3
+        );
+        assert_eq!(
+            Mat4x3f32::new([
+                [2.0, 1.0, -1.0],
+                [-4.0, -2.0, 2.0],
+                [1.0, 0.5, -0.5],
+                [6.0, 3.0, -3.0]
+            ])
+            .rank(1e-6),
+            1
+        );
+        assert_eq!(Mat4x3f32::zero().rank(1e-6), 0);
+    }
+
+    #[test]
+    fn null_space() {
+        let m = Mat4x3f32::new([
+            [2.0, 1.0, -1.0],
+            [-4.0, -2.0, 2.0],
+            [1.0, 0.5, -0.5],
+            [6.0, 3.0, -3.0],
+        ]);
+        let basis = m.null_space(1e-6);
+        assert_eq!(basis.len(), 2);
+        for (i, v) in basis.iter().enumerate() {
+            assert!((m * *v).mag() < 1e-5);
+            assert!((v.mag() - 1.0).abs() < 1e-5);
+            for w in &basis[..i] {
+                assert!(v.dot(*w).abs() < 1e-5);
+            }
+        }
+
+        let m = Mat4x3f32::new([
+            [2.5, 0.5, 0.0],
+            [0.0, 2.5, 0.5],
+            [0.5, 0.0, 2.5],
+            [-0.5, 0.5, 0.0],
+        ]);
+        let basis = m.null_space(1e-6);
+        assert_eq!(basis.len(), 0);
+        for v in basis {
+            assert!((m * v).mag() < 1e-5);
+        }
+        assert_eq!(Mat4x3f32::zero().null_space(1e-6).len(), 3);
+    }
+
+    #[test]
+    fn condition_number() {
+        let m = Mat4x3f32::new([
+            [1.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0],
+            [0.0, 0.0, 4.0],
+            [0.0, 0.0, 0.0],
+        ]);
+        assert!((m.condition_number() - 5.25).abs() < 1e-4);
+        assert!(
+            Mat4x3f32::new([
+                [2.0, 1.0, -1.0],
+                [-4.0, -2.0, 2.0],
+                [1.0, 0.5, -0.5],
+                [6.0, 3.0, -3.0]
+            ])
+            .condition_number()
+                > 1e6
+        );
+        assert_eq!(Mat4x3f32::zero().condition_number(), f32::INFINITY);
+    }
+
+    #[test]
+    fn is_orthogonal() {
+        let m = Mat4x3f32::new([
+            [0.6, -0.8, 0.0],
+            [0.8, 0.6, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, 0.0],
+        ]);
+        assert!(m.is_orthogonal(1e-6));
+        assert!(!(2.0 * m).is_orthogonal(1e-6));
+        assert!(!Mat4x3f32::new([
+            [2.5, 0.5, 0.0],
+            [0.0, 2.5, 0.5],
+            [0.5, 0.0, 2.5],
+            [-0.5, 0.5, 0.0]
+        ])
+        .is_orthogonal(1e-6));
+    }
+}
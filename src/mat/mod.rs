@@ -1,6 +1,27 @@
+pub mod colmat4f32;
+pub mod colmat4f64;
+pub mod convention;
 pub mod mat2f32;
 pub mod mat2f64;
+pub mod mat2x3f32;
+pub mod mat2x3f64;
+pub mod mat2x4f32;
+pub mod mat2x4f64;
 pub mod mat3f32;
 pub mod mat3f64;
+pub mod mat3x2f32;
+pub mod mat3x2f64;
+pub mod mat3x4f32;
+pub mod mat3x4f64;
 pub mod mat4f32;
 pub mod mat4f64;
+pub mod mat4x2f32;
+pub mod mat4x2f64;
+pub mod mat4x3f32;
+pub mod mat4x3f64;
+pub mod symmat3f32;
+pub mod symmat3f64;
+pub mod symmat4f32;
+pub mod symmat4f64;
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,380 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+
+use crate::mat::mat3f32::Mat3f32;
+use crate::vec::vec3f32::Vec3f32;
+
+/// A symmetric 3x3 floating point matrix, storing only the 6 elements
+/// on and above the main diagonal. Useful for inertia tensors, covariance matrices
+/// and quadrics. Use Mat3f32 for everything not provided here
+/// and convert with from_mat3() and to_mat3().
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bitcode", derive(bitcode::Encode, bitcode::Decode))]
+pub struct SymMat3f32 {
+    /// The upper triangle in row major order: m00, m01, m02, m11, m12, m22,
+    /// where mij is the element in row i and column j.
+    pub elements: [f32; 6],
+}
+
+impl SymMat3f32 {
+    /// Creates a new matrix from the upper triangle in row major order (see elements).
+    pub const fn new(elements: [f32; 6]) -> Self {
+        Self { elements }
+    }
+
+    /// Creates a new matrix with all elements equal to 0.0.
+    pub const fn zero() -> Self {
+        Self::new([0.0, 0.0, 0.0, 0.0, 0.0, 0.0])
+    }
+
+    /// Creates a new identity matrix.
+    pub const fn identity() -> Self {
+        Self::new([1.0, 0.0, 0.0, 1.0, 0.0, 1.0])
+    }
+
+    /// Creates a new diagonal matrix with the components of diagonal
+    /// on the main diagonal and all other elements equal to 0.0.
+    pub const fn from_diagonal(diagonal: Vec3f32) -> Self {
+        let d = &diagonal.coords;
+        Self::new([d[0], 0.0, 0.0, d[1], 0.0, d[2]])
+    }
+
+    /// Creates the outer product of v with itself (v * v^T).
+    pub const fn outer_product(v: Vec3f32) -> Self {
+        let v = &v.coords;
+        Self::new([
+            v[0] * v[0],
+            v[0] * v[1],
+            v[0] * v[2],
+            v[1] * v[1],
+            v[1] * v[2],
+            v[2] * v[2],
+        ])
+    }
+
+    /// Creates a new matrix from the symmetric part of m, (m + m^T) / 2.
+    /// This is exactly m if m is symmetric.
+    pub const fn from_mat3(m: Mat3f32) -> Self {
+        let m = &m.rows;
+        Self::new([
+            m[0][0],
+            (m[0][1] + m[1][0]) * 0.5,
+            (m[0][2] + m[2][0]) * 0.5,
+            m[1][1],
+            (m[1][2] + m[2][1]) * 0.5,
+            m[2][2],
+        ])
+    }
+
+    /// Returns self as a full 3x3 matrix.
+    pub const fn to_mat3(&self) -> Mat3f32 {
+        let e = &self.elements;
+        Mat3f32::new([[e[0], e[1], e[2]], [e[1], e[3], e[4]], [e[2], e[4], e[5]]])
+    }
+
+    /// Returns the element in row row and column col.
+    /// Panics if row or col is out of bounds.
+    pub const fn element(&self, row: usize, col: usize) -> f32 {
+        self.elements[Self::element_index(row, col)]
+    }
+
+    /// Replaces the element in row row and column col,
+    /// which also replaces the element in row col and column row.
+    /// Panics if row or col is out of bounds.
+    pub const fn set_element(&mut self, row: usize, col: usize, value: f32) {
+        self.elements[Self::element_index(row, col)] = value;
+    }
+
+    /// Returns the index into elements of the element in row row and column col.
+    const fn element_index(row: usize, col: usize) -> usize {
+        assert!(row < 3 && col < 3);
+        let (i, j) = if row <= col { (row, col) } else { (col, row) };
+        // The rows above row i store 3, 3 - 1, ... elements
+        i * 3 - i * (i.saturating_sub(1)) / 2 + (j - i)
+    }
+
+    /// Returns the elements on the main diagonal as a vector.
+    pub const fn diagonal(&self) -> Vec3f32 {
+        let e = &self.elements;
+        Vec3f32::new([e[0], e[3], e[5]])
+    }
+
+    /// Returns the trace of self (the sum of the main diagonal).
+    pub const fn trace(&self) -> f32 {
+        let e = &self.elements;
+        e[0] + e[3] + e[5]
+    }
+
+    /// Returns the determinant of self.
+    pub const fn determinant(&self) -> f32 {
+        self.to_mat3().determinant()
+    }
+
+    /// Returns the inverse of self, which is symmetric as well.
+    /// If self is not invertible, the result contains non-finite values.
+    /// Use try_inverse() to check for invertibility.
+    pub const fn inverse(&self) -> Self {
+        Self::from_mat3(self.to_mat3().inverse())
+    }
+
+    /// Returns the inverse of self, or None if the absolute value
+    /// of the determinant is less than or equal to tolerance.
+    pub const fn try_inverse(&self, tolerance: f32) -> Option<Self> {
+        match self.to_mat3().try_inverse(tolerance) {
+            Some(inverse) => Some(Self::from_mat3(inverse)),
+            None => None,
+        }
+    }
+
+    /// Evaluates the quadratic form v^T * self * v.
+    pub const fn quadratic_form(&self, v: Vec3f32) -> f32 {
+        let e = &self.elements;
+        let v = &v.coords;
+        e[0] * v[0] * v[0]
+            + e[3] * v[1] * v[1]
+            + e[5] * v[2] * v[2]
+            + 2.0 * (e[1] * v[0] * v[1] + e[2] * v[0] * v[2] + e[4] * v[1] * v[2])
+    }
+
+    /// Returns the sum of self and rhs.
+    /// Same as the + operator, but usable in const contexts.
+    pub const fn sum(&self, rhs: Self) -> Self {
+        let a = &self.elements;
+        let b = &rhs.elements;
+        Self::new([
+            a[0] + b[0],
+            a[1] + b[1],
+            a[2] + b[2],
+            a[3] + b[3],
+            a[4] + b[4],
+            a[5] + b[5],
+        ])
+    }
+
+    /// Returns the difference of self and rhs.
+    /// Same as the - operator, but usable in const contexts.
+    pub const fn difference(&self, rhs: Self) -> Self {
+        let a = &self.elements;
+        let b = &rhs.elements;
+        Self::new([
+            a[0] - b[0],
+            a[1] - b[1],
+            a[2] - b[2],
+            a[3] - b[3],
+            a[4] - b[4],
+            a[5] - b[5],
+        ])
+    }
+
+    /// Returns self with every element multiplied by scalar.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn scaled(&self, scalar: f32) -> Self {
+        let e = &self.elements;
+        Self::new([
+            e[0] * scalar,
+            e[1] * scalar,
+            e[2] * scalar,
+            e[3] * scalar,
+            e[4] * scalar,
+            e[5] * scalar,
+        ])
+    }
+
+    /// Returns self with every element divided by scalar.
+    /// Same as the / operator, but usable in const contexts.
+    pub const fn divided(&self, scalar: f32) -> Self {
+        let e = &self.elements;
+        Self::new([
+            e[0] / scalar,
+            e[1] / scalar,
+            e[2] / scalar,
+            e[3] / scalar,
+            e[4] / scalar,
+            e[5] / scalar,
+        ])
+    }
+
+    /// Returns the product of self and the column vector v.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn product_vec(&self, v: Vec3f32) -> Vec3f32 {
+        let e = &self.elements;
+        let v = &v.coords;
+        Vec3f32::new([
+            e[0] * v[0] + e[1] * v[1] + e[2] * v[2],
+            e[1] * v[0] + e[3] * v[1] + e[4] * v[2],
+            e[2] * v[0] + e[4] * v[1] + e[5] * v[2],
+        ])
+    }
+}
+
+impl Add<SymMat3f32> for SymMat3f32 {
+    type Output = SymMat3f32;
+    fn add(self, rhs: SymMat3f32) -> Self::Output {
+        self.sum(rhs)
+    }
+}
+
+impl AddAssign<SymMat3f32> for SymMat3f32 {
+    fn add_assign(&mut self, rhs: SymMat3f32) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<SymMat3f32> for SymMat3f32 {
+    type Output = SymMat3f32;
+    fn sub(self, rhs: SymMat3f32) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl SubAssign<SymMat3f32> for SymMat3f32 {
+    fn sub_assign(&mut self, rhs: SymMat3f32) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<f32> for SymMat3f32 {
+    type Output = SymMat3f32;
+    fn mul(self, scalar: f32) -> Self::Output {
+        self.scaled(scalar)
+    }
+}
+
+impl Mul<SymMat3f32> for f32 {
+    type Output = SymMat3f32;
+    fn mul(self, m: SymMat3f32) -> Self::Output {
+        m * self
+    }
+}
+
+impl MulAssign<f32> for SymMat3f32 {
+    fn mul_assign(&mut self, scalar: f32) {
+        *self = *self * scalar;
+    }
+}
+
+impl Div<f32> for SymMat3f32 {
+    type Output = SymMat3f32;
+    fn div(self, scalar: f32) -> Self::Output {
+        self.divided(scalar)
+    }
+}
+
+impl DivAssign<f32> for SymMat3f32 {
+    fn div_assign(&mut self, scalar: f32) {
+        *self = *self / scalar;
+    }
+}
+
+impl Mul<Vec3f32> for SymMat3f32 {
+    type Output = Vec3f32;
+    fn mul(self, v: Vec3f32) -> Self::Output {
+        self.product_vec(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mat::mat3f32::Mat3f32;
+    use crate::mat::symmat3f32::SymMat3f32;
+    use crate::mat::testing::assert_mat_close;
+    use crate::vec::vec3f32::Vec3f32;
+
+    #[test]
+    fn conversions() {
+        let m = Mat3f32::new([[4.0, 1.0, -2.0], [1.0, 3.0, 0.5], [-2.0, 0.5, 5.0]]);
+        let s = SymMat3f32::from_mat3(m);
+        assert_eq!(s.to_mat3().as_row_major(), m.as_row_major());
+        assert_eq!(
+            std::mem::size_of::<SymMat3f32>(),
+            6 * std::mem::size_of::<f32>()
+        );
+
+        // Non-symmetric matrices are symmetrized
+        let s = SymMat3f32::from_mat3(Mat3f32::new([
+            [1.0, 2.0, 3.0],
+            [4.0, 5.0, 6.0],
+            [7.0, 8.0, 9.0],
+        ]));
+        assert_eq!(s.elements, [1.0, 3.0, 5.0, 5.0, 7.0, 9.0]);
+
+        assert_mat_close(SymMat3f32::identity(), Mat3f32::identity(), 1e-5);
+        assert_mat_close(SymMat3f32::zero(), Mat3f32::zero(), 1e-5);
+        let v = Vec3f32::new([1.0, -2.0, 0.5]);
+        assert_mat_close(
+            SymMat3f32::from_diagonal(v),
+            Mat3f32::from_diagonal(v),
+            1e-5,
+        );
+        assert_mat_close(
+            SymMat3f32::outer_product(v),
+            Mat3f32::outer_product(v, v),
+            1e-5,
+        );
+    }
+
+    #[test]
+    fn elements() {
+        let m = Mat3f32::new([[4.0, 1.0, -2.0], [1.0, 3.0, 0.5], [-2.0, 0.5, 5.0]]);
+        let mut s = SymMat3f32::from_mat3(m);
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_eq!(s.element(i, j), m[i][j]);
+            }
+        }
+        s.set_element(2, 0, 9.0);
+        assert_eq!(s.element(0, 2), 9.0);
+        assert_eq!(s.to_mat3()[2][0], 9.0);
+        assert_eq!(s.diagonal().coords, m.diagonal().coords);
+        assert_eq!(s.trace(), m.trace());
+    }
+
+    #[test]
+    #[should_panic]
+    fn element_out_of_bounds() {
+        SymMat3f32::identity().element(0, 3);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (a, b) = (
+            Mat3f32::new([[4.0, 1.0, -2.0], [1.0, 3.0, 0.5], [-2.0, 0.5, 5.0]]),
+            Mat3f32::new([[1.0, -1.0, 0.0], [-1.0, 2.0, 3.0], [0.0, 3.0, -1.0]]),
+        );
+        let (sa, sb) = (SymMat3f32::from_mat3(a), SymMat3f32::from_mat3(b));
+        assert_mat_close(sa + sb, a + b, 1e-5);
+        assert_mat_close(sa - sb, a - b, 1e-5);
+        assert_mat_close(sa * 2.0, a * 2.0, 1e-5);
+        assert_mat_close(2.0 * sa, a * 2.0, 1e-5);
+        assert_mat_close(sa / 4.0, a / 4.0, 1e-5);
+
+        let mut s = sa;
+        s += sb;
+        s -= sa * 0.5;
+        s *= 3.0;
+        s /= 2.0;
+        assert_mat_close(s, (a + b - a * 0.5) * 1.5, 1e-5);
+
+        let v = Vec3f32::new([1.0, -2.0, 0.5]);
+        assert!((sa * v - a * v).mag() < 1e-5);
+    }
+
+    #[test]
+    fn quadratic_form() {
+        let m = Mat3f32::new([[4.0, 1.0, -2.0], [1.0, 3.0, 0.5], [-2.0, 0.5, 5.0]]);
+        let s = SymMat3f32::from_mat3(m);
+        let v = Vec3f32::new([1.0, -2.0, 0.5]);
+        assert!((s.quadratic_form(v) - v.dot(m * v)).abs() < 1e-5);
+        assert_eq!(SymMat3f32::identity().quadratic_form(v), v.mag_squared());
+    }
+
+    #[test]
+    fn inverse() {
+        let m = Mat3f32::new([[4.0, 1.0, -2.0], [1.0, 3.0, 0.5], [-2.0, 0.5, 5.0]]);
+        let s = SymMat3f32::from_mat3(m);
+        assert!((s.determinant() - m.determinant()).abs() < 1e-4);
+        assert_mat_close(s.inverse(), m.inverse(), 1e-5);
+        assert_mat_close(s.try_inverse(1e-6).unwrap(), m.inverse(), 1e-5);
+        assert!(SymMat3f32::zero().try_inverse(1e-6).is_none());
+    }
+}
@@ -0,0 +1,344 @@
+//! Assertions shared by the tests of the matrix types.
+//! They compare in double precision, so the same helpers work for f32 and f64 matrices.
+
+use std::fmt::Debug;
+
+use crate::mat::colmat4f32::ColMat4f32;
+use crate::mat::colmat4f64::ColMat4f64;
+use crate::mat::mat2f32::Mat2f32;
+use crate::mat::mat2f64::Mat2f64;
+use crate::mat::mat3f32::Mat3f32;
+use crate::mat::mat3f64::Mat3f64;
+use crate::mat::mat4f32::Mat4f32;
+use crate::mat::mat4f64::Mat4f64;
+use crate::mat::symmat3f32::SymMat3f32;
+use crate::mat::symmat3f64::SymMat3f64;
+use crate::mat::symmat4f32::SymMat4f32;
+use crate::mat::symmat4f64::SymMat4f64;
+use crate::vec::vec2f32::Vec2f32;
+use crate::vec::vec2f64::Vec2f64;
+use crate::vec::vec3f32::Vec3f32;
+use crate::vec::vec3f64::Vec3f64;
+use crate::vec::vec4f32::Vec4f32;
+use crate::vec::vec4f64::Vec4f64;
+
+/// A square matrix the shared assertions can check.
+pub(crate) trait TestMatrix: Copy + Debug {
+    /// Tolerance for values that are exact up to rounding errors,
+    /// like the orthonormality of a computed basis.
+    const TOLERANCE: f64;
+    /// Tolerance for residuals that accumulate the rounding errors of a decomposition,
+    /// like the difference between a matrix and the product of its factors.
+    const RESIDUAL_TOLERANCE: f64;
+
+    /// Returns the rows of self in double precision.
+    fn to_rows(&self) -> Vec<Vec<f64>>;
+}
+
+impl TestMatrix for Mat2f32 {
+    const TOLERANCE: f64 = 1e-5;
+    const RESIDUAL_TOLERANCE: f64 = 1e-4;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.rows)
+    }
+}
+
+impl TestMatrix for Mat2f64 {
+    const TOLERANCE: f64 = 1e-12;
+    const RESIDUAL_TOLERANCE: f64 = 1e-12;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.rows)
+    }
+}
+
+impl TestMatrix for Mat3f32 {
+    const TOLERANCE: f64 = 1e-5;
+    const RESIDUAL_TOLERANCE: f64 = 1e-4;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.rows)
+    }
+}
+
+impl TestMatrix for Mat3f64 {
+    const TOLERANCE: f64 = 1e-12;
+    const RESIDUAL_TOLERANCE: f64 = 1e-12;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.rows)
+    }
+}
+
+impl TestMatrix for Mat4f32 {
+    const TOLERANCE: f64 = 1e-5;
+    const RESIDUAL_TOLERANCE: f64 = 1e-4;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.rows)
+    }
+}
+
+impl TestMatrix for Mat4f64 {
+    const TOLERANCE: f64 = 1e-12;
+    const RESIDUAL_TOLERANCE: f64 = 1e-12;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.rows)
+    }
+}
+
+impl TestMatrix for ColMat4f32 {
+    const TOLERANCE: f64 = 1e-5;
+    const RESIDUAL_TOLERANCE: f64 = 1e-4;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.as_row_major())
+    }
+}
+
+impl TestMatrix for ColMat4f64 {
+    const TOLERANCE: f64 = 1e-12;
+    const RESIDUAL_TOLERANCE: f64 = 1e-12;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.as_row_major())
+    }
+}
+
+impl TestMatrix for SymMat3f32 {
+    const TOLERANCE: f64 = 1e-5;
+    const RESIDUAL_TOLERANCE: f64 = 1e-4;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.to_mat3().rows)
+    }
+}
+
+impl TestMatrix for SymMat3f64 {
+    const TOLERANCE: f64 = 1e-12;
+    const RESIDUAL_TOLERANCE: f64 = 1e-12;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.to_mat3().rows)
+    }
+}
+
+impl TestMatrix for SymMat4f32 {
+    const TOLERANCE: f64 = 1e-5;
+    const RESIDUAL_TOLERANCE: f64 = 1e-4;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.to_mat4().rows)
+    }
+}
+
+impl TestMatrix for SymMat4f64 {
+    const TOLERANCE: f64 = 1e-12;
+    const RESIDUAL_TOLERANCE: f64 = 1e-12;
+
+    fn to_rows(&self) -> Vec<Vec<f64>> {
+        widen_rows(&self.to_mat4().rows)
+    }
+}
+
+/// A vector the shared assertions can check.
+pub(crate) trait TestVector: Copy + Debug {
+    /// Returns the coordinates of self in double precision.
+    fn to_coords(&self) -> Vec<f64>;
+}
+
+impl TestVector for Vec2f32 {
+    fn to_coords(&self) -> Vec<f64> {
+        widen_coords(&self.coords)
+    }
+}
+
+impl TestVector for Vec2f64 {
+    fn to_coords(&self) -> Vec<f64> {
+        widen_coords(&self.coords)
+    }
+}
+
+impl TestVector for Vec3f32 {
+    fn to_coords(&self) -> Vec<f64> {
+        widen_coords(&self.coords)
+    }
+}
+
+impl TestVector for Vec3f64 {
+    fn to_coords(&self) -> Vec<f64> {
+        widen_coords(&self.coords)
+    }
+}
+
+impl TestVector for Vec4f32 {
+    fn to_coords(&self) -> Vec<f64> {
+        widen_coords(&self.coords)
+    }
+}
+
+impl TestVector for Vec4f64 {
+    fn to_coords(&self) -> Vec<f64> {
+        widen_coords(&self.coords)
+    }
+}
+
+fn widen_coords<T: Copy + Into<f64>>(coords: &[T]) -> Vec<f64> {
+    coords.iter().map(|&x| x.into()).collect()
+}
+
+fn widen_rows<T: Copy + Into<f64>, const N: usize>(rows: &[[T; N]]) -> Vec<Vec<f64>> {
+    rows.iter().map(|row| widen_coords(row)).collect()
+}
+
+/// Returns a general 4x4 matrix without special structure.
+pub(crate) fn sample_mat4() -> Mat4f64 {
+    Mat4f64::new([
+        [2.0, 0.5, -1.0, 3.0],
+        [1.0, 3.0, 0.0, -2.0],
+        [0.0, -1.5, 1.0, 4.0],
+        [0.5, 0.0, 2.0, 1.0],
+    ])
+}
+
+/// Asserts that a and b have the same size and that their elements
+/// differ by less than tolerance.
+pub(crate) fn assert_mat_close(a: impl TestMatrix, b: impl TestMatrix, tolerance: impl Into<f64>) {
+    assert_rows_close(&a.to_rows(), &b.to_rows(), tolerance.into());
+}
+
+/// Asserts that the columns of m are orthonormal (m^T * m = I).
+pub(crate) fn assert_orthonormal<M: TestMatrix>(m: M) {
+    let rows = m.to_rows();
+    for i in 0..rows.len() {
+        for j in 0..rows.len() {
+            let dot: f64 = rows.iter().map(|row| row[i] * row[j]).sum();
+            let expected = if i == j { 1.0 } else { 0.0 };
+            assert!(
+                (dot - expected).abs() < M::TOLERANCE,
+                "{:?} is not orthonormal",
+                m
+            );
+        }
+    }
+}
+
+/// Asserts that m * vectors = vectors * diag(values), that the values are sorted in
+/// descending order and that vectors is a rotation matrix.
+pub(crate) fn assert_eigen<M: TestMatrix>(m: M, values: impl TestVector, vectors: M) {
+    let values = values.to_coords();
+    for pair in values.windows(2) {
+        assert!(pair[0] >= pair[1], "{:?} is not sorted", values);
+    }
+    assert_rotation(vectors);
+    assert_rows_close(
+        &product(&m.to_rows(), &vectors.to_rows()),
+        &scale_columns(&vectors.to_rows(), &values),
+        M::RESIDUAL_TOLERANCE,
+    );
+}
+
+/// Asserts that svd is a singular value decomposition (U, sigma, V) of m with
+/// m = U * diag(sigma) * V^T, rotation matrices U and V and sigma sorted by descending
+/// absolute value.
+pub(crate) fn assert_svd<M: TestMatrix>(m: M, svd: (M, impl TestVector, M)) {
+    let (u, sigma, v) = svd;
+    assert_rotation(u);
+    assert_rotation(v);
+    let sigma = sigma.to_coords();
+    for pair in sigma.windows(2) {
+        assert!(
+            pair[0] >= pair[1].abs() - M::TOLERANCE,
+            "{:?} is not sorted",
+            sigma
+        );
+    }
+    let u_sigma = scale_columns(&u.to_rows(), &sigma);
+    assert_rows_close(
+        &product(&u_sigma, &transposed(&v.to_rows())),
+        &m.to_rows(),
+        M::RESIDUAL_TOLERANCE,
+    );
+}
+
+/// Asserts that polar is a polar decomposition (rotation, stretch) of m with
+/// m = rotation * stretch, a rotation matrix and an exactly symmetric stretch.
+pub(crate) fn assert_polar<M: TestMatrix>(m: M, polar: (M, M)) {
+    let (rotation, stretch) = polar;
+    assert_rotation(rotation);
+    let stretch = stretch.to_rows();
+    assert_eq!(stretch, transposed(&stretch));
+    assert_rows_close(
+        &product(&rotation.to_rows(), &stretch),
+        &m.to_rows(),
+        M::RESIDUAL_TOLERANCE,
+    );
+}
+
+/// Asserts that m is orthonormal with a determinant of +1.
+fn assert_rotation<M: TestMatrix>(m: M) {
+    assert_orthonormal(m);
+    let determinant = determinant(&m.to_rows());
+    assert!(
+        (determinant - 1.0).abs() < M::TOLERANCE,
+        "{:?} is not a rotation",
+        m
+    );
+}
+
+/// Multiplies the columns of rows by factors (rows * diag(factors)).
+fn scale_columns(rows: &[Vec<f64>], factors: &[f64]) -> Vec<Vec<f64>> {
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(factors)
+                .map(|(x, factor)| x * factor)
+                .collect()
+        })
+        .collect()
+}
+
+fn transposed(rows: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    (0..rows[0].len())
+        .map(|j| rows.iter().map(|row| row[j]).collect())
+        .collect()
+}
+
+fn product(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    a.iter()
+        .map(|row| {
+            (0..b[0].len())
+                .map(|j| row.iter().zip(b).map(|(x, b_row)| x * b_row[j]).sum())
+                .collect()
+        })
+        .collect()
+}
+
+/// Laplace expansion along the first row.
+fn determinant(rows: &[Vec<f64>]) -> f64 {
+    if rows.len() == 1 {
+        return rows[0][0];
+    }
+    let mut sign = 1.0;
+    let mut sum = 0.0;
+    for (j, x) in rows[0].iter().enumerate() {
+        let minor: Vec<Vec<f64>> = rows[1..]
+            .iter()
+            .map(|row| [&row[..j], &row[j + 1..]].concat())
+            .collect();
+        sum += sign * x * determinant(&minor);
+        sign = -sign;
+    }
+    sum
+}
+
+fn assert_rows_close(a: &[Vec<f64>], b: &[Vec<f64>], tolerance: f64) {
+    assert_eq!(a.len(), b.len());
+    for (row_a, row_b) in a.iter().zip(b) {
+        for (x, y) in row_a.iter().zip(row_b) {
+            assert!((x - y).abs() < tolerance, "{:?} != {:?}", a, b);
+        }
+    }
+}
@@ -10,17 +10,17 @@ pub struct Vec2f64 {
 
 impl Vec2f64 {
     /// Create a new vector with user defined components.
-    pub fn new(coords: [f64; 2]) -> Self {
+    pub const fn new(coords: [f64; 2]) -> Self {
         Self { coords }
     }
 
     /// Create a new vector with all components equal to 0.0.
-    pub fn zero() -> Self {
+    pub const fn zero() -> Self {
         Self::new([0.0, 0.0])
     }
 
     /// Create a new vector with all components equal to 1.0
-    pub fn ones() -> Self {
+    pub const fn ones() -> Self {
         Self::new([1.0, 1.0])
     }
 
@@ -31,8 +31,9 @@ impl Vec2f64 {
 
     /// The magnitude of the vector (also known as length), but squared.
     /// This is faster to compute than mag() and useful in some situations.
-    pub fn mag_squared(&self) -> f64 {
-        self[0] * self[0] + self[1] * self[1]
+    pub const fn mag_squared(&self) -> f64 {
+        let a = &self.coords;
+        a[0] * a[0] + a[1] * a[1]
     }
 
     /// Normalizes self
@@ -50,8 +51,54 @@ impl Vec2f64 {
     }
 
     /// Calculate the dot product between self and other.
-    pub fn dot(&self, other: Self) -> f64 {
-        self[0] * other[0] + self[1] * other[1]
+    pub const fn dot(&self, other: Self) -> f64 {
+        let a = &self.coords;
+        let b = &other.coords;
+        a[0] * b[0] + a[1] * b[1]
+    }
+
+    /// Orthonormalizes the vectors of basis in place using the modified
+    /// Gram-Schmidt process: the first vector keeps its direction, every following
+    /// vector is made orthogonal to the ones before it, then all are normalized.
+    /// Linearly dependent vectors result in NaN components.
+    pub fn gram_schmidt(basis: &mut [Vec2f64]) {
+        for i in 0..basis.len() {
+            let mut v = basis[i];
+            for done in basis[..i].iter() {
+                v -= v.dot(*done) * *done;
+            }
+            basis[i] = v.normed();
+        }
+    }
+
+    /// Returns the sum of self and rhs.
+    /// Same as the + operator, but usable in const contexts.
+    pub const fn sum(&self, rhs: Self) -> Self {
+        let a = &self.coords;
+        let b = &rhs.coords;
+        Self::new([a[0] + b[0], a[1] + b[1]])
+    }
+
+    /// Returns the difference of self and rhs.
+    /// Same as the - operator, but usable in const contexts.
+    pub const fn difference(&self, rhs: Self) -> Self {
+        let a = &self.coords;
+        let b = &rhs.coords;
+        Self::new([a[0] - b[0], a[1] - b[1]])
+    }
+
+    /// Returns self with every component multiplied by scalar.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn scaled(&self, scalar: f64) -> Self {
+        let a = &self.coords;
+        Self::new([a[0] * scalar, a[1] * scalar])
+    }
+
+    /// Returns self with every component divided by scalar.
+    /// Same as the / operator, but usable in const contexts.
+    pub const fn divided(&self, scalar: f64) -> Self {
+        let a = &self.coords;
+        Self::new([a[0] / scalar, a[1] / scalar])
     }
 }
 
@@ -71,10 +118,8 @@ impl IndexMut<usize> for Vec2f64 {
 
 impl Add<Vec2f64> for Vec2f64 {
     type Output = Vec2f64;
-    fn add(mut self, rhs: Vec2f64) -> Self::Output {
-        self[0] += rhs[0];
-        self[1] += rhs[1];
-        self
+    fn add(self, rhs: Vec2f64) -> Self::Output {
+        self.sum(rhs)
     }
 }
 
@@ -86,10 +131,8 @@ impl AddAssign<Vec2f64> for Vec2f64 {
 
 impl Sub<Vec2f64> for Vec2f64 {
     type Output = Vec2f64;
-    fn sub(mut self, rhs: Vec2f64) -> Self::Output {
-        self[0] -= rhs[0];
-        self[1] -= rhs[1];
-        self
+    fn sub(self, rhs: Vec2f64) -> Self::Output {
+        self.difference(rhs)
     }
 }
 
@@ -102,10 +145,8 @@ impl SubAssign<Vec2f64> for Vec2f64 {
 impl Mul<Vec2f64> for f64 {
     type Output = Vec2f64;
 
-    fn mul(self, mut v: Vec2f64) -> Self::Output {
-        v[0] *= self;
-        v[1] *= self;
-        v
+    fn mul(self, v: Vec2f64) -> Self::Output {
+        v.scaled(self)
     }
 }
 
@@ -113,31 +154,27 @@ impl Mul<f64> for Vec2f64 {
     type Output = Vec2f64;
 
     fn mul(self, scalar: f64) -> Self::Output {
-        scalar * self
+        self.scaled(scalar)
     }
 }
 
 impl MulAssign<f64> for Vec2f64 {
     fn mul_assign(&mut self, scalar: f64) {
-        self[0] *= scalar;
-        self[1] *= scalar;
+        *self = self.scaled(scalar);
     }
 }
 
 impl Div<f64> for Vec2f64 {
     type Output = Vec2f64;
 
-    fn div(mut self, scalar: f64) -> Self::Output {
-        self[0] /= scalar;
-        self[1] /= scalar;
-        self
+    fn div(self, scalar: f64) -> Self::Output {
+        self.divided(scalar)
     }
 }
 
 impl DivAssign<f64> for Vec2f64 {
     fn div_assign(&mut self, scalar: f64) {
-        self[0] /= scalar;
-        self[1] /= scalar;
+        *self = self.divided(scalar);
     }
 }
 
@@ -302,4 +339,48 @@ mod tests {
         assert_eq!(v[0], 6.0);
         assert_eq!(v[1], -3.0);
     }
+
+    #[test]
+    fn const_evaluation() {
+        const V: Vec2f64 = Vec2f64::new([4.0, 7.0]);
+        const W: Vec2f64 = Vec2f64::new([-2.0, 10.0]);
+        const SUM: Vec2f64 = V.sum(W);
+        const DIFF: Vec2f64 = V.difference(W);
+        const SCALED: Vec2f64 = V.scaled(3.0);
+        const DIVIDED: Vec2f64 = Vec2f64::ones().divided(2.0);
+        const DOT: f64 = V.dot(W);
+        const MAG_SQUARED: f64 = V.mag_squared();
+
+        assert_eq!(SUM[0], 2.0);
+        assert_eq!(SUM[1], 17.0);
+        assert_eq!(DIFF[0], 6.0);
+        assert_eq!(DIFF[1], -3.0);
+        assert_eq!(SCALED[0], 12.0);
+        assert_eq!(SCALED[1], 21.0);
+        assert_eq!(DIVIDED[0], 0.5);
+        assert_eq!(DIVIDED[1], 0.5);
+        assert_eq!(DOT, 62.0);
+        assert_eq!(MAG_SQUARED, 65.0);
+    }
+
+    #[test]
+    fn gram_schmidt() {
+        let mut basis = [Vec2f64::new([2.0, 1.0]), Vec2f64::new([1.0, 2.0])];
+        let first = basis[0].normed();
+        Vec2f64::gram_schmidt(&mut basis);
+
+        for i in 0..2 {
+            assert!((basis[i].mag() - 1.0).abs() < 1e-12);
+            for j in 0..i {
+                assert!(basis[i].dot(basis[j]).abs() < 1e-12);
+            }
+        }
+        assert!((basis[0] - first).mag() < 1e-12);
+
+        // Fewer vectors than dimensions
+        let mut pair = [Vec2f64::ones(), Vec2f64::new([1.0, 0.0])];
+        Vec2f64::gram_schmidt(&mut pair);
+        assert!(pair[0].dot(pair[1]).abs() < 1e-12);
+        assert!((pair[1].mag() - 1.0).abs() < 1e-12);
+    }
 }
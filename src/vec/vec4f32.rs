@@ -10,17 +10,17 @@ pub struct Vec4f32 {
 
 impl Vec4f32 {
     /// Create a new vector with user defined components.
-    pub fn new(coords: [f32; 4]) -> Self {
+    pub const fn new(coords: [f32; 4]) -> Self {
         Self { coords }
     }
 
     /// Create a new vector with all components equal to 0.0.
-    pub fn zero() -> Self {
+    pub const fn zero() -> Self {
         Self::new([0.0, 0.0, 0.0, 0.0])
     }
 
     /// Create a new vector with all components equal to 1.0.
-    pub fn ones() -> Self {
+    pub const fn ones() -> Self {
         Self::new([1.0, 1.0, 1.0, 1.0])
     }
 
@@ -31,8 +31,9 @@ impl Vec4f32 {
 
     /// The magnitude of the vector (also known as length), but squared.
     /// This is faster to compute than mag() and useful in some situations.
-    pub fn mag_squared(&self) -> f32 {
-        self[0] * self[0] + self[1] * self[1] + self[2] * self[2] + self[3] * self[3]
+    pub const fn mag_squared(&self) -> f32 {
+        let a = &self.coords;
+        a[0] * a[0] + a[1] * a[1] + a[2] * a[2] + a[3] * a[3]
     }
 
     /// Normalizes self
@@ -50,8 +51,54 @@ impl Vec4f32 {
     }
 
     /// Calculate the dot product between self and other.
-    pub fn dot(&self, other: Self) -> f32 {
-        self[0] * other[0] + self[1] * other[1] + self[2] * other[2] + self[3] * other[3]
+    pub const fn dot(&self, other: Self) -> f32 {
+        let a = &self.coords;
+        let b = &other.coords;
+        a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3]
+    }
+
+    /// Orthonormalizes the vectors of basis in place using the modified
+    /// Gram-Schmidt process: the first vector keeps its direction, every following
+    /// vector is made orthogonal to the ones before it, then all are normalized.
+    /// Linearly dependent vectors result in NaN components.
+    pub fn gram_schmidt(basis: &mut [Vec4f32]) {
+        for i in 0..basis.len() {
+            let mut v = basis[i];
+            for done in basis[..i].iter() {
+                v -= v.dot(*done) * *done;
+            }
+            basis[i] = v.normed();
+        }
+    }
+
+    /// Returns the sum of self and rhs.
+    /// Same as the + operator, but usable in const contexts.
+    pub const fn sum(&self, rhs: Self) -> Self {
+        let a = &self.coords;
+        let b = &rhs.coords;
+        Self::new([a[0] + b[0], a[1] + b[1], a[2] + b[2], a[3] + b[3]])
+    }
+
+    /// Returns the difference of self and rhs.
+    /// Same as the - operator, but usable in const contexts.
+    pub const fn difference(&self, rhs: Self) -> Self {
+        let a = &self.coords;
+        let b = &rhs.coords;
+        Self::new([a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]])
+    }
+
+    /// Returns self with every component multiplied by scalar.
+    /// Same as the * operator, but usable in const contexts.
+    pub const fn scaled(&self, scalar: f32) -> Self {
+        let a = &self.coords;
+        Self::new([a[0] * scalar, a[1] * scalar, a[2] * scalar, a[3] * scalar])
+    }
+
+    /// Returns self with every component divided by scalar.
+    /// Same as the / operator, but usable in const contexts.
+    pub const fn divided(&self, scalar: f32) -> Self {
+        let a = &self.coords;
+        Self::new([a[0] / scalar, a[1] / scalar, a[2] / scalar, a[3] / scalar])
     }
 }
 
@@ -71,12 +118,8 @@ impl IndexMut<usize> for Vec4f32 {
 
 impl Add<Vec4f32> for Vec4f32 {
     type Output = Vec4f32;
-    fn add(mut self, rhs: Vec4f32) -> Self::Output {
-        self[0] += rhs[0];
-        self[1] += rhs[1];
-        self[2] += rhs[2];
-        self[3] += rhs[3];
-        self
+    fn add(self, rhs: Vec4f32) -> Self::Output {
+        self.sum(rhs)
     }
 }
 
@@ -88,12 +131,8 @@ impl AddAssign<Vec4f32> for Vec4f32 {
 
 impl Sub<Vec4f32> for Vec4f32 {
     type Output = Vec4f32;
-    fn sub(mut self, rhs: Vec4f32) -> Self::Output {
-        self[0] -= rhs[0];
-        self[1] -= rhs[1];
-        self[2] -= rhs[2];
-        self[3] -= rhs[3];
-        self
+    fn sub(self, rhs: Vec4f32) -> Self::Output {
+        self.difference(rhs)
     }
 }
 
@@ -106,12 +145,8 @@ impl SubAssign<Vec4f32> for Vec4f32 {
 impl Mul<Vec4f32> for f32 {
     type Output = Vec4f32;
 
-    fn mul(self, mut v: Vec4f32) -> Self::Output {
-        v[0] *= self;
-        v[1] *= self;
-        v[2] *= self;
-        v[3] *= self;
-        v
+    fn mul(self, v: Vec4f32) -> Self::Output {
+        v.scaled(self)
     }
 }
 
@@ -119,37 +154,27 @@ impl Mul<f32> for Vec4f32 {
     type Output = Vec4f32;
 
     fn mul(self, scalar: f32) -> Self::Output {
-        scalar * self
+        self.scaled(scalar)
     }
 }
 
 impl MulAssign<f32> for Vec4f32 {
     fn mul_assign(&mut self, scalar: f32) {
-        self[0] *= scalar;
-        self[1] *= scalar;
-        self[2] *= scalar;
-        self[3] *= scalar;
+        *self = self.scaled(scalar);
     }
 }
 
 impl Div<f32> for Vec4f32 {
     type Output = Vec4f32;
 
-    fn div(mut self, scalar: f32) -> Self::Output {
-        self[0] /= scalar;
-        self[1] /= scalar;
-        self[2] /= scalar;
-        self[3] /= scalar;
-        self
+    fn div(self, scalar: f32) -> Self::Output {
+        self.divided(scalar)
     }
 }
 
 impl DivAssign<f32> for Vec4f32 {
     fn div_assign(&mut self, scalar: f32) {
-        self[0] /= scalar;
-        self[1] /= scalar;
-        self[2] /= scalar;
-        self[3] /= scalar;
+        *self = self.divided(scalar);
     }
 }
 
@@ -360,4 +385,61 @@ mod tests {
         assert_eq!(v[2], 3.0);
         assert_eq!(v[3], -1.0);
     }
+
+    #[test]
+    fn const_evaluation() {
+        const V: Vec4f32 = Vec4f32::new([4.0, 7.0, 5.0, 2.0]);
+        const W: Vec4f32 = Vec4f32::new([-2.0, 10.0, 2.0, 3.0]);
+        const SUM: Vec4f32 = V.sum(W);
+        const DIFF: Vec4f32 = V.difference(W);
+        const SCALED: Vec4f32 = V.scaled(3.0);
+        const DIVIDED: Vec4f32 = Vec4f32::ones().divided(2.0);
+        const DOT: f32 = V.dot(W);
+        const MAG_SQUARED: f32 = V.mag_squared();
+
+        assert_eq!(SUM[0], 2.0);
+        assert_eq!(SUM[1], 17.0);
+        assert_eq!(SUM[2], 7.0);
+        assert_eq!(SUM[3], 5.0);
+        assert_eq!(DIFF[0], 6.0);
+        assert_eq!(DIFF[1], -3.0);
+        assert_eq!(DIFF[2], 3.0);
+        assert_eq!(DIFF[3], -1.0);
+        assert_eq!(SCALED[0], 12.0);
+        assert_eq!(SCALED[1], 21.0);
+        assert_eq!(SCALED[2], 15.0);
+        assert_eq!(SCALED[3], 6.0);
+        assert_eq!(DIVIDED[0], 0.5);
+        assert_eq!(DIVIDED[1], 0.5);
+        assert_eq!(DIVIDED[2], 0.5);
+        assert_eq!(DIVIDED[3], 0.5);
+        assert_eq!(DOT, 78.0);
+        assert_eq!(MAG_SQUARED, 94.0);
+    }
+
+    #[test]
+    fn gram_schmidt() {
+        let mut basis = [
+            Vec4f32::new([2.0, 1.0, 0.5, 0.5]),
+            Vec4f32::new([1.0, 2.0, 1.0, 0.5]),
+            Vec4f32::new([1.0, 1.0, 2.0, 1.0]),
+            Vec4f32::new([1.0, 1.0, 1.0, 2.0]),
+        ];
+        let first = basis[0].normed();
+        Vec4f32::gram_schmidt(&mut basis);
+
+        for i in 0..4 {
+            assert!((basis[i].mag() - 1.0).abs() < 1e-5);
+            for j in 0..i {
+                assert!(basis[i].dot(basis[j]).abs() < 1e-5);
+            }
+        }
+        assert!((basis[0] - first).mag() < 1e-6);
+
+        // Fewer vectors than dimensions
+        let mut pair = [Vec4f32::ones(), Vec4f32::new([1.0, 0.0, 0.0, 0.0])];
+        Vec4f32::gram_schmidt(&mut pair);
+        assert!(pair[0].dot(pair[1]).abs() < 1e-6);
+        assert!((pair[1].mag() - 1.0).abs() < 1e-6);
+    }
 }